@@ -470,7 +470,7 @@ impl EnhancedFirecrawlClient {
         let response = self.execute_with_retry(request).await?;
 
         // Parse the response - this needs to match the actual API response format
-        let api_response: crate::api::models::crawl_model::CrawlStartResponse =
+        let _api_response: crate::api::models::crawl_model::CrawlStartResponse =
             response.json().await.map_err(|e| {
                 FirecrawlError::ApiError(crate::errors::ApiError::InvalidResponse(e.to_string()))
             })?;
@@ -510,15 +510,9 @@ impl EnhancedFirecrawlClient {
                         completed_at: Some(chrono::Utc::now()),
                         markdown: scrape_data.markdown.clone(),
                         html: scrape_data.html.clone().or(scrape_data.raw_html.clone()),
-                        metadata: crate::api::models::crawl_model::CrawlMetadata {
-                            keywords: None,
-                            robots: None,
-                            og_image: None,
-                            author: None,
-                            published_date: None,
-                            modified_date: None,
-                            site_name: None,
-                        },
+                        metadata: crate::api::models::crawl_model::CrawlMetadata::from_page_metadata(
+                            &scrape_data.metadata,
+                        ),
                     },
                 )
                 .collect();
@@ -553,14 +547,12 @@ impl FirecrawlClientFactory {
         FirecrawlClientBuilder::new()
             .base_url("https://api.firecrawl.dev")
             .build()
-            .map(|client| client.into())
     }
 
     /// Create a client from configuration
     pub fn from_config(config: &ApiConfig) -> FirecrawlResult<FirecrawlClient> {
         FirecrawlClientBuilder::from_config(config)
             .build()
-            .map(|client| client.into())
     }
 
     /// Create a client from environment variables
@@ -581,7 +573,6 @@ impl FirecrawlClientFactory {
             .compression(true)
             .http2(true)
             .build()
-            .map(|client| client.into())
     }
 
     /// Create a low-latency client optimized for single requests
@@ -594,7 +585,6 @@ impl FirecrawlClientFactory {
             .max_retries(1)
             .tcp_nodelay(true)
             .build()
-            .map(|client| client.into())
     }
 
     /// Create a client for testing purposes
@@ -606,7 +596,6 @@ impl FirecrawlClientFactory {
             .max_retries(1)
             .validate_certs(false)
             .build_for_testing()
-            .map(|client| client.into())
     }
 }
 
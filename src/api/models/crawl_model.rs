@@ -1,6 +1,7 @@
-use super::scrape_model::{OutputFormat, ScrapeData};
+use super::scrape_model::{Metadata, OutputFormat, ScrapeData, ScrapeOptions};
 use chrono;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // Re-export the CLI CrawlOptions to maintain consistency
 pub use crate::cli::CrawlOptions;
@@ -19,6 +20,55 @@ pub struct CrawlRequest {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub only_main_content: Option<bool>, // Extract only main content for each page
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_paths: Option<Vec<String>>, // URL path patterns to crawl
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_paths: Option<Vec<String>>, // URL path patterns to skip
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scrape_options: Option<ScrapeOptions>, // Options applied when scraping each page
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<CrawlWebhook>, // Endpoint the Firecrawl server calls back while crawling
+}
+
+// Crawl events the Firecrawl server can send to a webhook
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "camelCase")]
+pub enum CrawlWebhookEvent {
+    Started,   // The crawl job started
+    Page,      // A page was crawled
+    Completed, // The crawl job finished
+    Failed,    // The crawl job failed
+}
+
+// Webhook the Firecrawl server calls for crawl events, independently of this CLI
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrawlWebhook {
+    pub url: String, // Endpoint receiving the POST requests
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>, // Extra headers sent with every call
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>, // Echoed back in every payload
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<CrawlWebhookEvent>, // Events to send; the server sends all when empty
+}
+
+impl CrawlWebhook {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
 }
 
 // Response received when starting a new crawl job
@@ -54,6 +104,39 @@ pub struct CrawlMetadata {
     pub published_date: Option<chrono::DateTime<chrono::Utc>>,
     pub modified_date: Option<chrono::DateTime<chrono::Utc>>,
     pub site_name: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>, // Page title reported by the API
+    #[serde(default)]
+    pub status_code: Option<u16>, // HTTP status of the crawled page
+}
+
+impl CrawlMetadata {
+    // Build crawl metadata from the free-form page metadata returned by the API
+    pub fn from_page_metadata(metadata: &Metadata) -> Self {
+        let string_field = |key: &str| {
+            metadata
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        };
+
+        Self {
+            keywords: None,
+            robots: string_field("robots"),
+            og_image: string_field("ogImage"),
+            author: string_field("author"),
+            published_date: None,
+            modified_date: None,
+            site_name: string_field("ogSiteName"),
+            title: string_field("title"),
+            status_code: metadata
+                .extra
+                .get("statusCode")
+                .and_then(|v| v.as_u64())
+                .and_then(|code| u16::try_from(code).ok()),
+        }
+    }
 }
 
 // Enum representing the different states a crawl job can be in
@@ -69,6 +152,7 @@ pub enum CrawlState {
         status: String, // Current status text from API
         completed: u32, // Number of pages completed
         total: u32,     // Total number of pages expected
+        data: Vec<ScrapeData>, // Pages scraped so far
     },
     // Crawl job has completed successfully
     Completed {
@@ -99,6 +183,10 @@ pub struct CrawlRequestBuilder {
     limit: Option<u32>,
     formats: Option<Vec<OutputFormat>>,
     only_main_content: Option<bool>,
+    include_paths: Option<Vec<String>>,
+    exclude_paths: Option<Vec<String>>,
+    scrape_options: Option<ScrapeOptions>,
+    webhook: Option<CrawlWebhook>,
 }
 
 impl CrawlRequestBuilder {
@@ -108,6 +196,10 @@ impl CrawlRequestBuilder {
             limit: None,
             formats: None,
             only_main_content: None,
+            include_paths: None,
+            exclude_paths: None,
+            scrape_options: None,
+            webhook: None,
         }
     }
 
@@ -131,23 +223,42 @@ impl CrawlRequestBuilder {
         self
     }
 
+    pub fn include_paths(mut self, include_paths: Option<Vec<String>>) -> Self {
+        self.include_paths = include_paths;
+        self
+    }
+
+    pub fn exclude_paths(mut self, exclude_paths: Option<Vec<String>>) -> Self {
+        self.exclude_paths = exclude_paths;
+        self
+    }
+
+    pub fn scrape_options(mut self, scrape_options: Option<ScrapeOptions>) -> Self {
+        self.scrape_options = scrape_options;
+        self
+    }
+
+    pub fn webhook(mut self, webhook: Option<CrawlWebhook>) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
     pub fn build(self) -> Result<CrawlRequest, String> {
         Ok(CrawlRequest {
             url: self.url,
             limit: self.limit,
             formats: self.formats,
             only_main_content: self.only_main_content,
+            include_paths: self.include_paths,
+            exclude_paths: self.exclude_paths,
+            scrape_options: self.scrape_options,
+            webhook: self.webhook,
         })
     }
 }
 
 impl CrawlRequest {
     pub fn builder() -> CrawlRequestBuilder {
-        CrawlRequestBuilder {
-            url: String::new(),
-            limit: None,
-            formats: None,
-            only_main_content: None,
-        }
+        CrawlRequestBuilder::new(String::new())
     }
 }
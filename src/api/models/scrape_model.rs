@@ -23,7 +23,7 @@ pub enum ProxyType {
 }
 
 // Geographic location configuration for requests
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct Location {
     pub country: String, // Country code or name
@@ -113,6 +113,45 @@ pub enum Action {
                       // Click(ClickAction),
 }
 
+// Diff modes supported by the changeTracking format
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeTrackingMode {
+    GitDiff, // Unified text diff of the markdown
+    Json,    // Structured comparison of extracted fields
+}
+
+// Options for the changeTracking format object
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(tag = "type", rename = "changeTracking", rename_all = "camelCase")]
+pub struct ChangeTrackingFormat {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modes: Vec<ChangeTrackingMode>, // Diff modes to compute
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Value>, // JSON schema used by the json mode
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>, // Extraction prompt used by the json mode
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>, // Separate change history for this tag
+}
+
+// A requested format: either a plain format name or a format object with options
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ScrapeFormat {
+    Basic(OutputFormat),                   // e.g. "markdown"
+    ChangeTracking(ChangeTrackingFormat),  // {"type": "changeTracking", ...}
+}
+
+impl From<OutputFormat> for ScrapeFormat {
+    fn from(format: OutputFormat) -> Self {
+        ScrapeFormat::Basic(format)
+    }
+}
+
 // Main scrape request structure containing all configuration options
 #[derive(Serialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -121,7 +160,7 @@ pub struct ScrapeRequest {
 
     // --- Core configuration ---
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub formats: Vec<OutputFormat>, // Requested output formats
+    pub formats: Vec<ScrapeFormat>, // Requested output formats
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub only_main_content: Option<bool>, // Extract only main content (skip headers/footers)
@@ -190,6 +229,12 @@ pub struct ScrapeRequestBuilder {
     request: ScrapeRequest,
 }
 
+impl Default for ScrapeRequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ScrapeRequestBuilder {
     pub fn new() -> Self {
         Self {
@@ -203,7 +248,23 @@ impl ScrapeRequestBuilder {
     }
 
     pub fn formats(mut self, formats: Vec<OutputFormat>) -> Self {
-        self.request.formats = formats;
+        // Keep format objects added through dedicated builder methods
+        self.request
+            .formats
+            .retain(|format| !matches!(format, ScrapeFormat::Basic(_)));
+        self.request
+            .formats
+            .extend(formats.into_iter().map(ScrapeFormat::from));
+        self
+    }
+
+    pub fn change_tracking(mut self, change_tracking: ChangeTrackingFormat) -> Self {
+        self.request
+            .formats
+            .retain(|format| !matches!(format, ScrapeFormat::ChangeTracking(_)));
+        self.request
+            .formats
+            .push(ScrapeFormat::ChangeTracking(change_tracking));
         self
     }
 
@@ -292,7 +353,24 @@ impl ScrapeRequestBuilder {
         self
     }
 
-    pub fn build(self) -> ScrapeRequest {
+    pub fn build(mut self) -> ScrapeRequest {
+        // The API only computes change tracking against markdown content
+        let tracks_changes = self
+            .request
+            .formats
+            .iter()
+            .any(|format| matches!(format, ScrapeFormat::ChangeTracking(_)));
+        let has_markdown = self
+            .request
+            .formats
+            .iter()
+            .any(|format| matches!(format, ScrapeFormat::Basic(OutputFormat::Markdown)));
+        if tracks_changes && !has_markdown {
+            self.request
+                .formats
+                .insert(0, ScrapeFormat::Basic(OutputFormat::Markdown));
+        }
+
         self.request
     }
 }
@@ -462,12 +540,53 @@ pub struct JavaScriptReturn {
 #[serde(rename_all = "camelCase")]
 pub struct ChangeTracking {
     pub previous_scrape_at: Option<String>, // Timestamp of previous scrape
-    pub change_status: Option<String>,      // Status of changes detected
+    pub change_status: Option<ChangeStatus>, // Status of changes detected
     pub visibility: Option<String>,         // Visibility status
+    #[serde(default, deserialize_with = "deserialize_diff")]
     pub diff: Option<String>,               // Text difference/diff
     pub json: Option<HashMap<String, Value>>, // JSON difference data
 }
 
+// Result of comparing a page with its previous scrape
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeStatus {
+    New,     // Page was never scraped before
+    Same,    // Page content did not change
+    Changed, // Page content changed
+    Removed, // Page is no longer available
+}
+
+impl fmt::Display for ChangeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeStatus::New => write!(f, "new"),
+            ChangeStatus::Same => write!(f, "same"),
+            ChangeStatus::Changed => write!(f, "changed"),
+            ChangeStatus::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+// The git-diff mode is returned either as a plain string or as {"text": "..."}
+fn deserialize_diff<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Diff {
+        Text(String),
+        Object { text: Option<String> },
+    }
+
+    Ok(match Option::<Diff>::deserialize(deserializer)? {
+        Some(Diff::Text(text)) => Some(text),
+        Some(Diff::Object { text }) => text,
+        None => None,
+    })
+}
+
 // Comprehensive metadata extracted from the scraped page
 #[derive(Deserialize, Serialize, Debug, Default, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -3,11 +3,13 @@ use chrono;
 use reqwest::Client;
 use std::time::Duration;
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 
 use crate::api::{
     ApiResponse, CrawlRequest, CrawlResponse, CrawlStartResponse, CrawlState, CrawlStatusResponse,
-    OutputFormat, ScrapeData, ScrapeRequest, ScrapeResponse,
+    OutputFormat, ScrapeData, ScrapeRequest,
 };
+use crate::errors::FirecrawlError;
 use crate::services::CrawlMonitorService;
 use std::boxed::Box;
 
@@ -17,6 +19,7 @@ pub struct FirecrawlClient {
     client: Client,          // Reqwest HTTP client
     base_url: String,        // Base URL for the API
     api_key: Option<String>, // Optional API key for authentication
+    cancellation: CancellationToken, // Stops crawl polling, e.g. on Ctrl-C
 }
 
 impl FirecrawlClient {
@@ -31,9 +34,27 @@ impl FirecrawlClient {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
             api_key: api_key.map(|k| k.to_string()),
+            cancellation: CancellationToken::new(),
         })
     }
 
+    // Stop polling crawl jobs once `cancellation` is cancelled. The job is then
+    // cancelled on the server and the pages crawled so far are returned.
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    // Whether the token given to with_cancellation has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    // Resolves once the token given to with_cancellation is cancelled
+    pub async fn cancelled(&self) {
+        self.cancellation.cancelled().await
+    }
+
     // Add authorization header to requests if API key is available
     fn add_auth_headers(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
         if let Some(api_key) = &self.api_key {
@@ -49,9 +70,9 @@ impl FirecrawlClient {
         let request = ScrapeRequest {
             url: url.to_string(),
             formats: vec![
-                OutputFormat::Markdown,
-                OutputFormat::RawHtml,
-                OutputFormat::Html,
+                OutputFormat::Markdown.into(),
+                OutputFormat::RawHtml.into(),
+                OutputFormat::Html.into(),
             ],
             only_main_content: Some(true),
             ..Default::default()
@@ -71,11 +92,11 @@ impl FirecrawlClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!(
-                "Scrape request failed: {} - {}",
-                status,
-                error_text
-            ));
+            return Err(FirecrawlError::from_status(
+                status.as_u16(),
+                format!("Scrape request failed: {}", error_text),
+            )
+            .into());
         }
 
         // Parse and return the response
@@ -112,11 +133,11 @@ impl FirecrawlClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!(
-                "Scrape request failed: {} - {}",
-                status,
-                error_text
-            ));
+            return Err(FirecrawlError::from_status(
+                status.as_u16(),
+                format!("Scrape request failed: {}", error_text),
+            )
+            .into());
         }
 
         // Parse and return the response
@@ -160,7 +181,11 @@ impl FirecrawlClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Crawl start failed: {} - {}", status, error_text));
+            return Err(FirecrawlError::from_status(
+                status.as_u16(),
+                format!("Crawl start failed: {}", error_text),
+            )
+            .into());
         }
 
         // Extract job ID from the response
@@ -176,26 +201,58 @@ impl FirecrawlClient {
             direct_response.job_id
         };
 
-        // Poll for crawl completion
+        // Poll for crawl completion, keeping the pages crawled so far in case
+        // polling is cancelled
+        let mut crawled = Vec::new();
         loop {
-            let state = self.check_crawl_status(&job_id).await?;
+            let Some(state) = self.poll_crawl_status(&job_id).await else {
+                self.cancel_interrupted_crawl(&job_id).await;
+                return Ok(crawled);
+            };
 
-            match state {
+            match state? {
                 CrawlState::Completed { data, .. } => return Ok(data),
                 CrawlState::Failed { error, .. } => return Err(anyhow!("Crawl failed: {}", error)),
                 CrawlState::InProgress {
-                    completed, total, ..
+                    completed, total, data, ..
                 } => {
-                    // Display progress updates
-                    println!("⏳ Progress: {}/{}", completed, total);
+                    log::debug!("Crawl {} progress: {}/{}", job_id, completed, total);
+                    crawled = data;
                 }
                 CrawlState::Started { .. } => {
-                    println!("🚀 Crawl job started");
+                    log::debug!("Crawl {} started", job_id);
                 }
             }
 
             // Wait 2 seconds before next status check
-            sleep(Duration::from_secs(2)).await;
+            if !self.wait_for_next_poll().await {
+                self.cancel_interrupted_crawl(&job_id).await;
+                return Ok(crawled);
+            }
+        }
+    }
+
+    // Check the status of a crawl job, or None if polling was cancelled first
+    async fn poll_crawl_status(&self, job_id: &str) -> Option<Result<CrawlState>> {
+        tokio::select! {
+            _ = self.cancellation.cancelled() => None,
+            state = self.check_crawl_status(job_id) => Some(state),
+        }
+    }
+
+    // Wait between two status checks; false if polling was cancelled meanwhile
+    async fn wait_for_next_poll(&self) -> bool {
+        tokio::select! {
+            _ = self.cancellation.cancelled() => false,
+            _ = sleep(Duration::from_secs(2)) => true,
+        }
+    }
+
+    // Cancel a job whose polling was interrupted, so it stops using credits
+    async fn cancel_interrupted_crawl(&self, job_id: &str) {
+        match self.cancel_crawl(job_id).await {
+            Ok(()) => log::info!("Cancelled crawl job {}", job_id),
+            Err(e) => log::warn!("Failed to cancel crawl job {}: {}", job_id, e),
         }
     }
 
@@ -212,7 +269,13 @@ impl FirecrawlClient {
 
         // Handle error responses
         if !response.status().is_success() {
-            return Err(anyhow!("Status check failed"));
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(FirecrawlError::from_status(
+                status.as_u16(),
+                format!("Status check failed: {}", error_text),
+            )
+            .into());
         }
 
         // Parse and categorize the response
@@ -234,6 +297,7 @@ impl FirecrawlClient {
                 status: status_response.status,
                 completed: status_response.completed.unwrap_or(0),
                 total: status_response.total.unwrap_or(0),
+                data: status_response.data.unwrap_or_default(),
             }),
         }
     }
@@ -259,7 +323,11 @@ impl FirecrawlClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Crawl start failed: {} - {}", status, error_text));
+            return Err(FirecrawlError::from_status(
+                status.as_u16(),
+                format!("Crawl start failed: {}", error_text),
+            )
+            .into());
         }
 
         // Extract job ID from the response
@@ -276,6 +344,28 @@ impl FirecrawlClient {
             Ok(direct_response)
         }
     }
+
+    // Cancel a running crawl job
+    pub async fn cancel_crawl(&self, job_id: &str) -> Result<()> {
+        let response = self
+            .add_auth_headers(
+                self.client
+                    .delete(format!("{}/crawl/{}", self.base_url, job_id)),
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(FirecrawlError::from_status(
+                status.as_u16(),
+                format!("Crawl cancel failed: {}", error_text),
+            )
+            .into());
+        }
+        Ok(())
+    }
 }
 
 // Implement CrawlMonitorService for FirecrawlClient
@@ -292,39 +382,36 @@ impl CrawlMonitorService for FirecrawlClient {
         >,
     > {
         Box::pin(async move {
-            let mut results = Vec::new();
+            // Pages already reported through the progress callback
+            let mut reported = std::collections::HashSet::new();
+            // Pages crawled so far, returned if polling is cancelled
+            let mut crawled = Vec::new();
 
             loop {
-                let state = self.check_crawl_status(job_id).await.map_err(|e| {
-                    crate::errors::FirecrawlError::ApiError(crate::errors::ApiError::Other(
-                        e.to_string(),
-                    ))
-                })?;
+                let Some(state) = self.poll_crawl_status(job_id).await else {
+                    self.cancel_interrupted_crawl(job_id).await;
+                    break Ok(crawl_responses(crawled));
+                };
+                let state = state.map_err(|e| FirecrawlError::from_client_error(&e))?;
 
                 match state {
                     CrawlState::Completed { data, .. } => {
-                        // Convert ScrapeData to CrawlResponse
-                        for (index, scrape_data) in data.into_iter().enumerate() {
-                            let response = CrawlResponse {
-                                id: format!("crawl-result-{}", index),
-                                url: scrape_data.url.unwrap_or_else(|| "unknown".to_string()),
-                                status: "completed".to_string(),
-                                completed_at: Some(chrono::Utc::now()),
-                                markdown: scrape_data.markdown,
-                                html: scrape_data.html,
-                                metadata: crate::api::models::crawl_model::CrawlMetadata {
-                                    keywords: None, // This would need to be populated from extra metadata
-                                    robots: None,
-                                    og_image: None,
-                                    author: None,
-                                    published_date: None,
-                                    modified_date: None,
-                                    site_name: None,
-                                },
-                            };
-                            results.push(response);
-                        }
-                        break Ok(results);
+                        // Report the pages that finished since the last poll
+                        let total = data.len() as u32;
+                        let new_urls: Vec<String> = data
+                            .iter()
+                            .filter_map(|page| page.url.clone())
+                            .filter(|url| reported.insert(url.clone()))
+                            .collect();
+                        progress_callback(crate::services::CrawlProgress {
+                            completed: total,
+                            total,
+                            current_url: new_urls.last().cloned(),
+                            status: "completed".to_string(),
+                            new_urls,
+                        });
+
+                        break Ok(crawl_responses(data));
                     }
                     CrawlState::Failed { error, .. } => {
                         break Err(crate::errors::FirecrawlError::ApiError(
@@ -332,16 +419,25 @@ impl CrawlMonitorService for FirecrawlClient {
                         ));
                     }
                     CrawlState::InProgress {
-                        completed, total, ..
+                        completed,
+                        total,
+                        data,
+                        ..
                     } => {
+                        let new_urls: Vec<String> = data
+                            .iter()
+                            .filter_map(|page| page.url.clone())
+                            .filter(|url| reported.insert(url.clone()))
+                            .collect();
+                        crawled = data;
                         let progress = crate::services::CrawlProgress {
                             completed,
                             total,
-                            current_url: None,
+                            current_url: new_urls.last().cloned(),
                             status: "in_progress".to_string(),
+                            new_urls,
                         };
                         progress_callback(progress);
-                        tokio::time::sleep(Duration::from_secs(2)).await;
                     }
                     CrawlState::Started { .. } => {
                         let progress = crate::services::CrawlProgress {
@@ -349,12 +445,35 @@ impl CrawlMonitorService for FirecrawlClient {
                             total: 0,
                             current_url: None,
                             status: "started".to_string(),
+                            new_urls: Vec::new(),
                         };
                         progress_callback(progress);
-                        tokio::time::sleep(Duration::from_secs(2)).await;
                     }
                 }
+
+                if !self.wait_for_next_poll().await {
+                    self.cancel_interrupted_crawl(job_id).await;
+                    break Ok(crawl_responses(crawled));
+                }
             }
         })
     }
 }
+
+// Convert the pages of a crawl status response to crawl results
+fn crawl_responses(data: Vec<ScrapeData>) -> Vec<CrawlResponse> {
+    data.into_iter()
+        .enumerate()
+        .map(|(index, scrape_data)| CrawlResponse {
+            id: format!("crawl-result-{}", index),
+            url: scrape_data.url.unwrap_or_else(|| "unknown".to_string()),
+            status: "completed".to_string(),
+            completed_at: Some(chrono::Utc::now()),
+            markdown: scrape_data.markdown,
+            html: scrape_data.html,
+            metadata: crate::api::models::crawl_model::CrawlMetadata::from_page_metadata(
+                &scrape_data.metadata,
+            ),
+        })
+        .collect()
+}
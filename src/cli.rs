@@ -2,10 +2,18 @@ use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::api::models::crawl_model::CrawlWebhookEvent;
+use crate::api::models::scrape_model::{ChangeTrackingMode, Location, ProxyType};
+use crate::errors::ExitCode;
+use crate::services::{DiffFormat, Verbosity};
+use crate::storage::RemovedPagePolicy;
+
 // Main CLI structure using clap for argument parsing
 #[derive(Parser)]
 #[command(name = "fc_cli")]
 #[command(about = "Firecrawl Rust CLI Tool")]
+#[command(args_conflicts_with_subcommands = true)]
+#[command(after_long_help = ExitCode::table())]
 pub struct Cli {
     // Base URL for the Firecrawl API (defaults to local instance or FIRE_API_URL env var)
     #[arg(long, env = "FIRE_API_URL", default_value = "http://localhost:3002/v2")]
@@ -19,11 +27,87 @@ pub struct Cli {
     #[arg(short, long, help = "Launch Terminal User Interface")]
     pub tui: bool,
 
+    // URLs to preload as scrape tasks when launching the TUI
+    #[arg(requires = "tui")]
+    pub urls: Vec<String>,
+
+    // Named TUI session whose task list is restored on launch and saved on
+    // quit; without it the TUI starts empty and keeps nothing
+    #[arg(long, requires = "tui", value_name = "NAME")]
+    pub session: Option<String>,
+
+    // Print no progress output, only errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    // Print every progress event on its own line and a summary at the end
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    // Write task events as NDJSON to stderr instead of the progress output
+    #[arg(long, global = true, conflicts_with = "events_file")]
+    pub events: bool,
+
+    // Append task events as NDJSON to a file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub events_file: Option<PathBuf>,
+
+    // POST a JSON notification to these URLs when a task completes or fails
+    #[arg(long, global = true, env = "FIRE_NOTIFY_WEBHOOK", value_delimiter = ',', value_name = "URL")]
+    pub notify_webhook: Vec<String>,
+
+    // Secret the notifications are signed with (HMAC-SHA256)
+    #[arg(long, global = true, env = "FIRE_WEBHOOK_SECRET", hide_env_values = true)]
+    pub webhook_secret: Option<String>,
+
+    // How a failure is reported on stderr before exiting with its exit code
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
+
     // Subcommands for different operations (scrape/crawl)
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+impl Cli {
+    // How much progress output the plain CLI prints; none when the events
+    // go to stderr, which then carries nothing but JSON lines
+    pub fn verbosity(&self) -> Verbosity {
+        if self.quiet || self.events {
+            Verbosity::Quiet
+        } else if self.verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    // How a failure is reported; always JSON when the events go to stderr,
+    // so every line there stays parseable
+    pub fn effective_error_format(&self) -> ErrorFormat {
+        if self.events {
+            ErrorFormat::Json
+        } else {
+            self.error_format
+        }
+    }
+
+    // The error format asked for on a command line that could not be parsed,
+    // so that usage errors follow `--error-format json` and `--events` too
+    pub fn requested_error_format(args: &[std::ffi::OsString]) -> ErrorFormat {
+        let mut args = args.iter().filter_map(|arg| arg.to_str());
+        while let Some(arg) = args.next() {
+            match arg {
+                "--" => break,
+                "--events" | "--error-format=json" => return ErrorFormat::Json,
+                "--error-format" if args.next() == Some("json") => return ErrorFormat::Json,
+                _ => {}
+            }
+        }
+        ErrorFormat::Text
+    }
+}
+
 // Enumeration of available CLI commands
 #[derive(Subcommand)]
 pub enum Commands {
@@ -45,13 +129,82 @@ pub enum Commands {
         // Output directory for saved files (defaults to ./output)
         #[arg(short, long, default_value = "./output")]
         output_dir: PathBuf,
+        // Also write an index.md table of contents next to manifest.json
+        #[arg(long)]
+        index: bool,
+        // Only rewrite pages that changed since the manifest of a previous run
+        #[arg(long)]
+        sync: bool,
+        // With --sync, what to do with pages the crawl no longer returns
+        #[arg(long, value_enum, default_value_t = RemovedPagePolicy::Keep, requires = "sync")]
+        removed: RemovedPagePolicy,
+        // Stop at the first page that cannot be saved instead of saving the
+        // others and listing the failures in failures.json
+        #[arg(long)]
+        fail_fast: bool,
+        // Have the Firecrawl server itself call this URL for crawl events
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+        // Events the server sends to --webhook (defaults to all)
+        #[arg(long, value_enum, value_delimiter = ',', requires = "webhook")]
+        webhook_events: Vec<CrawlWebhookEvent>,
+    },
+    // Watch command that rescrapes a URL periodically and reports changes
+    Watch {
+        // URL to watch
+        url: String,
+        // Time between checks, e.g. 30m, 1h, 1day
+        #[arg(long, default_value = "1h", value_parser = humantime::parse_duration)]
+        every: std::time::Duration,
+        // Change tracking modes to request (git-diff, json)
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "git-diff")]
+        modes: Vec<ChangeTrackingMode>,
+        // Keep a separate change history under this tag
+        #[arg(long)]
+        tag: Option<String>,
+        // Stop after this many checks instead of running forever
+        #[arg(long)]
+        max_checks: Option<u32>,
+        // Output directory for change snapshots (defaults to ./output)
+        #[arg(short, long, default_value = "./output")]
+        output_dir: PathBuf,
+        // Emit one JSON event per line instead of human-readable output
+        #[arg(long)]
+        json: bool,
     },
+    // Diff command that compares the pages saved by two crawl runs
+    Diff {
+        // Output directory of the earlier run
+        old_dir: PathBuf,
+        // Output directory of the later run
+        new_dir: PathBuf,
+        // Only compare this URL
+        #[arg(long)]
+        url: Option<String>,
+        // Report to produce: unified diff, JSON summary or side-by-side HTML
+        #[arg(long, value_enum, default_value_t = DiffFormat::Unified)]
+        format: DiffFormat,
+        // Write the report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+// Formats of the error report printed when a command fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ErrorFormat {
+    // Human-readable message with a hint
+    #[default]
+    Text,
+    // One JSON object with the error code, messages and context
+    Json,
 }
 
 /// Output format options
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum OutputFormat {
     #[serde(rename = "markdown")]
+    #[default]
     Markdown,
     #[serde(rename = "html")]
     Html,
@@ -67,12 +220,6 @@ pub enum OutputFormat {
     Images,
 }
 
-impl Default for OutputFormat {
-    fn default() -> Self {
-        OutputFormat::Markdown
-    }
-}
-
 impl std::fmt::Display for OutputFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -88,28 +235,29 @@ impl std::fmt::Display for OutputFormat {
 }
 
 /// Scrape operation options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScrapeOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub only_main_content: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub include_tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub exclude_tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub formats: Option<Vec<OutputFormat>>,
-}
-
-impl Default for ScrapeOptions {
-    fn default() -> Self {
-        Self {
-            only_main_content: None,
-            include_tags: None,
-            exclude_tags: None,
-            formats: None,
-        }
-    }
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mobile: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<Location>,
 }
 
 /// Crawl operation options
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CrawlOptions {
     pub limit: Option<u32>,
@@ -119,16 +267,6 @@ pub struct CrawlOptions {
     pub only_main_content: Option<bool>,
 }
 
-impl Default for CrawlOptions {
-    fn default() -> Self {
-        Self {
-            limit: None,
-            formats: None,
-            only_main_content: None,
-        }
-    }
-}
-
 /// Action enum for task types
 #[derive(Debug, Clone)]
 pub enum Action {
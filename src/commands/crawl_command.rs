@@ -1,5 +1,6 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::Path;
+use tokio_util::sync::CancellationToken;
 
 use crate::api::models::crawl_model::{CrawlOptions, CrawlRequest, CrawlResponse};
 use crate::api::services::client::FirecrawlClient;
@@ -7,7 +8,9 @@ use crate::cli::OutputFormat;
 use crate::commands::{Command, CommandObserver, CommandResult, NoOpObserver};
 use crate::errors::{FirecrawlError, FirecrawlResult};
 use crate::services::CrawlMonitorService;
-use crate::storage::ContentRepository;
+use crate::storage::{
+    ContentRepository, CrawlRunOptions, ManifestConfig, RemovedPagePolicy, save_crawl_run,
+};
 
 /// Command for crawling a URL
 #[derive(Debug, Clone)]
@@ -15,6 +18,12 @@ pub struct CrawlCommand {
     pub url: String,
     pub options: Option<CrawlOptions>,
     pub output_format: OutputFormat,
+    pub write_index: bool,
+    pub sync: Option<RemovedPagePolicy>,
+    /// Abort on the first page that cannot be saved instead of reporting it
+    pub fail_fast: bool,
+    /// Stops the crawl early; the pages crawled so far are still saved
+    pub cancellation: CancellationToken,
 }
 
 impl CrawlCommand {
@@ -24,16 +33,31 @@ impl CrawlCommand {
             url,
             options,
             output_format,
+            write_index: false,
+            sync: None,
+            fail_fast: false,
+            cancellation: CancellationToken::new(),
         }
     }
 
+    /// Stop the crawl once `cancellation` is cancelled. The job is cancelled
+    /// on the server, the pages crawled so far are saved and the command then
+    /// fails with [`FirecrawlError::Cancelled`].
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
     /// Create a builder for crawl command
     pub fn builder() -> CrawlCommandBuilder {
         CrawlCommandBuilder::new()
     }
 
-    /// Execute the crawl operation with the provided client
-    async fn execute_crawl(&self, client: &FirecrawlClient) -> FirecrawlResult<Vec<crate::api::models::crawl_model::CrawlResponse>> {
+    /// Execute the crawl operation with the provided client, returning the job id and results
+    async fn execute_crawl(
+        &self,
+        client: &FirecrawlClient,
+    ) -> FirecrawlResult<(String, Vec<CrawlResponse>)> {
         let request = if let Some(options) = &self.options {
             CrawlRequest::builder()
                 .url(self.url.clone())
@@ -59,7 +83,7 @@ impl CrawlCommand {
 
         // Wait for crawl to complete and get results
         let monitor_service = client as &dyn CrawlMonitorService;
-        monitor_service
+        let results = monitor_service
             .monitor_crawl_job(
                 &job_id,
                 Box::new(|_progress| {
@@ -67,7 +91,9 @@ impl CrawlCommand {
                     // For now, we'll just ignore progress updates
                 }),
             )
-            .await
+            .await?;
+
+        Ok((job_id, results))
     }
 }
 
@@ -78,32 +104,61 @@ impl Command for CrawlCommand {
     async fn execute(
         &self,
         repository: &dyn ContentRepository,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> FirecrawlResult<Self::Result> {
         // Create client
         let api_key = std::env::var("FIRECRAWL_API_KEY").ok();
         let client = FirecrawlClient::new("https://api.firecrawl.dev", api_key.as_deref())
-            .map_err(|e| FirecrawlError::ConfigurationError(e.to_string()))?;
+            .map_err(|e| FirecrawlError::ConfigurationError(e.to_string()))?
+            .with_cancellation(self.cancellation.clone());
 
         // Notify start
         let observer = NoOpObserver; // Could be injected
         observer.on_command_started(self);
 
         // Execute crawl
-        let crawl_results = self.execute_crawl(&client).await.map_err(|e| {
-            observer.on_command_failed(self, &e);
-            e
+        let (job_id, crawl_results) = self.execute_crawl(&client).await.inspect_err(|e| {
+            observer.on_command_failed(self, e);
         })?;
 
-        // Save results
-        let file_paths = repository
-            .save_crawl_results(&crawl_results, &self.url, self.output_format, output_dir)
-            .await
-            .map_err(FirecrawlError::StorageError)?;
+        let config = ManifestConfig {
+            output_format: self.output_format,
+            options: self.options.clone(),
+        };
+        let options = CrawlRunOptions {
+            write_index: self.write_index,
+            sync: self.sync,
+            fail_fast: self.fail_fast,
+        };
+
+        // Save the pages and record what was written so later runs and tools
+        // can consume the directory
+        let run = save_crawl_run(
+            repository,
+            &crawl_results,
+            &self.url,
+            Some(job_id.clone()),
+            config,
+            output_dir,
+            &options,
+        )
+        .await?;
+
+        if self.cancellation.is_cancelled() {
+            let error = FirecrawlError::Cancelled(format!(
+                "crawl job {} stopped after {} pages",
+                job_id,
+                crawl_results.len()
+            ));
+            observer.on_command_failed(self, &error);
+            return Err(error);
+        }
 
         let result = CommandResult::Crawl {
             url: self.url.clone(),
-            file_paths,
+            file_paths: run.page_files(output_dir),
+            manifest_path: run.manifest_files.first().cloned(),
+            failures: run.failures.failures,
         };
 
         observer.on_command_completed(self, &result);
@@ -119,7 +174,7 @@ impl Command for CrawlCommand {
     }
 
     fn output_format(&self) -> OutputFormat {
-        self.output_format.clone()
+        self.output_format
     }
 }
 
@@ -128,6 +183,16 @@ pub struct CrawlCommandBuilder {
     url: Option<String>,
     options: Option<CrawlOptions>,
     output_format: OutputFormat,
+    write_index: bool,
+    sync: Option<RemovedPagePolicy>,
+    fail_fast: bool,
+    cancellation: CancellationToken,
+}
+
+impl Default for CrawlCommandBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CrawlCommandBuilder {
@@ -136,6 +201,10 @@ impl CrawlCommandBuilder {
             url: None,
             options: None,
             output_format: OutputFormat::Markdown,
+            write_index: false,
+            sync: None,
+            fail_fast: false,
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -154,6 +223,26 @@ impl CrawlCommandBuilder {
         self
     }
 
+    pub fn write_index(mut self, write_index: bool) -> Self {
+        self.write_index = write_index;
+        self
+    }
+
+    pub fn sync(mut self, removed: RemovedPagePolicy) -> Self {
+        self.sync = Some(removed);
+        self
+    }
+
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    pub fn cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
     pub fn build(self) -> FirecrawlResult<CrawlCommand> {
         let url = self
             .url
@@ -163,6 +252,10 @@ impl CrawlCommandBuilder {
             url,
             options: self.options,
             output_format: self.output_format,
+            write_index: self.write_index,
+            sync: self.sync,
+            fail_fast: self.fail_fast,
+            cancellation: self.cancellation,
         })
     }
 }
@@ -1,9 +1,9 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::errors::{FirecrawlError, FirecrawlResult};
 use crate::cli::OutputFormat;
-use crate::storage::ContentRepository;
+use crate::storage::{ContentRepository, PageFailure};
 
 pub mod crawl_command;
 pub mod scrape_command;
@@ -22,7 +22,7 @@ pub trait Command {
     async fn execute(
         &self,
         repository: &dyn ContentRepository,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> FirecrawlResult<Self::Result>;
 
     /// Get a description of what this command does
@@ -45,6 +45,9 @@ pub enum CommandResult {
     Crawl {
         url: String,
         file_paths: Vec<PathBuf>,
+        manifest_path: Option<PathBuf>,
+        /// Pages that could not be saved, also listed in `failures.json`
+        failures: Vec<PageFailure>,
     },
 }
 
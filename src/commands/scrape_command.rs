@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::Path;
 
 use crate::api::models::scrape_model::{ScrapeRequest, ScrapeResponse, ScrapeOptions};
 use crate::api::services::client::FirecrawlClient;
@@ -35,7 +35,7 @@ impl ScrapeCommand {
     async fn execute_scrape(&self, client: &FirecrawlClient) -> FirecrawlResult<crate::api::models::scrape_model::ScrapeData> {
         let mut builder = ScrapeRequest::builder()
             .url(self.url.clone())
-            .formats(vec![self.output_format.clone()]);
+            .formats(vec![self.output_format]);
 
         if let Some(options) = &self.options {
             if let Some(only_main) = options.only_main_content {
@@ -47,6 +47,18 @@ impl ScrapeCommand {
             if let Some(ref exclude_tags) = options.exclude_tags {
                 builder = builder.exclude_tags(exclude_tags.clone());
             }
+            if let Some(wait_for) = options.wait_for {
+                builder = builder.wait_for(wait_for);
+            }
+            if let Some(mobile) = options.mobile {
+                builder = builder.mobile(mobile);
+            }
+            if let Some(ref proxy) = options.proxy {
+                builder = builder.proxy(proxy.clone());
+            }
+            if let Some(ref location) = options.location {
+                builder = builder.location(location.clone());
+            }
         }
 
         let request = builder.build();
@@ -63,7 +75,7 @@ impl Command for ScrapeCommand {
     async fn execute(
         &self,
         repository: &dyn ContentRepository,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> FirecrawlResult<Self::Result> {
         // Create client
         let api_key = std::env::var("FIRECRAWL_API_KEY").ok();
@@ -76,9 +88,8 @@ impl Command for ScrapeCommand {
 
         // Execute scrape
         let scrape_data = self.execute_scrape(&client).await
-            .map_err(|e| {
-                observer.on_command_failed(self, &e);
-                e
+            .inspect_err(|e| {
+                observer.on_command_failed(self, e);
             })?;
 
         // Wrap ScrapeData in ScrapeResponse
@@ -112,7 +123,7 @@ impl Command for ScrapeCommand {
     }
 
     fn output_format(&self) -> OutputFormat {
-        self.output_format.clone()
+        self.output_format
     }
 }
 
@@ -123,6 +134,12 @@ pub struct ScrapeCommandBuilder {
     output_format: OutputFormat,
 }
 
+impl Default for ScrapeCommandBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ScrapeCommandBuilder {
     pub fn new() -> Self {
         Self {
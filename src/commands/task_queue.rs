@@ -1,17 +1,87 @@
 use std::collections::VecDeque;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::{Mutex, Semaphore};
+use tokio_util::sync::CancellationToken;
 
 use crate::commands::{Command, CommandObserver, CommandResult, NoOpObserver};
-use crate::errors::{FirecrawlError, FirecrawlResult};
-use crate::storage::ContentRepository;
+use crate::errors::{ExitCode, FirecrawlError, FirecrawlResult};
+use crate::storage::{ContentRepository, FailureReport, PageFailure};
+
+/// Command as shared by the workers of a queue
+type SharedCommand = Arc<dyn Command<Result = CommandResult> + Send + Sync>;
+
+/// Outcome of running the commands of a queue
+#[derive(Debug, Clone)]
+pub struct BatchReport {
+    /// Results of the commands that succeeded, in the order they were queued
+    pub results: Vec<CommandResult>,
+
+    /// Commands that failed, and pages their crawls could not save
+    pub failures: FailureReport,
+
+    /// `failures.json`, written by
+    /// [`TaskService::execute_batch`](crate::services::TaskService::execute_batch)
+    /// when anything failed
+    pub failures_file: Option<PathBuf>,
+}
+
+impl Default for BatchReport {
+    fn default() -> Self {
+        Self {
+            results: Vec::new(),
+            failures: FailureReport::new(None, 0, Vec::new()),
+            failures_file: None,
+        }
+    }
+}
+
+impl BatchReport {
+    /// Record the outcome of a command that ran. A scrape counts as one page,
+    /// a crawl as every page it tried to save.
+    pub fn record(&mut self, url: &str, outcome: FirecrawlResult<CommandResult>) {
+        match outcome {
+            Ok(result) => {
+                match &result {
+                    CommandResult::Scrape { .. } => self.failures.attempted += 1,
+                    CommandResult::Crawl {
+                        file_paths,
+                        failures,
+                        ..
+                    } => {
+                        self.failures.attempted += file_paths.len() + failures.len();
+                        self.failures.failures.extend(failures.iter().cloned());
+                    }
+                }
+                self.results.push(result);
+            }
+            Err(e) => {
+                self.failures.attempted += 1;
+                self.failures.failures.push(PageFailure::new(url, e));
+            }
+        }
+    }
+
+    /// [`ExitCode::PartialSuccess`] when some commands or pages failed while
+    /// others were saved, [`ExitCode::Failure`] when every command failed
+    pub fn exit_code(&self) -> ExitCode {
+        if self.failures.is_empty() {
+            ExitCode::Success
+        } else if self.results.is_empty() {
+            ExitCode::Failure
+        } else {
+            ExitCode::PartialSuccess
+        }
+    }
+}
 
 /// Task queue for managing and executing commands concurrently
 pub struct TaskQueue {
-    commands: Arc<Mutex<VecDeque<Box<dyn Command<Result = CommandResult> + Send + Sync>>>>,
+    commands: Arc<Mutex<VecDeque<SharedCommand>>>,
     semaphore: Arc<Semaphore>,
     observer: Arc<dyn CommandObserver + Send + Sync>,
+    cancellation: CancellationToken,
+    fail_fast: bool,
 }
 
 impl TaskQueue {
@@ -21,6 +91,8 @@ impl TaskQueue {
             commands: Arc::new(Mutex::new(VecDeque::new())),
             semaphore: Arc::new(Semaphore::new(concurrency_limit)),
             observer: Arc::new(NoOpObserver),
+            cancellation: CancellationToken::new(),
+            fail_fast: false,
         }
     }
 
@@ -33,16 +105,32 @@ impl TaskQueue {
             commands: Arc::new(Mutex::new(VecDeque::new())),
             semaphore: Arc::new(Semaphore::new(concurrency_limit)),
             observer,
+            cancellation: CancellationToken::new(),
+            fail_fast: false,
         }
     }
 
+    /// Stop scheduling commands once `cancellation` is cancelled. Commands
+    /// already running finish and the others are not started.
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Stop at the first command that fails instead of running the others
+    /// and listing the failures in the [`BatchReport`]
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
     /// Add a command to the queue
     pub async fn enqueue<C>(&self, command: C)
     where
         C: Command<Result = CommandResult> + Send + Sync + 'static,
     {
         let mut commands = self.commands.lock().await;
-        commands.push_back(Box::new(command));
+        commands.push_back(Arc::new(command));
     }
 
     /// Get the number of pending commands
@@ -57,17 +145,19 @@ impl TaskQueue {
         commands.is_empty()
     }
 
-    /// Execute all commands in the queue
-    pub async fn execute_all<R: ContentRepository + ?Sized>(
+    /// Execute all commands in the queue, as many at a time as the
+    /// concurrency limit allows
+    pub async fn execute_all(
         &self,
-        repository: &R,
-        output_dir: &PathBuf,
-    ) -> FirecrawlResult<Vec<CommandResult>> {
-        let mut results = Vec::new();
+        repository: Arc<dyn ContentRepository + Send + Sync>,
+        output_dir: &Path,
+    ) -> FirecrawlResult<BatchReport> {
+        // Cancelled with the queue, or by the first failure when failing fast
+        let stop = self.cancellation.child_token();
         let mut handles = Vec::new();
 
         // Process all commands
-        loop {
+        while !stop.is_cancelled() {
             let command = {
                 let mut commands = self.commands.lock().await;
                 commands.pop_front()
@@ -76,77 +166,99 @@ impl TaskQueue {
             if let Some(cmd) = command {
                 let semaphore = Arc::clone(&self.semaphore);
                 let observer = Arc::clone(&self.observer);
+                let repository = Arc::clone(&repository);
+                let output_dir = output_dir.to_path_buf();
+                let stop = stop.clone();
+                let fail_fast = self.fail_fast;
                 let url = cmd.url().to_string();
 
+                // Resolves to `None` for a command that never started
                 let handle = tokio::spawn(async move {
-                    let _permit = semaphore.acquire().await.map_err(|_| {
-                        FirecrawlError::ExecutionError(format!(
-                            "Failed to acquire permit for task: {}",
-                            url
-                        ))
-                    })?;
-
-                    // Clone the command for execution
-                    // This is a bit of a hack due to trait object limitations
-                    // In a real implementation, we might use Arc<dyn Command>
-                    observer.on_command_started(&*cmd);
+                    let _permit = tokio::select! {
+                        permit = semaphore.acquire() => match permit {
+                            Ok(permit) => permit,
+                            Err(_) => {
+                                return Some(Err(FirecrawlError::ExecutionError(format!(
+                                    "Failed to acquire permit for task: {}",
+                                    cmd.url()
+                                ))));
+                            }
+                        },
+                        _ = stop.cancelled() => return None,
+                    };
+                    // Stopped while this command waited for a worker
+                    if stop.is_cancelled() {
+                        return None;
+                    }
 
-                    // For now, we'll return a placeholder
-                    // This needs to be refactored to properly handle trait objects in async context
-                    Ok::<CommandResult, FirecrawlError>(CommandResult::Scrape {
-                        url,
-                        file_path: PathBuf::new(), // placeholder
-                    })
+                    observer.on_command_started(&*cmd);
+                    let outcome = cmd.execute(repository.as_ref(), &output_dir).await;
+                    match &outcome {
+                        Ok(result) => observer.on_command_completed(&*cmd, result),
+                        Err(e) => {
+                            observer.on_command_failed(&*cmd, e);
+                            if fail_fast {
+                                stop.cancel();
+                            }
+                        }
+                    }
+                    Some(outcome)
                 });
 
-                handles.push(handle);
+                handles.push((url, handle));
             } else {
                 break; // No more commands
             }
         }
 
         // Wait for all tasks to complete
-        for handle in handles {
-            match handle.await {
-                Ok(result) => match result {
-                    Ok(cmd_result) => results.push(cmd_result),
-                    Err(e) => return Err(e),
-                },
-                Err(e) => {
-                    return Err(FirecrawlError::ExecutionError(format!(
-                        "Task panicked: {}",
-                        e
-                    )));
+        let mut report = BatchReport::default();
+        let mut first_error = None;
+        for (url, handle) in handles {
+            let outcome = match handle.await {
+                Ok(Some(outcome)) => outcome,
+                Ok(None) => continue, // Never started
+                Err(e) => Err(FirecrawlError::ExecutionError(format!("Task panicked: {}", e))),
+            };
+            match outcome {
+                Err(e) if self.fail_fast => {
+                    first_error.get_or_insert(e);
                 }
+                outcome => report.record(&url, outcome),
             }
         }
 
-        Ok(results)
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(report),
+        }
     }
 
     /// Execute commands one by one (sequential execution)
     pub async fn execute_sequential<R: ContentRepository>(
         &self,
         repository: &R,
-        output_dir: &PathBuf,
-    ) -> FirecrawlResult<Vec<CommandResult>> {
-        let mut results = Vec::new();
+        output_dir: &Path,
+    ) -> FirecrawlResult<BatchReport> {
+        let mut report = BatchReport::default();
 
-        loop {
+        while !self.cancellation.is_cancelled() {
             let command = {
                 let mut commands = self.commands.lock().await;
                 commands.pop_front()
             };
 
             if let Some(cmd) = command {
-                let result = cmd.execute(repository, output_dir).await?;
-                results.push(result);
+                match cmd.execute(repository, output_dir).await {
+                    Err(e) if self.fail_fast => return Err(e),
+                    outcome => report.record(cmd.url(), outcome),
+                }
             } else {
                 break; // No more commands
             }
         }
 
-        Ok(results)
+        Ok(report)
     }
 }
 
@@ -184,6 +296,7 @@ mod tests {
     use crate::cli::OutputFormat;
     use crate::commands::ScrapeCommand;
     use crate::storage::FileSystemRepository;
+    use tempfile::tempdir;
 
     #[tokio::test]
     async fn test_task_queue_basic_operations() {
@@ -203,5 +316,173 @@ mod tests {
         assert!(!queue.is_empty().await);
         assert_eq!(queue.pending_count().await, 1);
     }
-}
 
+    #[tokio::test]
+    async fn test_cancelled_queue_starts_no_more_commands() {
+        let dir = tempdir().unwrap();
+        let repository = FileSystemRepository::new(dir.path().to_path_buf());
+        let cancellation = CancellationToken::new();
+        let queue = TaskQueue::new(1).with_cancellation(cancellation.clone());
+
+        queue
+            .enqueue(ScrapeCommand::new(
+                "https://example.com".to_string(),
+                None,
+                OutputFormat::Markdown,
+            ))
+            .await;
+        cancellation.cancel();
+
+        let report = queue
+            .execute_sequential(&repository, dir.path())
+            .await
+            .unwrap();
+        assert!(report.results.is_empty());
+        assert_eq!(queue.pending_count().await, 1);
+    }
+
+    // Command that fails for URLs containing `fail`, without touching the network
+    struct StubCommand(String);
+
+    impl StubCommand {
+        fn run(&self, output_dir: &Path) -> FirecrawlResult<CommandResult> {
+            if self.0.contains("fail") {
+                return Err(FirecrawlError::ValidationError("bad page".to_string()));
+            }
+            Ok(CommandResult::Scrape {
+                url: self.0.clone(),
+                file_path: output_dir.join("page.md"),
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Command for StubCommand {
+        type Result = CommandResult;
+
+        async fn execute(
+            &self,
+            _repository: &dyn ContentRepository,
+            output_dir: &Path,
+        ) -> FirecrawlResult<CommandResult> {
+            self.run(output_dir)
+        }
+
+        fn description(&self) -> String {
+            format!("Stub {}", self.0)
+        }
+
+        fn url(&self) -> &str {
+            &self.0
+        }
+
+        fn output_format(&self) -> OutputFormat {
+            OutputFormat::Markdown
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_commands_are_reported_unless_failing_fast() {
+        let dir = tempdir().unwrap();
+        let repository = FileSystemRepository::new(dir.path().to_path_buf());
+        for fail_fast in [false, true] {
+            let queue = TaskQueue::new(1).with_fail_fast(fail_fast);
+            for url in ["https://a.com/fail", "https://b.com"] {
+                queue.enqueue(StubCommand(url.to_string())).await;
+            }
+
+            let outcome = queue.execute_sequential(&repository, dir.path()).await;
+            if fail_fast {
+                assert!(matches!(outcome, Err(FirecrawlError::ValidationError(_))));
+                assert_eq!(queue.pending_count().await, 1);
+            } else {
+                let report = outcome.unwrap();
+                assert_eq!(report.results.len(), 1);
+                assert_eq!(
+                    report.failures.failures,
+                    vec![PageFailure::new(
+                        "https://a.com/fail",
+                        "Validation error: bad page"
+                    )]
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_batch_runs_its_commands_and_reports_failures() {
+        let dir = tempdir().unwrap();
+        let repository = Arc::new(FileSystemRepository::new(dir.path().to_path_buf()));
+        let queue = TaskQueue::new(2);
+        for url in ["https://a.com/fail", "https://b.com"] {
+            queue.enqueue(StubCommand(url.to_string())).await;
+        }
+
+        let report = queue.execute_all(repository, dir.path()).await.unwrap();
+        assert!(matches!(
+            report.results.as_slice(),
+            [CommandResult::Scrape { url, .. }] if url == "https://b.com"
+        ));
+        assert_eq!(report.failures.to_string(), "1 of 2 pages could not be saved");
+        assert_eq!(report.failures.failures[0].url, "https://a.com/fail");
+        assert_eq!(report.exit_code(), ExitCode::PartialSuccess);
+    }
+
+    // Records that it ran and cancels the batch it is part of
+    struct CancellingCommand {
+        url: String,
+        cancellation: CancellationToken,
+        ran: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Command for CancellingCommand {
+        type Result = CommandResult;
+
+        async fn execute(
+            &self,
+            _repository: &dyn ContentRepository,
+            output_dir: &Path,
+        ) -> FirecrawlResult<CommandResult> {
+            self.ran.lock().unwrap().push(self.url.clone());
+            self.cancellation.cancel();
+            StubCommand(self.url.clone()).run(output_dir)
+        }
+
+        fn description(&self) -> String {
+            format!("Cancel at {}", self.url)
+        }
+
+        fn url(&self) -> &str {
+            &self.url
+        }
+
+        fn output_format(&self) -> OutputFormat {
+            OutputFormat::Markdown
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_batch_runs_no_later_commands() {
+        let dir = tempdir().unwrap();
+        let repository = Arc::new(FileSystemRepository::new(dir.path().to_path_buf()));
+        let cancellation = CancellationToken::new();
+        let ran = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let queue = TaskQueue::new(1).with_cancellation(cancellation.clone());
+        for url in ["https://a.com", "https://b.com", "https://c.com"] {
+            queue
+                .enqueue(CancellingCommand {
+                    url: url.to_string(),
+                    cancellation: cancellation.clone(),
+                    ran: Arc::clone(&ran),
+                })
+                .await;
+        }
+
+        let report = queue.execute_all(repository, dir.path()).await.unwrap();
+        // The first command cancels the batch while the others wait for a worker
+        assert_eq!(ran.lock().unwrap().len(), 1);
+        assert_eq!(report.results.len(), 1);
+        assert!(report.failures.is_empty());
+    }
+}
@@ -5,21 +5,73 @@ use std::time::Duration;
 use crate::config::AppConfig;
 use crate::errors::{FirecrawlError, FirecrawlResult};
 
+/// Commented sample configuration, used when the defaults cannot be serialized
+const SAMPLE_CONFIG: &str = r#"# Firecrawl CLI Configuration
+
+[api]
+base_url = "https://api.firecrawl.dev"
+# api_key = "your-api-key-here"
+timeout = { secs = 30, nanos = 0 }
+max_retries = 3
+retry_delay = { secs = 1, nanos = 0 }
+
+[output]
+default_directory = "./output"
+default_format = "markdown"
+create_date_subdirectories = false
+# filename_prefix = "firecrawl_"
+overwrite_existing = false
+max_filename_length = 255
+
+[execution]
+max_concurrent_tasks = 4
+# default_crawl_limit = 10
+progress_update_interval = { secs = 0, nanos = 500_000_000 }
+verbose_logging = false
+
+[execution.cache]
+enabled = false
+directory = "./cache"
+ttl = { secs = 3600, nanos = 0 }
+max_size_mb = 100
+
+[ui]
+enable_colors = true
+
+[ui.theme]
+color_scheme = "default"  # dark, light, high-contrast or a palette below
+use_unicode = true
+
+# [ui.theme.palettes.mine]
+# base = "dark"
+# title = "magenta"
+# selection_bg = "blue"
+
+[ui.tui]
+refresh_rate = { secs = 0, nanos = 100_000_000 }
+max_log_lines = 1000
+show_help_by_default = true
+
+# [ui.tui.keybindings]
+# quit = ["q", "ctrl-q"]
+# select-next = ["j", "down", "ctrl-n"]
+"#;
+
 /// Configuration file loader
 pub struct ConfigLoader;
 
 impl ConfigLoader {
     /// Default configuration file locations (in order of preference)
     pub fn default_config_paths() -> Vec<PathBuf> {
-        let mut paths = Vec::new();
-
         // Current directory
-        paths.push(PathBuf::from("firecrawl.toml"));
-        paths.push(PathBuf::from("firecrawl.yaml"));
-        paths.push(PathBuf::from("firecrawl.yml"));
-        paths.push(PathBuf::from(".firecrawl.toml"));
-        paths.push(PathBuf::from(".firecrawl.yaml"));
-        paths.push(PathBuf::from(".firecrawl.yml"));
+        let mut paths = vec![
+            PathBuf::from("firecrawl.toml"),
+            PathBuf::from("firecrawl.yaml"),
+            PathBuf::from("firecrawl.yml"),
+            PathBuf::from(".firecrawl.toml"),
+            PathBuf::from(".firecrawl.yaml"),
+            PathBuf::from(".firecrawl.yml"),
+        ];
 
         // User home directory
         if let Some(home_dir) = dirs::home_dir() {
@@ -39,6 +91,11 @@ impl ConfigLoader {
         paths
     }
 
+    /// Per-user configuration directory (`~/.config/firecrawl`)
+    pub fn config_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home_dir| home_dir.join(".config").join("firecrawl"))
+    }
+
     /// Load configuration from the first found file
     pub fn load() -> FirecrawlResult<AppConfig> {
         let paths = Self::default_config_paths();
@@ -169,7 +226,7 @@ impl ConfigLoader {
             config.api.timeout = env_config.api.timeout;
         }
 
-        if env_config.output.default_directory != PathBuf::from("./output") {
+        if env_config.output.default_directory.as_path() != Path::new("./output") {
             config.output.default_directory = env_config.output.default_directory;
         }
         if env_config.output.default_format != crate::cli::OutputFormat::Markdown {
@@ -190,57 +247,13 @@ impl ConfigLoader {
     /// Generate a sample configuration file
     pub fn generate_sample_config() -> String {
         let sample_config = AppConfig::default();
-        Self::serialize_toml(&sample_config).unwrap_or_else(|_| {
-            r#"# Firecrawl CLI Configuration
-
-[api]
-base_url = "https://api.firecrawl.dev"
-# api_key = "your-api-key-here"
-timeout = 30  # seconds
-max_retries = 3
-retry_delay = 1000  # milliseconds
-
-[output]
-default_directory = "./output"
-default_format = "markdown"
-create_date_subdirectories = false
-# filename_prefix = "firecrawl_"
-overwrite_existing = false
-max_filename_length = 255
-
-[execution]
-max_concurrent_tasks = 4
-# default_crawl_limit = 10
-progress_update_interval = 500  # milliseconds
-verbose_logging = false
-
-[execution.cache]
-enabled = false
-directory = "./cache"
-ttl = 3600  # seconds
-max_size_mb = 100
-
-[ui]
-enable_colors = true
-
-[ui.theme]
-color_scheme = "default"
-use_unicode = true
-
-[ui.tui]
-refresh_rate = 100  # milliseconds
-max_log_lines = 1000
-show_help_by_default = true
-"#
-            .to_string()
-        })
+        Self::serialize_toml(&sample_config).unwrap_or_else(|_| SAMPLE_CONFIG.to_string())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     use tempfile::tempdir;
 
     #[test]
@@ -270,6 +283,25 @@ mod tests {
         assert!(sample.contains("[output]"));
     }
 
+    #[test]
+    fn test_sample_config_examples_are_valid() {
+        // Uncomment the example settings, leaving the explanatory comments
+        let uncommented: Vec<&str> = SAMPLE_CONFIG
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(example) if example.starts_with('[') || example.contains(" = ") => example,
+                _ => line,
+            })
+            .collect();
+        let config = ConfigLoader::parse_toml(&uncommented.join("\n")).unwrap();
+
+        config.validate().unwrap();
+        assert_eq!(config.api.api_key.as_deref(), Some("your-api-key-here"));
+        assert_eq!(config.ui.tui.keybindings.len(), 2);
+        crate::tui::keymap::Keymap::from_config(&config.ui.tui.keybindings).unwrap();
+        crate::tui::theme::Theme::from_config(&config.ui).unwrap();
+    }
+
     #[test]
     fn test_default_config_paths() {
         let paths = ConfigLoader::default_config_paths();
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
@@ -12,7 +13,7 @@ pub use loader::*;
 pub use environment::*;
 
 /// Application configuration structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AppConfig {
     /// API configuration
     pub api: ApiConfig,
@@ -27,17 +28,6 @@ pub struct AppConfig {
     pub ui: UiConfig,
 }
 
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            api: ApiConfig::default(),
-            output: OutputConfig::default(),
-            execution: ExecutionConfig::default(),
-            ui: UiConfig::default(),
-        }
-    }
-}
-
 /// API-related configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiConfig {
@@ -214,6 +204,10 @@ pub struct ThemeConfig {
 
     /// Whether to use Unicode characters
     pub use_unicode: bool,
+
+    /// User-defined palettes, selectable by name through `color_scheme`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub palettes: BTreeMap<String, PaletteConfig>,
 }
 
 impl Default for ThemeConfig {
@@ -221,10 +215,23 @@ impl Default for ThemeConfig {
         Self {
             color_scheme: "default".to_string(),
             use_unicode: true,
+            palettes: BTreeMap::new(),
         }
     }
 }
 
+/// User-defined TUI palette; roles it does not set come from `base`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaletteConfig {
+    /// Builtin theme to start from (`dark`, `light` or `high-contrast`)
+    pub base: Option<String>,
+
+    /// Colors by role, e.g. `title = "cyan"` or `error = "#ff5555"`
+    #[serde(flatten)]
+    pub colors: BTreeMap<String, String>,
+}
+
 /// TUI-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TuiConfig {
@@ -236,6 +243,10 @@ pub struct TuiConfig {
 
     /// Whether to show the help panel by default
     pub show_help_by_default: bool,
+
+    /// Key bindings by action name, e.g. `quit = ["q", "ctrl-q"]`; actions not listed keep their defaults
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<String, Vec<String>>,
 }
 
 impl Default for TuiConfig {
@@ -244,6 +255,7 @@ impl Default for TuiConfig {
             refresh_rate: Duration::from_millis(100),
             max_log_lines: 1000,
             show_help_by_default: true,
+            keybindings: BTreeMap::new(),
         }
     }
 }
@@ -280,13 +292,12 @@ impl AppConfig {
         }
 
         // Validate proxy configuration if present
-        if let Some(proxy) = &self.api.proxy {
-            if proxy.url.is_empty() {
+        if let Some(proxy) = &self.api.proxy
+            && proxy.url.is_empty() {
                 return Err(FirecrawlError::ConfigurationError(
                     "Proxy URL cannot be empty".to_string()
                 ));
             }
-        }
 
         Ok(())
     }
@@ -314,6 +325,12 @@ pub struct AppConfigBuilder {
     config: AppConfig,
 }
 
+impl Default for AppConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AppConfigBuilder {
     pub fn new() -> Self {
         Self {
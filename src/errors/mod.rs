@@ -1,4 +1,5 @@
 use crate::storage::StorageError;
+use serde::Serialize;
 use thiserror::Error;
 
 /// Domain-specific error types for the Firecrawl CLI
@@ -20,6 +21,10 @@ pub enum FirecrawlError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    /// Invalid command line, as reported by the argument parser
+    #[error("Usage error: {0}")]
+    UsageError(String),
+
     /// Execution errors
     #[error("Execution error: {0}")]
     ExecutionError(String),
@@ -43,6 +48,14 @@ pub enum FirecrawlError {
     /// Timeout errors
     #[error("Operation timed out: {0}")]
     TimeoutError(String),
+
+    /// Some items of a task succeeded and others failed
+    #[error("Partially completed: {0}")]
+    PartialSuccess(String),
+
+    /// The user interrupted the operation
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
 }
 
 /// API-specific error types
@@ -121,25 +134,31 @@ impl FirecrawlError {
             FirecrawlError::StorageError(_) => "STORAGE_ERROR",
             FirecrawlError::ConfigurationError(_) => "CONFIG_ERROR",
             FirecrawlError::ValidationError(_) => "VALIDATION_ERROR",
+            FirecrawlError::UsageError(_) => "USAGE_ERROR",
             FirecrawlError::ExecutionError(_) => "EXECUTION_ERROR",
             FirecrawlError::NetworkError(_) => "NETWORK_ERROR",
             FirecrawlError::UiError(_) => "UI_ERROR",
             FirecrawlError::AuthenticationError(_) => "AUTH_ERROR",
             FirecrawlError::RateLimitError(_) => "RATE_LIMIT_ERROR",
             FirecrawlError::TimeoutError(_) => "TIMEOUT_ERROR",
+            FirecrawlError::PartialSuccess(_) => "PARTIAL_SUCCESS",
+            FirecrawlError::Cancelled(_) => "CANCELLED",
         }
     }
 
+    /// Process exit code for this error
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from_error_code(self.error_code())
+    }
+
     /// Check if this is a retryable error
     pub fn is_retryable(&self) -> bool {
         match self {
             FirecrawlError::NetworkError(_) => true,
-            FirecrawlError::ApiError(api_error) => match api_error {
-                ApiError::RequestError(_) => true,
-                ApiError::Timeout(_) => true,
-                ApiError::RateLimitExceeded => true,
-                _ => false,
-            },
+            FirecrawlError::ApiError(api_error) => matches!(
+                api_error,
+                ApiError::RequestError(_) | ApiError::Timeout(_) | ApiError::RateLimitExceeded
+            ),
             FirecrawlError::TimeoutError(_) => true,
             FirecrawlError::StorageError(_) => false, // Usually not retryable
             _ => false,
@@ -149,10 +168,12 @@ impl FirecrawlError {
     /// Get user-friendly error message
     pub fn user_message(&self) -> String {
         match self {
-            FirecrawlError::ApiError(ApiError::AuthenticationFailed(_)) => {
+            FirecrawlError::ApiError(ApiError::AuthenticationFailed(_))
+            | FirecrawlError::AuthenticationError(_) => {
                 "Please check your API key. You can set it using the FIRECRAWL_API_KEY environment variable.".to_string()
             }
-            FirecrawlError::ApiError(ApiError::RateLimitExceeded) => {
+            FirecrawlError::ApiError(ApiError::RateLimitExceeded)
+            | FirecrawlError::RateLimitError(_) => {
                 "You've exceeded the rate limit. Please wait and try again later.".to_string()
             }
             FirecrawlError::ValidationError(msg) => {
@@ -167,10 +188,139 @@ impl FirecrawlError {
             _ => self.to_string(),
         }
     }
+
+    /// Classify an unsuccessful HTTP response of the API by its status
+    pub fn from_status(status: u16, message: String) -> Self {
+        match status {
+            401 | 403 => FirecrawlError::AuthenticationError(message),
+            429 => FirecrawlError::RateLimitError(message),
+            _ => FirecrawlError::ApiError(ApiError::ApiFailure { status, message }),
+        }
+    }
+
+    /// Classify an error returned by the API client by its underlying cause
+    pub fn from_client_error(err: &anyhow::Error) -> Self {
+        if let Some(error) = err.downcast_ref::<FirecrawlError>() {
+            return error.clone();
+        }
+        let Some(request_error) = err.downcast_ref::<reqwest::Error>() else {
+            return FirecrawlError::ApiError(ApiError::Other(err.to_string()));
+        };
+        match request_error.status().map(|status| status.as_u16()) {
+            Some(401 | 403) => return FirecrawlError::AuthenticationError(err.to_string()),
+            Some(429) => return FirecrawlError::RateLimitError(err.to_string()),
+            _ => {}
+        }
+        if request_error.is_timeout() {
+            FirecrawlError::TimeoutError(err.to_string())
+        } else if request_error.is_connect() {
+            FirecrawlError::NetworkError(NetworkError::ConnectionFailed(err.to_string()))
+        } else if request_error.is_decode() {
+            FirecrawlError::ApiError(ApiError::InvalidResponse(err.to_string()))
+        } else {
+            FirecrawlError::ApiError(ApiError::RequestError(err.to_string()))
+        }
+    }
+}
+
+/// Exit codes of the CLI, one per class of [`FirecrawlError::error_code`].
+/// The values are stable so scripts can branch on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    /// Any failure without a more specific code
+    Failure = 1,
+    /// Invalid command line, as reported by the argument parser
+    Usage = 2,
+    PartialSuccess = 3,
+    Validation = 4,
+    Configuration = 5,
+    Authentication = 6,
+    RateLimited = 7,
+    Network = 8,
+    Timeout = 9,
+    Api = 10,
+    Storage = 11,
+    /// Interrupted with Ctrl-C, like the shell's 128 + SIGINT
+    Interrupted = 130,
+}
+
+impl ExitCode {
+    pub const ALL: [ExitCode; 13] = [
+        ExitCode::Success,
+        ExitCode::Failure,
+        ExitCode::Usage,
+        ExitCode::PartialSuccess,
+        ExitCode::Validation,
+        ExitCode::Configuration,
+        ExitCode::Authentication,
+        ExitCode::RateLimited,
+        ExitCode::Network,
+        ExitCode::Timeout,
+        ExitCode::Api,
+        ExitCode::Storage,
+        ExitCode::Interrupted,
+    ];
+
+    /// Exit code for an error code of [`FirecrawlError::error_code`]
+    pub fn from_error_code(code: &str) -> Self {
+        match code {
+            "USAGE_ERROR" => ExitCode::Usage,
+            "PARTIAL_SUCCESS" => ExitCode::PartialSuccess,
+            "VALIDATION_ERROR" => ExitCode::Validation,
+            "CONFIG_ERROR" => ExitCode::Configuration,
+            "AUTH_ERROR" => ExitCode::Authentication,
+            "RATE_LIMIT_ERROR" => ExitCode::RateLimited,
+            "NETWORK_ERROR" => ExitCode::Network,
+            "TIMEOUT_ERROR" => ExitCode::Timeout,
+            "API_ERROR" => ExitCode::Api,
+            "STORAGE_ERROR" => ExitCode::Storage,
+            "CANCELLED" => ExitCode::Interrupted,
+            _ => ExitCode::Failure,
+        }
+    }
+
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// One line summary for the exit code table in the help
+    pub fn description(self) -> &'static str {
+        match self {
+            ExitCode::Success => "success",
+            ExitCode::Failure => "unexpected failure",
+            ExitCode::Usage => "invalid command line (USAGE_ERROR)",
+            ExitCode::PartialSuccess => "some pages failed, the others were saved",
+            ExitCode::Validation => "invalid input (VALIDATION_ERROR)",
+            ExitCode::Configuration => "invalid configuration (CONFIG_ERROR)",
+            ExitCode::Authentication => "missing or rejected API key (AUTH_ERROR)",
+            ExitCode::RateLimited => "API rate limit exceeded (RATE_LIMIT_ERROR)",
+            ExitCode::Network => "server unreachable (NETWORK_ERROR)",
+            ExitCode::Timeout => "operation timed out (TIMEOUT_ERROR)",
+            ExitCode::Api => "API request failed (API_ERROR)",
+            ExitCode::Storage => "output could not be written (STORAGE_ERROR)",
+            ExitCode::Interrupted => "interrupted with Ctrl-C (CANCELLED)",
+        }
+    }
+
+    /// The exit code table, for the help of the CLI
+    pub fn table() -> String {
+        let mut table = String::from("Exit codes:\n");
+        for exit_code in Self::ALL {
+            table.push_str(&format!("  {:>2}  {}\n", exit_code.code(), exit_code.description()));
+        }
+        table
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(exit_code: ExitCode) -> Self {
+        std::process::ExitCode::from(exit_code.code())
+    }
 }
 
 /// Error context for better debugging
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ErrorContext {
     pub operation: String,
     pub url: Option<String>,
@@ -203,7 +353,7 @@ impl ErrorContext {
 }
 
 /// Enhanced error with context
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 #[error("{error}")]
 pub struct ContextualError {
     #[source]
@@ -219,5 +369,82 @@ impl ContextualError {
     pub fn with_context(error: FirecrawlError, operation: &str, component: &str) -> Self {
         Self::new(error, ErrorContext::new(operation, component))
     }
+
+    /// Recover the typed error behind an error that reached the top of the
+    /// CLI, falling back to an `EXECUTION_ERROR` for untyped ones
+    pub fn from_anyhow(err: &anyhow::Error, operation: &str, component: &str) -> Self {
+        if let Some(error) = err.downcast_ref::<ContextualError>() {
+            return error.clone();
+        }
+        let error = if let Some(error) = err.downcast_ref::<StorageError>() {
+            FirecrawlError::StorageError(error.clone())
+        } else if let Some(error) = err.downcast_ref::<std::io::Error>() {
+            FirecrawlError::StorageError(StorageError::FileSystem(error.to_string()))
+        } else if err.is::<FirecrawlError>() || err.is::<reqwest::Error>() {
+            FirecrawlError::from_client_error(err)
+        } else {
+            FirecrawlError::ExecutionError(format!("{:#}", err))
+        };
+        Self::with_context(error, operation, component)
+    }
+
+    pub fn exit_code(&self) -> ExitCode {
+        self.error.exit_code()
+    }
+
+    /// Machine-readable report of the error, for `--error-format json`
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.error.error_code(),
+            "message": self.error.to_string(),
+            "user_message": self.error.user_message(),
+            "context": self.context,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_follow_error_codes() {
+        let cases = [
+            (FirecrawlError::AuthenticationError("401".into()), 6),
+            (FirecrawlError::RateLimitError("429".into()), 7),
+            (FirecrawlError::from_status(401, "bad key".into()), 6),
+            (FirecrawlError::from_status(503, "down".into()), 10),
+            (FirecrawlError::ValidationError("url".into()), 4),
+            (FirecrawlError::UsageError("unexpected argument".into()), 2),
+            (FirecrawlError::NetworkError(NetworkError::DnsError("x".into())), 8),
+            (FirecrawlError::PartialSuccess("1 of 3 failed".into()), 3),
+            (FirecrawlError::StorageError(StorageError::FileSystem("full".into())), 11),
+            (FirecrawlError::ExecutionError("boom".into()), 1),
+            (FirecrawlError::Cancelled("crawl".into()), 130),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.exit_code().code(), code, "{}", error.error_code());
+        }
+
+        // The table lists every code once, in order
+        let codes: Vec<u8> = ExitCode::ALL.iter().map(|c| c.code()).collect();
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_untyped_errors_are_classified_for_the_json_report() {
+        let io = anyhow::Error::new(std::io::Error::other("disk full"));
+        let error = ContextualError::from_anyhow(&io, "scrape", "cli");
+        assert_eq!(error.exit_code(), ExitCode::Storage);
+
+        let context = ErrorContext::new("crawl", "cli").with_url("https://a.com");
+        let typed: anyhow::Error =
+            ContextualError::new(FirecrawlError::RateLimitError("slow down".into()), context).into();
+        let report = ContextualError::from_anyhow(&typed, "other", "cli").to_json();
+        assert_eq!(report["code"], "RATE_LIMIT_ERROR");
+        assert_eq!(report["message"], "Rate limit exceeded: slow down");
+        assert_eq!(report["context"]["operation"], "crawl");
+        assert_eq!(report["context"]["url"], "https://a.com");
+    }
 }
 
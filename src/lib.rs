@@ -26,7 +26,7 @@
 //!
 //! ## Quick Start
 //!
-//! ```rust
+//! ```rust,no_run
 //! use firecrawl_cli::{
 //!     services::{TaskService, TaskServiceBuilder},
 //!     config::{AppConfig, ConfigLoader},
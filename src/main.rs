@@ -1,18 +1,189 @@
 use anyhow::Result;
-use clap::Parser;
-use firecrawl_cli::api::FirecrawlClient;
-use firecrawl_cli::{cli::Cli, utils::*};
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::ExitCode as ProcessExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use firecrawl_cli::api::{CrawlRequest, CrawlWebhook, FirecrawlClient, ScrapeData};
+use firecrawl_cli::errors::{ContextualError, ErrorContext, ExitCode, FirecrawlError};
+use firecrawl_cli::services::{
+    ChangeWatcher, CrawlMonitorService, CrawlProgress, DiffStatus, EventStreamObserver,
+    ProgressService, ProgressServiceFactory, TaskId, Verbosity, WatchOptions, WebhookOptions,
+    WebhookProgressObserver, diff_output_dirs,
+};
+use firecrawl_cli::storage::{
+    CrawlRunOptions, FileSystemRepository, ManifestConfig, save_crawl_run,
+};
+use firecrawl_cli::tui::session::session_path;
+use firecrawl_cli::tui::{App, run_tui};
+use firecrawl_cli::{
+    cli::{Cli, CrawlOptions, ErrorFormat, OutputFormat},
+    config::{AppConfig, ConfigLoader},
+    utils::*,
+};
+use tokio_util::sync::CancellationToken;
 
-// Async main function that handles CLI commands and orchestrates the scraping/crawling process
+// How long the CLI waits at exit for webhook notifications still being sent
+const WEBHOOK_FLUSH_TIMEOUT: Duration = Duration::from_secs(15);
+
+// Async main function that runs the CLI and turns a failure into a typed exit code
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ProcessExitCode {
     dotenvy::dotenv().ok();
     // Parse command line arguments using clap
-    let cli = Cli::parse();
+    let (cli, matches) = match parse_args(std::env::args_os().collect()) {
+        Ok(parsed) => parsed,
+        Err(code) => return code.into(),
+    };
+    let error_format = cli.effective_error_format();
+    let operation = match matches.subcommand_name() {
+        Some(name) => name,
+        None if cli.tui => "tui",
+        None => "cli",
+    };
+
+    match run(cli, &matches).await {
+        Ok(()) => ExitCode::Success.into(),
+        Err(e) => {
+            let error = ContextualError::from_anyhow(&e, operation, "cli");
+            report_error(&error, error_format);
+            error.exit_code().into()
+        }
+    }
+}
+
+// Parse the command line. Help and version requests print and exit as
+// usual; an invalid command line is reported in the requested error format
+// and fails with the usage exit code.
+fn parse_args(args: Vec<OsString>) -> std::result::Result<(Cli, ArgMatches), ExitCode> {
+    let parsed = Cli::command()
+        .try_get_matches_from(&args)
+        .and_then(|matches| Ok((Cli::from_arg_matches(&matches)?, matches)));
+    let error = match parsed {
+        Ok(parsed) => return Ok(parsed),
+        Err(error) if !error.use_stderr() => error.exit(),
+        Err(error) => error,
+    };
+
+    match Cli::requested_error_format(&args) {
+        ErrorFormat::Json => {
+            let rendered = error.render().to_string();
+            let message = rendered.lines().next().unwrap_or_default();
+            let error = ContextualError::new(
+                FirecrawlError::UsageError(message.trim_start_matches("error: ").to_string()),
+                ErrorContext::new("parse", "cli"),
+            );
+            report_error(&error, ErrorFormat::Json);
+        }
+        ErrorFormat::Text => {
+            let _ = error.print();
+        }
+    }
+    Err(ExitCode::Usage)
+}
+
+// Print why the CLI failed on stderr, as text or as one JSON object
+fn report_error(error: &ContextualError, format: ErrorFormat) {
+    match format {
+        ErrorFormat::Text => {
+            eprintln!("❌ {}", error);
+            let hint = error.error.user_message();
+            if hint != error.error.to_string() {
+                eprintln!("💡 {}", hint);
+            }
+        }
+        ErrorFormat::Json => eprintln!("{}", error.to_json()),
+    }
+}
+
+// Cancel `cancellation` on the first Ctrl-C so the running task can wind down,
+// and exit right away on the second. `announce` is off when stderr carries
+// the event stream; the task's failure event reports the cancellation there.
+fn cancel_on_ctrl_c(cancellation: CancellationToken, announce: bool) {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            return;
+        }
+        if announce {
+            eprintln!("\n⏹️  Cancelling, press Ctrl-C again to exit immediately");
+        }
+        cancellation.cancel();
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(ExitCode::Interrupted.code().into());
+        }
+    });
+}
+
+// Handle CLI commands and orchestrate the scraping/crawling process
+async fn run(cli: Cli, matches: &ArgMatches) -> Result<()> {
+    // Launch the Terminal User Interface, preloading any URLs as scrape tasks
+    if cli.tui {
+        let mut app = App::from_config(effective_config(&cli, matches)?)?;
+        // Only a named session is restored and saved; a plain launch starts empty
+        if let Some(session) = &cli.session {
+            app.open_session(session_path(session)?)?;
+        }
+        for url in cli.urls {
+            app.add_scrape_task(url);
+        }
+        return run_tui(app).await;
+    }
+
+    let error_format = cli.effective_error_format();
 
     // Initialize the Firecrawl API client with the provided URL and API key
     let client = FirecrawlClient::new(&cli.api_url, cli.api_key.as_deref())?;
 
+    // Progress bars on stderr, or plain lines when it is not a terminal
+    let verbosity = cli.verbosity();
+    let progress = ProgressServiceFactory::create_terminal_service(verbosity).await;
+
+    // Machine-readable task events, on stderr or appended to a file
+    if cli.events {
+        progress
+            .register_observer(Arc::new(EventStreamObserver::stderr()))
+            .await;
+    } else if let Some(path) = &cli.events_file {
+        progress
+            .register_observer(Arc::new(EventStreamObserver::append_to_file(path)?))
+            .await;
+    }
+    // Notifications for unattended runs, sent once a task completes or fails
+    let webhooks = if cli.notify_webhook.is_empty() {
+        None
+    } else {
+        let options = WebhookOptions {
+            secret: cli.webhook_secret.clone(),
+            ..WebhookOptions::default()
+        };
+        let observer = Arc::new(WebhookProgressObserver::new(
+            cli.notify_webhook.clone(),
+            options,
+        )?);
+        progress.register_observer(observer.clone()).await;
+        Some(observer)
+    };
+
+    let result = run_command(cli, client, &progress, verbosity, error_format).await;
+
+    // Notifications are sent in the background; give them, including those
+    // of a failed command, a bounded time to go out before exiting
+    if let Some(webhooks) = webhooks {
+        webhooks.flush(WEBHOOK_FLUSH_TIMEOUT).await;
+    }
+    result
+}
+
+// Run the subcommand of a plain CLI invocation
+async fn run_command(
+    cli: Cli,
+    client: FirecrawlClient,
+    progress: &Arc<dyn ProgressService + Send + Sync>,
+    verbosity: Verbosity,
+    error_format: ErrorFormat,
+) -> Result<()> {
     // Handle different CLI commands: Scrape and Crawl
     if let Some(command) = cli.command {
         match command {
@@ -21,93 +192,154 @@ async fn main() -> Result<()> {
                 println!("🔥 Scraping: {}", url);
 
                 // Execute the scrape request to the API
-                match client.scrape(&url).await {
-                    Ok(result) => {
-                        // Display the scrape result summary
-                        println!("{}", result);
-
-                        // Save HTML content if available
-                        if let Some(html) = result.html {
-                            save_html(&output_dir, &url, &html, Some("html content")).await?;
-                        }
-
-                        // Save Markdown content if available
-                        if let Some(markdown) = result.markdown {
-                            save_markdown(&output_dir, &url, &markdown, Some("markdown content"))
-                                .await?;
-                        }
-
-                        // Save metadata as JSON if there's any metadata available
-                        if !result.metadata.extra.is_empty() {
-                            let metadata = serde_json::json!({
-                                "metadata": result.metadata.extra
-                            });
-                            save_json(&output_dir, &url, &metadata, Some("metadata")).await?;
-                        }
-
-                        println!("✅ Scrape completed successfully!");
-                    }
+                let task_id = TaskId::next();
+                progress.notify_task_started(task_id, &url, "scrape").await;
+                let result = match client.scrape(&url).await {
+                    Ok(result) => result,
                     Err(e) => {
-                        // Handle scraping errors and display user-friendly message
-                        eprintln!("❌ Scrape failed: {}", e);
-                        return Err(e);
+                        // Handle scraping errors; main reports them once the progress output is done
+                        let error = FirecrawlError::from_client_error(&e);
+                        progress.notify_task_failed(task_id, &url, "scrape", &error).await;
+                        let context = ErrorContext::new("scrape", "cli").with_url(&url);
+                        return Err(ContextualError::new(error, context).into());
                     }
+                };
+
+                // The task completes once its files are written
+                let saved = save_scrape(progress, task_id, &url, &result, &output_dir).await;
+                if let Err(e) = saved {
+                    let error = ContextualError::from_anyhow(&e, "scrape", "cli").error;
+                    progress.notify_task_failed(task_id, &url, "scrape", &error).await;
+                    return Err(e);
                 }
+                progress.notify_task_completed(task_id, &url, "scrape").await;
+
+                // Display the scrape result summary
+                println!("{}", result);
+                println!("✅ Scrape completed successfully!");
             }
             // Handle the Crawl command for multi-page crawling
             firecrawl_cli::cli::Commands::Crawl {
                 url,
                 limit,
                 output_dir,
+                index,
+                sync,
+                removed,
+                fail_fast,
+                webhook,
+                webhook_events,
             } => {
                 println!("🕷️  Crawling: {} (limit: {:?})", url, limit);
 
-                // Execute the crawl request to the API with specified page limit
-                match client.crawl(&url, Some(limit)).await {
-                    Ok(results) => {
-                        // Check if any pages were crawled
-                        if results.is_empty() {
-                            println!("⚠️  No pages were crawled");
-                            return Ok(());
-                        }
+                // Ctrl-C cancels the job on the server; the pages crawled so
+                // far are still saved and recorded in the manifest
+                let cancellation = CancellationToken::new();
+                cancel_on_ctrl_c(cancellation.clone(), !cli.events);
+                let client = client.with_cancellation(cancellation);
 
-                        // Process each crawled page result
-                        for (i, result) in results.iter().enumerate() {
-                            // Save markdown content if available
-                            if let Some(markdown) = &result.markdown {
-                                let result_url = result.url.as_deref().unwrap_or(&url);
-                                save_markdown(&output_dir, result_url, markdown, Some("Metadata"))
-                                    .await?;
-                            }
+                let options = CrawlOptions {
+                    limit: Some(limit),
+                    ..Default::default()
+                };
+                let request = CrawlRequest::builder()
+                    .url(url.clone())
+                    .limit(options.limit)
+                    .webhook(webhook.map(|url| CrawlWebhook {
+                        events: webhook_events,
+                        ..CrawlWebhook::new(url)
+                    }))
+                    .build()
+                    .map_err(FirecrawlError::ValidationError)?;
+                let run_options = CrawlRunOptions {
+                    write_index: index,
+                    sync: sync.then_some(removed),
+                    fail_fast,
+                };
+                run_crawl(&client, progress, request, options, &output_dir, run_options).await?;
+            }
+            // Handle the Watch command for periodic change tracking
+            firecrawl_cli::cli::Commands::Watch {
+                url,
+                every,
+                modes,
+                tag,
+                max_checks,
+                output_dir,
+                json,
+            } => {
+                if !json {
+                    println!(
+                        "👀 Watching: {} (every {})",
+                        url,
+                        humantime::format_duration(every)
+                    );
+                }
 
-                            // Save metadata as JSON if available
-                            if !result.metadata.extra.is_empty() {
-                                let result_url = result.url.as_deref().unwrap_or(&url);
-                                let metadata = serde_json::json!({
-                                    "metadata": result.metadata.extra
-                                });
-                                save_json(
-                                    &output_dir,
-                                    result_url,
-                                    &metadata,
-                                    Some("Extra Metadata"),
-                                )
-                                .await?;
-                            }
+                let watcher = ChangeWatcher::new(
+                    client,
+                    WatchOptions {
+                        every,
+                        modes,
+                        tag,
+                        max_checks,
+                        output_dir,
+                    },
+                );
 
-                            // Display progress for each processed page
-                            let result_url = result.url.as_deref().unwrap_or(&url);
-                            println!("✅ Processed {}/{}: {}", i + 1, results.len(), result_url);
+                watcher
+                    .watch(&url, |event| match event {
+                        Ok(event) if json => match serde_json::to_string(&event) {
+                            Ok(line) => println!("{}", line),
+                            Err(e) => eprintln!("❌ Failed to encode event: {}", e),
+                        },
+                        Ok(event) => {
+                            println!(
+                                "[{}] {}: {}",
+                                event.checked_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                                event.status,
+                                event.url
+                            );
+                            if let Some(diff) = event.diff.as_deref().filter(|_| event.is_change()) {
+                                println!("{}", diff);
+                            }
+                        }
+                        // Keep stderr parseable for --events and --error-format json
+                        Err(e) if error_format == ErrorFormat::Json => {
+                            let context = ErrorContext::new("watch", "cli").with_url(&url);
+                            report_error(&ContextualError::new(e, context), error_format);
                         }
+                        Err(e) => eprintln!("❌ Check failed: {}", e),
+                    })
+                    .await;
+            }
+            // Handle the Diff command for comparing two saved runs locally
+            firecrawl_cli::cli::Commands::Diff {
+                old_dir,
+                new_dir,
+                url,
+                format,
+                output,
+            } => {
+                let repository = FileSystemRepository::new(new_dir.clone());
+                let report =
+                    diff_output_dirs(&repository, &old_dir, &new_dir, url.as_deref()).await?;
+                let rendered = report.render(format)?;
 
-                        // Display final crawl completion summary
-                        println!("🎉 Crawling completed! Processed {} pages", results.len());
-                    }
-                    Err(e) => {
-                        // Handle crawling errors and display user-friendly message
-                        eprintln!("❌ Crawl failed: {}", e);
-                        return Err(e);
+                match output {
+                    Some(path) => {
+                        tokio::fs::write(&path, rendered).await?;
+                        println!(
+                            "📝 {} added, {} removed, {} changed, {} unchanged; report saved to {:?}",
+                            report.count(DiffStatus::Added),
+                            report.count(DiffStatus::Removed),
+                            report.count(DiffStatus::Changed),
+                            report.count(DiffStatus::Unchanged),
+                            path
+                        );
                     }
+                    None if rendered.ends_with('\n') => print!("{}", rendered),
+                    None => println!("{}", rendered),
                 }
             }
         }
@@ -120,6 +352,603 @@ async fn main() -> Result<()> {
         println!("Use --tui flag to launch the Terminal User Interface");
     }
 
+    if verbosity == Verbosity::Verbose {
+        let stats = progress.get_statistics().await;
+        if stats.total_tasks > 0 {
+            eprintln!(
+                "📊 {} completed, {} failed, average {:.1?}",
+                stats.completed_tasks, stats.failed_tasks, stats.average_execution_time
+            );
+        }
+    }
+
     // Return success if all operations completed
     Ok(())
 }
+
+// Save the HTML, Markdown and metadata of a scraped page to `output_dir`,
+// reporting every file written
+async fn save_scrape(
+    progress: &Arc<dyn ProgressService + Send + Sync>,
+    task_id: TaskId,
+    url: &str,
+    result: &ScrapeData,
+    output_dir: &Path,
+) -> Result<()> {
+    let output_dir = &output_dir.to_path_buf();
+
+    // Save HTML content if available
+    if let Some(html) = &result.html {
+        let path = save_html(output_dir, url, html, Some("html content")).await?;
+        progress.notify_page_saved(task_id, url, "scrape", url, &path).await;
+    }
+
+    // Save Markdown content if available
+    if let Some(markdown) = &result.markdown {
+        let path = save_markdown(output_dir, url, markdown, Some("markdown content")).await?;
+        progress.notify_page_saved(task_id, url, "scrape", url, &path).await;
+    }
+
+    // Save metadata as JSON if there's any metadata available
+    if !result.metadata.extra.is_empty() {
+        let metadata = serde_json::json!({
+            "metadata": result.metadata.extra
+        });
+        let path = save_json(output_dir, url, &metadata, Some("metadata")).await?;
+        progress.notify_page_saved(task_id, url, "scrape", url, &path).await;
+    }
+    Ok(())
+}
+
+// Run a crawl job to the end, or until `client` is cancelled, then save the
+// pages crawled to `output_dir` and record the run in its manifest
+async fn run_crawl(
+    client: &FirecrawlClient,
+    progress: &Arc<dyn ProgressService + Send + Sync>,
+    request: CrawlRequest,
+    options: CrawlOptions,
+    output_dir: &Path,
+    run_options: CrawlRunOptions,
+) -> Result<()> {
+    let url = request.url.clone();
+
+    // Start the crawl job and wait for it to finish, reporting progress
+    let task_id = TaskId::next();
+    progress.notify_task_started(task_id, &url, "crawl").await;
+    let start = match client.crawl_url(request).await {
+        Ok(start) => start,
+        Err(e) => {
+            let error = FirecrawlError::from_client_error(&e);
+            progress.notify_task_failed(task_id, &url, "crawl", &error).await;
+            let context = ErrorContext::new("crawl", "cli").with_url(&url);
+            return Err(ContextualError::new(error, context).into());
+        }
+    };
+
+    // The monitor callback is synchronous; forward its updates to the
+    // progress service from a task of their own
+    let (update_sender, mut updates) = tokio::sync::mpsc::unbounded_channel::<CrawlProgress>();
+    let forwarder = {
+        let progress = Arc::clone(progress);
+        let url = url.clone();
+        tokio::spawn(async move {
+            while let Some(update) = updates.recv().await {
+                progress
+                    .notify_task_items(task_id, &url, "crawl", update.completed, update.total)
+                    .await;
+            }
+        })
+    };
+    let monitored = client
+        .monitor_crawl_job(
+            &start.job_id,
+            Box::new(move |update| {
+                let _ = update_sender.send(update);
+            }),
+        )
+        .await;
+    // The sender went with the callback, so this ends once drained
+    let _ = forwarder.await;
+    let job_id = start.job_id.clone();
+    let cancelled = client.is_cancelled();
+    let cancelled_error = |pages: usize| {
+        let context = ErrorContext::new("crawl", "cli")
+            .with_url(&url)
+            .with_info("job_id", &job_id);
+        let error = FirecrawlError::Cancelled(format!(
+            "crawl job {} stopped after {} pages",
+            job_id, pages
+        ));
+        ContextualError::new(error, context)
+    };
+    let results = match monitored {
+        Ok(results) => {
+            if cancelled {
+                println!("⏹️  Crawl cancelled, saving the {} pages crawled so far", results.len());
+            }
+            results
+        }
+        Err(error) => {
+            progress.notify_task_failed(task_id, &url, "crawl", &error).await;
+            let context = ErrorContext::new("crawl", "cli")
+                .with_url(&url)
+                .with_info("job_id", &start.job_id);
+            return Err(ContextualError::new(error, context).into());
+        }
+    };
+
+    // Check if any pages were crawled
+    if results.is_empty() {
+        if cancelled {
+            let error = cancelled_error(0);
+            progress.notify_task_failed(task_id, &url, "crawl", &error.error).await;
+            println!("⚠️  No pages were crawled");
+            return Err(error.into());
+        }
+        progress.notify_task_completed(task_id, &url, "crawl").await;
+        println!("⚠️  No pages were crawled");
+        return Ok(());
+    }
+
+    let repository = FileSystemRepository::new(output_dir.to_path_buf());
+    let config = ManifestConfig {
+        output_format: OutputFormat::Markdown,
+        options: Some(options),
+    };
+
+    // Save the pages and record the run in manifest.json; the task only
+    // completes once its output is on disk
+    let saved = save_crawl_run(
+        &repository,
+        &results,
+        &url,
+        Some(start.job_id),
+        config,
+        output_dir,
+        &run_options,
+    )
+    .await;
+    let run = match saved {
+        Ok(run) => run,
+        Err(e) => {
+            let context = ErrorContext::new("crawl", "cli")
+                .with_url(&url)
+                .with_info("job_id", &job_id);
+            let error = ContextualError::new(FirecrawlError::StorageError(e), context);
+            progress.notify_task_failed(task_id, &url, "crawl", &error.error).await;
+            return Err(error.into());
+        }
+    };
+    for (page_url, path) in &run.pages {
+        progress.notify_page_saved(task_id, &url, "crawl", page_url, path).await;
+    }
+
+    // Ctrl-C may also have come while the pages were being saved, and pages
+    // that could not be saved make the run a partial success
+    let failed = if client.is_cancelled() {
+        Some(cancelled_error(results.len()))
+    } else {
+        run.failures_file.as_ref().map(|path| {
+            let context = ErrorContext::new("crawl", "cli")
+                .with_url(&url)
+                .with_info("failures_file", &path.display().to_string());
+            let error = FirecrawlError::PartialSuccess(run.failures.to_string());
+            ContextualError::new(error, context)
+        })
+    };
+    match &failed {
+        Some(error) => {
+            progress.notify_task_failed(task_id, &url, "crawl", &error.error).await;
+        }
+        None => progress.notify_task_completed(task_id, &url, "crawl").await,
+    }
+
+    match &run.sync {
+        Some(report) => {
+            for (_, path) in &run.pages {
+                println!("✅ Wrote {:?}", path);
+            }
+            println!("🔄 Sync: {}", report);
+        }
+        None => {
+            for (i, (_, path)) in run.pages.iter().enumerate() {
+                println!("✅ Processed {}/{}: {:?}", i + 1, results.len(), path);
+            }
+        }
+    }
+    for path in &run.manifest_files {
+        println!("💾 Saved manifest: {:?}", path);
+    }
+    for failure in &run.failures.failures {
+        println!("⚠️  Could not save {}: {}", failure.url, failure.reason);
+    }
+    if let Some(path) = &run.failures_file {
+        println!("📝 Saved failure report: {:?}", path);
+    }
+    if let Some(error) = failed {
+        return Err(error.into());
+    }
+
+    // Display final crawl completion summary
+    println!("🎉 Crawling completed! Processed {} pages", results.len());
+    Ok(())
+}
+
+
+// Load the configuration from files and environment, letting API settings
+// given explicitly on the command line (or via FIRE_API_* variables) win
+fn effective_config(cli: &Cli, matches: &ArgMatches) -> Result<AppConfig> {
+    let mut config = ConfigLoader::load()?;
+
+    if !matches!(matches.value_source("api_url"), Some(ValueSource::DefaultValue) | None) {
+        config.api.base_url = cli.api_url.clone();
+    }
+    if cli.api_key.is_some() {
+        config.api.api_key = cli.api_key.clone();
+    }
+
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use firecrawl_cli::services::{ProgressObserver, ProgressServiceFactory};
+    use firecrawl_cli::storage::savers::MarkdownSaver;
+    use firecrawl_cli::storage::{ContentSaver, MANIFEST_FILE_NAME};
+    use std::sync::Mutex;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    // Local API whose crawl job reports one more page on every poll, and
+    // completes on the first poll when `complete` is set. Returns the base URL
+    // and the method and path of every request.
+    async fn crawl_api(complete: bool) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/v2", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+        tokio::spawn(async move {
+            let mut polls = 0;
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                if request_line.is_empty() {
+                    continue;
+                }
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    match line.trim_end().split_once(": ") {
+                        Some((key, value)) if key.eq_ignore_ascii_case("content-length") => {
+                            length = value.parse().unwrap();
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.unwrap();
+                let request: Vec<&str> = request_line.split_whitespace().take(2).collect();
+                log.lock().unwrap().push(request.join(" "));
+
+                let body = match request[0] {
+                    "POST" => serde_json::json!({ "id": "job-1" }),
+                    "GET" => {
+                        polls += 1;
+                        let pages: Vec<_> = (0..polls)
+                            .map(|i| {
+                                serde_json::json!({
+                                    "markdown": format!("# Page {}", i),
+                                    "url": format!("https://example.com/{}", i),
+                                    "metadata": { "title": format!("Page {}", i) },
+                                })
+                            })
+                            .collect();
+                        let status = if complete { "completed" } else { "scraping" };
+                        serde_json::json!({
+                            "status": status,
+                            "completed": polls,
+                            "total": if complete { polls } else { 100 },
+                            "data": pages,
+                        })
+                    }
+                    _ => serde_json::json!({ "status": "cancelled" }),
+                }
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = reader.into_inner().write_all(response.as_bytes()).await;
+            }
+        });
+        (base_url, requests)
+    }
+
+    // Observer pressing Ctrl-C: cancels the token on the first progress
+    // update, or once the crawl is saving its pages
+    struct CancelOn {
+        saving: bool,
+        cancellation: CancellationToken,
+    }
+
+    #[async_trait]
+    impl ProgressObserver for CancelOn {
+        async fn on_task_started(&self, _task_id: TaskId, _url: &str, _task_type: &str) {}
+
+        async fn on_task_progress(&self, _: TaskId, _: &str, _: &str, _progress: f32) {
+            if !self.saving {
+                self.cancellation.cancel();
+            }
+        }
+
+        async fn on_task_completed(&self, _task_id: TaskId, _url: &str, _task_type: &str) {}
+
+        async fn on_page_saved(&self, _: TaskId, _: &str, _: &str, _: &str, _: &Path) {
+            if self.saving {
+                self.cancellation.cancel();
+            }
+        }
+
+        async fn on_task_failed(&self, _: TaskId, _: &str, _: &str, _: &FirecrawlError) {}
+
+        fn observer_id(&self) -> &str {
+            "cancel-on"
+        }
+    }
+
+    // Crawl until the observer cancels, returning the exit code, the
+    // requests the API received and the output directory
+    async fn cancelled_crawl(saving: bool) -> (ExitCode, Vec<String>, tempfile::TempDir) {
+        let (base_url, requests) = crawl_api(saving).await;
+        let cancellation = CancellationToken::new();
+        let client = FirecrawlClient::new(&base_url, None)
+            .unwrap()
+            .with_cancellation(cancellation.clone());
+        let progress = ProgressServiceFactory::create_silent_service();
+        progress
+            .register_observer(Arc::new(CancelOn {
+                saving,
+                cancellation,
+            }))
+            .await;
+
+        let output = tempfile::tempdir().unwrap();
+        let request = CrawlRequest::builder()
+            .url("https://example.com".to_string())
+            .build()
+            .unwrap();
+        let result = run_crawl(
+            &client,
+            &progress,
+            request,
+            CrawlOptions::default(),
+            output.path(),
+            CrawlRunOptions::default(),
+        )
+        .await;
+
+        let error = ContextualError::from_anyhow(&result.unwrap_err(), "crawl", "cli");
+        assert!(matches!(error.error, FirecrawlError::Cancelled(_)));
+        let requests = requests.lock().unwrap().clone();
+        (error.exit_code(), requests, output)
+    }
+
+    #[tokio::test]
+    async fn test_crawl_cancelled_while_running_saves_its_pages_and_is_interrupted() {
+        let (exit_code, requests, output) = cancelled_crawl(false).await;
+
+        assert_eq!(exit_code, ExitCode::Interrupted);
+        assert!(requests.contains(&"DELETE /v2/crawl/job-1".to_string()));
+        assert!(output.path().join(MANIFEST_FILE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_crawl_cancelled_while_saving_is_interrupted() {
+        let (exit_code, requests, output) = cancelled_crawl(true).await;
+
+        assert_eq!(exit_code, ExitCode::Interrupted);
+        assert!(!requests.iter().any(|request| request.starts_with("DELETE")));
+        assert!(output.path().join(MANIFEST_FILE_NAME).exists());
+    }
+
+    // Observer recording the name of every notification, with the error code
+    // of failures, and the task ids they came with
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(TaskId, String)>>);
+
+    impl Recorder {
+        fn push(&self, task_id: TaskId, event: String) {
+            self.0.lock().unwrap().push((task_id, event));
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.0.lock().unwrap().iter().map(|(_, event)| event.clone()).collect()
+        }
+    }
+
+    #[async_trait]
+    impl ProgressObserver for Recorder {
+        async fn on_task_started(&self, task_id: TaskId, _url: &str, _task_type: &str) {
+            self.push(task_id, "task_started".to_string());
+        }
+
+        async fn on_task_progress(&self, _: TaskId, _: &str, _: &str, _progress: f32) {}
+
+        async fn on_task_completed(&self, task_id: TaskId, _url: &str, _task_type: &str) {
+            self.push(task_id, "task_completed".to_string());
+        }
+
+        async fn on_page_saved(&self, task_id: TaskId, _: &str, _: &str, _: &str, _: &Path) {
+            self.push(task_id, "page_saved".to_string());
+        }
+
+        async fn on_task_failed(&self, task_id: TaskId, _: &str, _: &str, error: &FirecrawlError) {
+            self.push(task_id, format!("task_failed {}", error.error_code()));
+        }
+
+        fn observer_id(&self) -> &str {
+            "recorder"
+        }
+    }
+
+    // Run a crawl that completes on its first poll, saving to `output_dir`
+    async fn recorded_crawl(output_dir: &Path) -> (Result<()>, Arc<Recorder>) {
+        let (base_url, _) = crawl_api(true).await;
+        let client = FirecrawlClient::new(&base_url, None).unwrap();
+        let progress = ProgressServiceFactory::create_silent_service();
+        let recorder = Arc::new(Recorder::default());
+        progress.register_observer(recorder.clone()).await;
+
+        let request = CrawlRequest::builder()
+            .url("https://example.com".to_string())
+            .build()
+            .unwrap();
+        let result = run_crawl(
+            &client,
+            &progress,
+            request,
+            CrawlOptions::default(),
+            output_dir,
+            CrawlRunOptions::default(),
+        )
+        .await;
+        (result, recorder)
+    }
+
+    #[tokio::test]
+    async fn test_crawl_completes_after_its_pages_are_saved() {
+        let output = tempfile::tempdir().unwrap();
+        let (result, recorder) = recorded_crawl(output.path()).await;
+
+        result.unwrap();
+        assert_eq!(
+            recorder.events(),
+            vec!["task_started", "page_saved", "task_completed"]
+        );
+        let ids: Vec<TaskId> = recorder.0.lock().unwrap().iter().map(|(id, _)| *id).collect();
+        assert!(ids.iter().all(|id| *id == ids[0]));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_whose_pages_cannot_be_saved_fails() {
+        // A file where the output directory should be
+        let output = tempfile::NamedTempFile::new().unwrap();
+        let (result, recorder) = recorded_crawl(output.path()).await;
+
+        let error = ContextualError::from_anyhow(&result.unwrap_err(), "crawl", "cli");
+        let events = recorder.events();
+        assert_eq!(
+            events.last().unwrap(),
+            &format!("task_failed {}", error.error.error_code())
+        );
+        assert!(!events.contains(&"task_completed".to_string()));
+    }
+
+    // Local webhook endpoint answering 200 and recording every payload
+    async fn webhook_listener() -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let payloads = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&payloads);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    if let Some((key, value)) = line.trim_end().split_once(": ")
+                        && key.eq_ignore_ascii_case("content-length")
+                    {
+                        length = value.parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.unwrap();
+                log.lock().unwrap().push(serde_json::from_slice(&body).unwrap());
+                let response = "HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+                let _ = reader.into_inner().write_all(response.as_bytes()).await;
+            }
+        });
+        (url, payloads)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_reports_a_crawl_whose_pages_could_not_be_saved() {
+        let (base_url, _) = crawl_api(true).await;
+        let (hook_url, payloads) = webhook_listener().await;
+        let client = FirecrawlClient::new(&base_url, None).unwrap();
+        let progress = ProgressServiceFactory::create_silent_service();
+        let observer = WebhookProgressObserver::new(vec![hook_url], WebhookOptions::default());
+        let webhooks = Arc::new(observer.unwrap());
+        progress.register_observer(webhooks.clone()).await;
+
+        // A directory where the only page's file goes makes its write fail
+        let output = tempfile::tempdir().unwrap();
+        let blocked = MarkdownSaver.generate_filename("https://example.com/0", Some(0));
+        std::fs::create_dir(output.path().join(blocked)).unwrap();
+        let request = CrawlRequest::builder()
+            .url("https://example.com".to_string())
+            .build()
+            .unwrap();
+        let result = run_crawl(
+            &client,
+            &progress,
+            request,
+            CrawlOptions::default(),
+            output.path(),
+            CrawlRunOptions::default(),
+        )
+        .await;
+        webhooks.flush(Duration::from_secs(5)).await;
+
+        let error = ContextualError::from_anyhow(&result.unwrap_err(), "crawl", "cli");
+        assert_eq!(error.exit_code(), ExitCode::PartialSuccess);
+        let payloads = payloads.lock().unwrap().clone();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0]["event"], "task_failed");
+        assert_eq!(payloads[0]["error_code"], "PARTIAL_SUCCESS");
+    }
+
+    #[test]
+    fn test_errors_are_json_when_events_go_to_stderr() {
+        let parse = |args: &[&str]| {
+            let matches = Cli::command().get_matches_from(args);
+            Cli::from_arg_matches(&matches).unwrap()
+        };
+
+        let cli = parse(&["firecrawl_cli", "scrape", "https://example.com"]);
+        assert_eq!(cli.effective_error_format(), ErrorFormat::Text);
+        let cli = parse(&["firecrawl_cli", "scrape", "--events", "https://example.com"]);
+        assert_eq!(cli.effective_error_format(), ErrorFormat::Json);
+    }
+
+    #[test]
+    fn test_invalid_command_line_fails_with_the_usage_code() {
+        let args = |args: &[&str]| args.iter().map(OsString::from).collect::<Vec<_>>();
+
+        let invalid = args(&["firecrawl_cli", "scrape", "--no-such-flag", "--error-format", "json"]);
+        assert_eq!(Cli::requested_error_format(&invalid), ErrorFormat::Json);
+        assert!(matches!(parse_args(invalid), Err(ExitCode::Usage)));
+
+        let invalid = args(&["firecrawl_cli", "crawl", "--events", "--limit", "many", "https://a.com"]);
+        assert_eq!(Cli::requested_error_format(&invalid), ErrorFormat::Json);
+        assert!(matches!(parse_args(invalid), Err(ExitCode::Usage)));
+
+        let invalid = args(&["firecrawl_cli", "scrape", "--no-such-flag"]);
+        assert_eq!(Cli::requested_error_format(&invalid), ErrorFormat::Text);
+        assert!(matches!(parse_args(invalid), Err(ExitCode::Usage)));
+
+        let valid = args(&["firecrawl_cli", "scrape", "https://example.com"]);
+        assert!(parse_args(valid).is_ok());
+    }
+}
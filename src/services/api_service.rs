@@ -4,8 +4,8 @@ use std::time::Duration;
 
 use super::CrawlProgress;
 use crate::api::models::{
-    crawl_model::{CrawlOptions, CrawlRequest, CrawlResponse},
-    scrape_model::{ScrapeOptions, ScrapeRequest, ScrapeResponse},
+    crawl_model::{CrawlRequest, CrawlResponse},
+    scrape_model::{ScrapeRequest, ScrapeResponse},
 };
 use crate::api::services::client::FirecrawlClient;
 use crate::config::{ApiConfig, AppConfig};
@@ -128,6 +128,8 @@ impl ApiService for DefaultApiService {
                 published_date: None,
                 modified_date: None,
                 site_name: None,
+                title: None,
+                status_code: None,
             },
         };
 
@@ -264,20 +266,12 @@ impl ApiService for MockApiService {
                 published_date: None,
                 modified_date: None,
                 site_name: None,
+                title: None,
+                status_code: None,
             },
         })
     }
 
-    fn monitor_crawl_job<'a>(
-        &'a self,
-        _job_id: &'a str,
-        mut _progress_callback: Box<dyn FnMut(CrawlProgress) + Send + 'a>,
-    ) -> std::pin::Pin<
-        Box<dyn std::future::Future<Output = FirecrawlResult<Vec<CrawlResponse>>> + Send + 'a>,
-    > {
-        Box::pin(async move { Ok(vec![]) })
-    }
-
     async fn get_status(&self) -> FirecrawlResult<ApiStatus> {
         Ok(ApiStatus {
             is_healthy: true,
@@ -309,8 +303,7 @@ mod tests {
 
         let scrape_request = ScrapeRequest::builder()
             .url("https://example.com".to_string())
-            .build()
-            .unwrap();
+            .build();
 
         let result = service.scrape_url(scrape_request).await.unwrap();
         assert!(result.success);
@@ -1,13 +1,12 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::cli::OutputFormat;
 use crate::commands::CommandResult;
 use crate::config::{AppConfig, CacheConfig};
-use crate::errors::{FirecrawlError, FirecrawlResult};
+use crate::errors::FirecrawlResult;
 
 /// Trait for caching operations
 #[async_trait]
@@ -90,6 +89,7 @@ pub struct MemoryCacheService {
 #[derive(Debug, Clone)]
 struct CacheEntry {
     data: CacheData,
+    #[allow(dead_code)]
     created_at: chrono::DateTime<chrono::Utc>,
     expires_at: Option<chrono::DateTime<chrono::Utc>>,
     access_count: u64,
@@ -159,7 +159,7 @@ impl CacheService for MemoryCacheService {
     ) -> FirecrawlResult<()> {
         let key = Self::generate_key(url, format, "scrape");
         let now = chrono::Utc::now();
-        let expires_at = if self.config.ttl.as_secs() > 0 {
+        let expires_at = if !self.config.ttl.is_zero() {
             Some(now + self.config.ttl)
         } else {
             None
@@ -245,7 +245,7 @@ impl CacheService for MemoryCacheService {
     ) -> FirecrawlResult<()> {
         let key = Self::generate_key(url, format, "crawl");
         let now = chrono::Utc::now();
-        let expires_at = if self.config.ttl.as_secs() > 0 {
+        let expires_at = if !self.config.ttl.is_zero() {
             Some(now + self.config.ttl)
         } else {
             None
@@ -330,10 +330,10 @@ impl CacheService for MemoryCacheService {
         let cache = self.cache.read().await;
         let scrape_exists = cache
             .get(&scrape_key)
-            .map_or(false, |entry| !self.is_expired(entry));
+            .is_some_and(|entry| !self.is_expired(entry));
         let crawl_exists = cache
             .get(&crawl_key)
-            .map_or(false, |entry| !self.is_expired(entry));
+            .is_some_and(|entry| !self.is_expired(entry));
 
         Ok(scrape_exists || crawl_exists)
     }
@@ -473,6 +473,7 @@ impl CacheServiceFactory {
 mod tests {
     use super::*;
     use crate::commands::CommandResult;
+    use std::path::PathBuf;
 
     fn create_test_scrape_result() -> CommandResult {
         CommandResult::Scrape {
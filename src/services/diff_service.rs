@@ -0,0 +1,534 @@
+use serde::Serialize;
+use similar::{ChangeTag, DiffTag, TextDiff};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{FirecrawlError, FirecrawlResult};
+use crate::storage::{ContentRepository, Manifest};
+
+/// Lines of context shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Section name used for content that precedes the first heading
+const PREAMBLE_HEADING: &str = "(preamble)";
+
+/// How a page or section differs between two runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStatus {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+impl fmt::Display for DiffStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiffStatus::Added => write!(f, "added"),
+            DiffStatus::Removed => write!(f, "removed"),
+            DiffStatus::Changed => write!(f, "changed"),
+            DiffStatus::Unchanged => write!(f, "unchanged"),
+        }
+    }
+}
+
+/// Report rendered by `fc_cli diff`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum DiffFormat {
+    /// Unified diff of the page content
+    #[default]
+    Unified,
+    /// JSON summary of changed sections by heading
+    Json,
+    /// Side-by-side HTML report
+    Html,
+}
+
+/// Change to a single section of a page, keyed by its heading
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SectionChange {
+    pub heading: String,
+    pub status: DiffStatus,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+}
+
+/// Comparison of a single page between two output directories
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageDiff {
+    pub url: String,
+    pub status: DiffStatus,
+    pub old_file: Option<PathBuf>,
+    pub new_file: Option<PathBuf>,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub sections: Vec<SectionChange>,
+    #[serde(skip)]
+    old_content: String,
+    #[serde(skip)]
+    new_content: String,
+}
+
+impl PageDiff {
+    /// Compare the saved content of a page; `None` means the page is missing on that side
+    pub fn compare(
+        url: &str,
+        old: Option<(PathBuf, String)>,
+        new: Option<(PathBuf, String)>,
+    ) -> Self {
+        let status = match (&old, &new) {
+            (None, _) => DiffStatus::Added,
+            (_, None) => DiffStatus::Removed,
+            (Some((_, old)), Some((_, new))) if old == new => DiffStatus::Unchanged,
+            _ => DiffStatus::Changed,
+        };
+        let (old_file, old_content) = old.unzip();
+        let (new_file, new_content) = new.unzip();
+        let old_content = old_content.unwrap_or_default();
+        let new_content = new_content.unwrap_or_default();
+
+        let (lines_added, lines_removed) = count_changes(&old_content, &new_content);
+        let sections = if status == DiffStatus::Unchanged {
+            Vec::new()
+        } else {
+            diff_sections(&old_content, &new_content)
+        };
+
+        Self {
+            url: url.to_string(),
+            status,
+            old_file,
+            new_file,
+            lines_added,
+            lines_removed,
+            sections,
+            old_content,
+            new_content,
+        }
+    }
+
+    /// Unified diff of the page content, empty when it did not change
+    pub fn unified_diff(&self) -> String {
+        if self.status == DiffStatus::Unchanged {
+            return String::new();
+        }
+
+        let old_label = label(self.old_file.as_deref());
+        let new_label = label(self.new_file.as_deref());
+        TextDiff::from_lines(&self.old_content, &self.new_content)
+            .unified_diff()
+            .context_radius(CONTEXT_LINES)
+            .header(&format!("a/{}", old_label), &format!("b/{}", new_label))
+            .to_string()
+    }
+
+    /// Side-by-side HTML table of the changed lines
+    fn render_html_table(&self) -> String {
+        let diff = TextDiff::from_lines(&self.old_content, &self.new_content);
+        let old_lines: Vec<&str> = self.old_content.lines().collect();
+        let new_lines: Vec<&str> = self.new_content.lines().collect();
+        let mut table = String::from("<table class=\"diff\">\n");
+
+        for (index, group) in diff.grouped_ops(CONTEXT_LINES).iter().enumerate() {
+            if index > 0 {
+                table.push_str("<tr class=\"skip\"><td colspan=\"4\">&hellip;</td></tr>\n");
+            }
+            for op in group {
+                let (tag, old_range, new_range) = op.as_tag_tuple();
+                let rows = old_range.len().max(new_range.len());
+                for row in 0..rows {
+                    let old = old_range
+                        .clone()
+                        .nth(row)
+                        .map(|i| (i, old_lines.get(i).copied().unwrap_or_default()));
+                    let new = new_range
+                        .clone()
+                        .nth(row)
+                        .map(|i| (i, new_lines.get(i).copied().unwrap_or_default()));
+                    let class = match tag {
+                        DiffTag::Equal => "equal",
+                        DiffTag::Delete => "delete",
+                        DiffTag::Insert => "insert",
+                        DiffTag::Replace => "replace",
+                    };
+                    table.push_str(&format!(
+                        "<tr class=\"{}\">{}{}</tr>\n",
+                        class,
+                        html_cells(old),
+                        html_cells(new)
+                    ));
+                }
+            }
+        }
+
+        table.push_str("</table>\n");
+        table
+    }
+}
+
+/// Comparison of every page between two output directories
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffReport {
+    pub old_dir: PathBuf,
+    pub new_dir: PathBuf,
+    pub pages: Vec<PageDiff>,
+}
+
+impl DiffReport {
+    /// Whether any page was added, removed or changed
+    pub fn has_changes(&self) -> bool {
+        self.pages
+            .iter()
+            .any(|page| page.status != DiffStatus::Unchanged)
+    }
+
+    /// Number of pages with the given status
+    pub fn count(&self, status: DiffStatus) -> usize {
+        self.pages.iter().filter(|page| page.status == status).count()
+    }
+
+    /// Render the report in the requested format
+    pub fn render(&self, format: DiffFormat) -> FirecrawlResult<String> {
+        match format {
+            DiffFormat::Unified => Ok(self.render_unified()),
+            DiffFormat::Json => self.render_summary(),
+            DiffFormat::Html => Ok(self.render_html()),
+        }
+    }
+
+    /// Concatenated unified diffs of every changed page
+    pub fn render_unified(&self) -> String {
+        self.pages.iter().map(PageDiff::unified_diff).collect()
+    }
+
+    /// JSON summary listing each page and its changed sections by heading
+    pub fn render_summary(&self) -> FirecrawlResult<String> {
+        let summary = serde_json::json!({
+            "oldDir": self.old_dir,
+            "newDir": self.new_dir,
+            "totals": {
+                "added": self.count(DiffStatus::Added),
+                "removed": self.count(DiffStatus::Removed),
+                "changed": self.count(DiffStatus::Changed),
+                "unchanged": self.count(DiffStatus::Unchanged),
+            },
+            "pages": self.pages,
+        });
+
+        serde_json::to_string_pretty(&summary)
+            .map_err(|e| FirecrawlError::ExecutionError(format!("Failed to encode summary: {}", e)))
+    }
+
+    /// Self-contained HTML page with a side-by-side view of every changed page
+    pub fn render_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Diff {} &rarr; {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+            escape_html(&self.old_dir.to_string_lossy()),
+            escape_html(&self.new_dir.to_string_lossy()),
+            HTML_STYLE
+        );
+        html.push_str(&format!(
+            "<h1>{} &rarr; {}</h1>\n<p>{} added, {} removed, {} changed, {} unchanged</p>\n",
+            escape_html(&self.old_dir.to_string_lossy()),
+            escape_html(&self.new_dir.to_string_lossy()),
+            self.count(DiffStatus::Added),
+            self.count(DiffStatus::Removed),
+            self.count(DiffStatus::Changed),
+            self.count(DiffStatus::Unchanged)
+        ));
+
+        for page in self
+            .pages
+            .iter()
+            .filter(|page| page.status != DiffStatus::Unchanged)
+        {
+            html.push_str(&format!(
+                "<h2><span class=\"status {}\">{}</span> {}</h2>\n",
+                page.status,
+                page.status,
+                escape_html(&page.url)
+            ));
+            if !page.sections.is_empty() {
+                html.push_str("<ul>\n");
+                for section in &page.sections {
+                    html.push_str(&format!(
+                        "<li>{} <em>{}</em> (+{} -{})</li>\n",
+                        escape_html(&section.heading),
+                        section.status,
+                        section.lines_added,
+                        section.lines_removed
+                    ));
+                }
+                html.push_str("</ul>\n");
+            }
+            html.push_str(&page.render_html_table());
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Compare the pages saved in two output directories, matching them by URL
+/// through each directory's manifest. Restricts the report to `url` if given.
+pub async fn diff_output_dirs(
+    repository: &dyn ContentRepository,
+    old_dir: &Path,
+    new_dir: &Path,
+    url: Option<&str>,
+) -> FirecrawlResult<DiffReport> {
+    let old_manifest = load_manifest(old_dir).await?;
+    let new_manifest = load_manifest(new_dir).await?;
+
+    let urls: BTreeSet<&str> = match url {
+        Some(url) => {
+            if old_manifest.entry_for_url(url).is_none() && new_manifest.entry_for_url(url).is_none()
+            {
+                return Err(FirecrawlError::ValidationError(format!(
+                    "{} is not in either manifest",
+                    url
+                )));
+            }
+            BTreeSet::from([url])
+        }
+        None => old_manifest
+            .entries
+            .iter()
+            .chain(&new_manifest.entries)
+            .map(|entry| entry.url.as_str())
+            .collect(),
+    };
+
+    let mut pages = Vec::new();
+    for url in urls {
+        let old = read_page(repository, &old_manifest, old_dir, url).await?;
+        let new = read_page(repository, &new_manifest, new_dir, url).await?;
+        pages.push(PageDiff::compare(url, old, new));
+    }
+
+    Ok(DiffReport {
+        old_dir: old_dir.to_path_buf(),
+        new_dir: new_dir.to_path_buf(),
+        pages,
+    })
+}
+
+async fn load_manifest(dir: &Path) -> FirecrawlResult<Manifest> {
+    Manifest::load(dir).await?.ok_or_else(|| {
+        FirecrawlError::ValidationError(format!(
+            "No manifest found in {}; it must be the output of a crawl",
+            dir.display()
+        ))
+    })
+}
+
+async fn read_page(
+    repository: &dyn ContentRepository,
+    manifest: &Manifest,
+    dir: &Path,
+    url: &str,
+) -> FirecrawlResult<Option<(PathBuf, String)>> {
+    let Some(entry) = manifest.entry_for_url(url) else {
+        return Ok(None);
+    };
+
+    let content = repository.read_crawl_page(manifest, dir, url).await?;
+    Ok(content.map(|content| (dir.join(&entry.file), content)))
+}
+
+/// Count added and removed lines between two texts
+fn count_changes(old: &str, new: &str) -> (usize, usize) {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .fold((0, 0), |(added, removed), change| match change.tag() {
+            ChangeTag::Insert => (added + 1, removed),
+            ChangeTag::Delete => (added, removed + 1),
+            ChangeTag::Equal => (added, removed),
+        })
+}
+
+/// Compare two Markdown documents section by section
+fn diff_sections(old: &str, new: &str) -> Vec<SectionChange> {
+    let old_sections = split_sections(old);
+    let new_sections = split_sections(new);
+    let old_by_heading: HashMap<&str, &str> = old_sections
+        .iter()
+        .map(|(heading, body)| (heading.as_str(), body.as_str()))
+        .collect();
+    let new_by_heading: HashMap<&str, &str> = new_sections
+        .iter()
+        .map(|(heading, body)| (heading.as_str(), body.as_str()))
+        .collect();
+
+    let mut changes = Vec::new();
+    for (heading, body) in &new_sections {
+        let old_body = old_by_heading.get(heading.as_str()).copied();
+        let status = match old_body {
+            None => DiffStatus::Added,
+            Some(old_body) if old_body == body => continue,
+            Some(_) => DiffStatus::Changed,
+        };
+        let (lines_added, lines_removed) = count_changes(old_body.unwrap_or_default(), body);
+        changes.push(SectionChange {
+            heading: heading.clone(),
+            status,
+            lines_added,
+            lines_removed,
+        });
+    }
+
+    for (heading, body) in &old_sections {
+        if !new_by_heading.contains_key(heading.as_str()) {
+            changes.push(SectionChange {
+                heading: heading.clone(),
+                status: DiffStatus::Removed,
+                lines_added: 0,
+                lines_removed: body.lines().count(),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Split Markdown into `(heading, content)` sections. Repeated headings are
+/// numbered so every key is unique; headings inside code fences are ignored.
+fn split_sections(content: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut heading = PREAMBLE_HEADING.to_string();
+    let mut body = String::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        if !in_fence && let Some(title) = heading_title(line) {
+            if heading != PREAMBLE_HEADING || !body.trim().is_empty() {
+                sections.push((heading, std::mem::take(&mut body)));
+            }
+            body.clear();
+
+            let count = seen.entry(title.to_string()).or_insert(0);
+            *count += 1;
+            heading = if *count == 1 {
+                title.to_string()
+            } else {
+                format!("{} ({})", title, count)
+            };
+        }
+
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    if heading != PREAMBLE_HEADING || !body.trim().is_empty() {
+        sections.push((heading, body));
+    }
+    sections
+}
+
+/// Title of an ATX heading line (`# Title`), if the line is one
+fn heading_title(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+
+    let rest = &line[hashes..];
+    if rest.is_empty() || rest.starts_with(' ') || rest.starts_with('\t') {
+        let title = rest.trim().trim_end_matches('#').trim();
+        Some(if title.is_empty() { "(untitled)" } else { title })
+    } else {
+        None
+    }
+}
+
+fn label(path: Option<&Path>) -> String {
+    path.map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "/dev/null".to_string())
+}
+
+fn html_cells(line: Option<(usize, &str)>) -> String {
+    match line {
+        Some((index, text)) => format!(
+            "<td class=\"lineno\">{}</td><td><pre>{}</pre></td>",
+            index + 1,
+            escape_html(text)
+        ),
+        None => "<td class=\"lineno\"></td><td></td>".to_string(),
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table.diff { border-collapse: collapse; width: 100%; table-layout: fixed; margin-bottom: 2em; }
+table.diff td { vertical-align: top; border: 1px solid #ddd; padding: 0 4px; }
+table.diff td.lineno { width: 3em; color: #888; text-align: right; }
+table.diff pre { margin: 0; white-space: pre-wrap; word-break: break-word; }
+tr.delete td:nth-child(2), tr.replace td:nth-child(2) { background: #fdd; }
+tr.insert td:nth-child(4), tr.replace td:nth-child(4) { background: #dfd; }
+tr.skip td { text-align: center; color: #888; }
+.status { font-size: 0.7em; padding: 2px 6px; border-radius: 3px; background: #eee; }
+.status.added { background: #dfd; } .status.removed { background: #fdd; } .status.changed { background: #ffd; }
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sections_ignores_code_fences() {
+        let sections = split_sections("intro\n# A\none\n```\n# not a heading\n```\n## B\ntwo\n# A\nthree\n");
+        let headings: Vec<&str> = sections.iter().map(|(h, _)| h.as_str()).collect();
+        assert_eq!(headings, vec![PREAMBLE_HEADING, "A", "B", "A (2)"]);
+    }
+
+    #[test]
+    fn test_page_diff_reports_changed_sections() {
+        let old = "# Intro\nhello\n# Pricing\n$10\n# Legacy\nold\n";
+        let new = "# Intro\nhello\n# Pricing\n$12\n# Contact\nmail\n";
+        let diff = PageDiff::compare(
+            "https://example.com",
+            Some((PathBuf::from("old/a.md"), old.to_string())),
+            Some((PathBuf::from("new/a.md"), new.to_string())),
+        );
+
+        assert_eq!(diff.status, DiffStatus::Changed);
+        let sections: Vec<(&str, DiffStatus)> = diff
+            .sections
+            .iter()
+            .map(|s| (s.heading.as_str(), s.status))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Pricing", DiffStatus::Changed),
+                ("Contact", DiffStatus::Added),
+                ("Legacy", DiffStatus::Removed),
+            ]
+        );
+
+        let unified = diff.unified_diff();
+        assert!(unified.contains("--- a/old/a.md"));
+        assert!(unified.contains("-$10"));
+        assert!(unified.contains("+$12"));
+        assert!(diff.render_html_table().contains("$12"));
+    }
+}
@@ -0,0 +1,391 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::errors::FirecrawlError;
+use crate::services::{ProgressObserver, TaskId, item_ratio};
+
+/// Version of the event schema, bumped on incompatible changes. The schema
+/// itself is documented in `docs/events.schema.json`.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// One line of the event stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskEvent {
+    /// Version of the schema the event follows
+    pub schema_version: u32,
+
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+
+    /// Run of the task the event belongs to, unique within one CLI process
+    pub task_id: TaskId,
+
+    /// `scrape` or `crawl`
+    pub task_type: String,
+
+    /// URL the task was started for
+    pub url: String,
+
+    /// Milliseconds since the task started; 0 for `task_started`
+    pub elapsed_ms: u64,
+
+    #[serde(flatten)]
+    pub kind: TaskEventKind,
+}
+
+impl TaskEvent {
+    /// Create an event of the current schema version, timestamped now
+    pub fn new(
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        elapsed_ms: u64,
+        kind: TaskEventKind,
+    ) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            timestamp: Utc::now(),
+            task_id,
+            task_type: task_type.to_string(),
+            url: url.to_string(),
+            elapsed_ms,
+            kind,
+        }
+    }
+
+    /// Name of the event, as in the `event` field
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            TaskEventKind::TaskStarted => "task_started",
+            TaskEventKind::Progress { .. } => "progress",
+            TaskEventKind::PageSaved { .. } => "page_saved",
+            TaskEventKind::TaskCompleted => "task_completed",
+            TaskEventKind::TaskFailed { .. } => "task_failed",
+        }
+    }
+
+    /// Event for a failed task
+    pub fn failed(
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        elapsed_ms: u64,
+        error: &FirecrawlError,
+    ) -> Self {
+        let kind = TaskEventKind::TaskFailed {
+            error_code: error.error_code().to_string(),
+            message: error.to_string(),
+            retryable: error.is_retryable(),
+        };
+        Self::new(task_id, url, task_type, elapsed_ms, kind)
+    }
+}
+
+/// What happened, tagged by the `event` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TaskEventKind {
+    TaskStarted,
+    Progress {
+        /// Share of the task done, from 0.0 to 1.0
+        progress: f32,
+        /// Finished items, e.g. crawled pages, when the task counts them
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        completed: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        total: Option<u32>,
+    },
+    PageSaved {
+        /// URL of the saved page
+        page_url: String,
+        /// File the page was written to
+        path: PathBuf,
+    },
+    TaskCompleted,
+    TaskFailed {
+        /// [`FirecrawlError::error_code`] of the failure
+        error_code: String,
+        message: String,
+        retryable: bool,
+    },
+}
+
+/// Start times of running tasks, for the `elapsed_ms` of their events
+#[derive(Default)]
+pub(crate) struct TaskClock {
+    started: Mutex<HashMap<TaskId, Instant>>,
+}
+
+impl TaskClock {
+    pub(crate) fn start(&self, task_id: TaskId) {
+        self.started
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(task_id, Instant::now());
+    }
+
+    /// Milliseconds since the task started, forgetting the start if it finished
+    pub(crate) fn elapsed_ms(&self, task_id: TaskId, finished: bool) -> u64 {
+        let mut started = self.started.lock().unwrap_or_else(|e| e.into_inner());
+        let start = if finished {
+            started.remove(&task_id)
+        } else {
+            started.get(&task_id).copied()
+        };
+        start.map_or(0, |start| start.elapsed().as_millis() as u64)
+    }
+}
+
+/// Progress observer writing one JSON [`TaskEvent`] per line, for scripts
+/// that drive the CLI
+pub struct EventStreamObserver {
+    id: String,
+    writer: Mutex<Box<dyn Write + Send>>,
+    clock: TaskClock,
+}
+
+impl EventStreamObserver {
+    /// Create an observer writing events to `writer`
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            id: format!(
+                "events-{}",
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+            ),
+            writer: Mutex::new(writer),
+            clock: TaskClock::default(),
+        }
+    }
+
+    /// Create an observer writing events to stderr
+    pub fn stderr() -> Self {
+        Self::new(Box::new(std::io::stderr()))
+    }
+
+    /// Create an observer appending events to the file at `path`
+    pub fn append_to_file(path: &Path) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self::new(Box::new(file)))
+    }
+
+    fn emit(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        elapsed_ms: u64,
+        kind: TaskEventKind,
+    ) {
+        self.write(&TaskEvent::new(task_id, url, task_type, elapsed_ms, kind));
+    }
+
+    fn write(&self, event: &TaskEvent) {
+        let Ok(mut line) = serde_json::to_string(event) else {
+            return;
+        };
+        line.push('\n');
+        // One write per line, so concurrent tasks never interleave within one.
+        // The stream is best effort; a closed pipe must not fail the task.
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writer.write_all(line.as_bytes());
+        let _ = writer.flush();
+    }
+}
+
+#[async_trait]
+impl ProgressObserver for EventStreamObserver {
+    async fn on_task_started(&self, task_id: TaskId, url: &str, task_type: &str) {
+        self.clock.start(task_id);
+        self.emit(task_id, url, task_type, 0, TaskEventKind::TaskStarted);
+    }
+
+    async fn on_task_progress(&self, task_id: TaskId, url: &str, task_type: &str, progress: f32) {
+        let elapsed_ms = self.clock.elapsed_ms(task_id, false);
+        let kind = TaskEventKind::Progress {
+            progress,
+            completed: None,
+            total: None,
+        };
+        self.emit(task_id, url, task_type, elapsed_ms, kind);
+    }
+
+    async fn on_task_items(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        completed: u32,
+        total: u32,
+    ) {
+        let elapsed_ms = self.clock.elapsed_ms(task_id, false);
+        let kind = TaskEventKind::Progress {
+            progress: item_ratio(completed, total),
+            completed: Some(completed),
+            total: Some(total),
+        };
+        self.emit(task_id, url, task_type, elapsed_ms, kind);
+    }
+
+    async fn on_task_completed(&self, task_id: TaskId, url: &str, task_type: &str) {
+        let elapsed_ms = self.clock.elapsed_ms(task_id, true);
+        self.emit(task_id, url, task_type, elapsed_ms, TaskEventKind::TaskCompleted);
+    }
+
+    async fn on_page_saved(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        page_url: &str,
+        path: &Path,
+    ) {
+        // Pages are saved before the task completes or fails
+        let elapsed_ms = self.clock.elapsed_ms(task_id, false);
+        let kind = TaskEventKind::PageSaved {
+            page_url: page_url.to_string(),
+            path: path.to_path_buf(),
+        };
+        self.emit(task_id, url, task_type, elapsed_ms, kind);
+    }
+
+    async fn on_task_failed(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    ) {
+        let elapsed_ms = self.clock.elapsed_ms(task_id, true);
+        self.write(&TaskEvent::failed(task_id, url, task_type, elapsed_ms, error));
+    }
+
+    fn observer_id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::NetworkError;
+    use std::sync::Arc;
+
+    /// Writer whose output the test can read back
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_is_written_as_one_event_per_line() {
+        let output = Output::default();
+        let observer = EventStreamObserver::new(Box::new(output.clone()));
+        let (a, b) = (TaskId::next(), TaskId::next());
+
+        observer.on_task_started(a, "https://a.com", "crawl").await;
+        observer.on_task_items(a, "https://a.com", "crawl", 1, 4).await;
+        observer
+            .on_page_saved(a, "https://a.com", "crawl", "https://a.com/x", Path::new("out/x.md"))
+            .await;
+        observer.on_task_completed(a, "https://a.com", "crawl").await;
+        observer.on_task_started(b, "https://b.com", "scrape").await;
+        let error = FirecrawlError::NetworkError(NetworkError::ConnectionFailed(
+            "refused".to_string(),
+        ));
+        observer.on_task_failed(b, "https://b.com", "scrape", &error).await;
+
+        let text = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<TaskEvent> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 6);
+        assert!(events.iter().all(|e| e.schema_version == EVENT_SCHEMA_VERSION));
+        assert_eq!(
+            events[1].kind,
+            TaskEventKind::Progress {
+                progress: 0.25,
+                completed: Some(1),
+                total: Some(4),
+            }
+        );
+        assert_eq!(events[3].kind, TaskEventKind::TaskCompleted);
+        assert!(events[..4].iter().all(|e| e.task_id == a));
+        assert_eq!(events[5].task_id, b);
+        assert_eq!(
+            events[2].kind,
+            TaskEventKind::PageSaved {
+                page_url: "https://a.com/x".to_string(),
+                path: PathBuf::from("out/x.md"),
+            }
+        );
+
+        let failed: serde_json::Value = serde_json::from_str(text.lines().last().unwrap()).unwrap();
+        assert_eq!(failed["event"], "task_failed");
+        assert_eq!(failed["task_type"], "scrape");
+        assert_eq!(failed["error_code"], "NETWORK_ERROR");
+        assert_eq!(failed["retryable"], true);
+    }
+
+    #[test]
+    fn test_schema_document_matches_events() {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../../docs/events.schema.json")).unwrap();
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            EVENT_SCHEMA_VERSION
+        );
+        let event = serde_json::to_value(TaskEvent::new(
+            TaskId::next(),
+            "https://a.com",
+            "scrape",
+            0,
+            TaskEventKind::TaskStarted,
+        ))
+        .unwrap();
+        for field in schema["required"].as_array().unwrap() {
+            assert!(event.get(field.as_str().unwrap()).is_some(), "{field} missing");
+        }
+
+        let events = &schema["properties"]["event"]["enum"];
+        for kind in [
+            TaskEventKind::TaskStarted,
+            TaskEventKind::Progress {
+                progress: 0.0,
+                completed: None,
+                total: None,
+            },
+            TaskEventKind::PageSaved {
+                page_url: String::new(),
+                path: PathBuf::new(),
+            },
+            TaskEventKind::TaskCompleted,
+            TaskEventKind::TaskFailed {
+                error_code: String::new(),
+                message: String::new(),
+                retryable: false,
+            },
+        ] {
+            let name = serde_json::to_value(&kind).unwrap()["event"].clone();
+            assert!(events.as_array().unwrap().contains(&name), "{name} missing");
+        }
+    }
+}
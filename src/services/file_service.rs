@@ -1,10 +1,9 @@
-use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 
 use crate::api::models::{crawl_model::CrawlResponse, scrape_model::ScrapeResponse};
 use crate::cli::OutputFormat;
 use crate::errors::{FirecrawlError, FirecrawlResult};
-use crate::storage::ContentRepository;
+use crate::storage::{ContentRepository, CrawlSaveReport};
 
 /// Service for file operations that wraps the repository pattern
 pub struct FileService {
@@ -35,7 +34,7 @@ impl FileService {
         result: &ScrapeResponse,
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> FirecrawlResult<PathBuf> {
         // Ensure output directory exists
         self.repository
@@ -56,8 +55,9 @@ impl FileService {
         results: &[CrawlResponse],
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
-    ) -> FirecrawlResult<Vec<PathBuf>> {
+        output_dir: &Path,
+        fail_fast: bool,
+    ) -> FirecrawlResult<CrawlSaveReport> {
         // Ensure output directory exists
         self.repository
             .ensure_directory(output_dir)
@@ -66,7 +66,7 @@ impl FileService {
 
         // Save the results
         self.repository
-            .save_crawl_results(results, url, format, output_dir)
+            .save_crawl_results(results, url, format, output_dir, fail_fast)
             .await
             .map_err(FirecrawlError::StorageError)
     }
@@ -77,12 +77,12 @@ impl FileService {
     }
 
     /// Check if a file exists
-    pub async fn file_exists(&self, path: &PathBuf) -> bool {
+    pub async fn file_exists(&self, path: &Path) -> bool {
         self.repository.file_exists(path).await
     }
 
     /// Ensure a directory exists
-    pub async fn ensure_directory(&self, path: &PathBuf) -> FirecrawlResult<()> {
+    pub async fn ensure_directory(&self, path: &Path) -> FirecrawlResult<()> {
         self.repository
             .ensure_directory(path)
             .await
@@ -109,7 +109,7 @@ impl FileService {
     /// Create subdirectory with URL-based naming
     pub async fn create_url_subdirectory(
         &self,
-        base_dir: &PathBuf,
+        base_dir: &Path,
         url: &str,
     ) -> FirecrawlResult<PathBuf> {
         use slug::slugify;
@@ -124,7 +124,7 @@ impl FileService {
         format: OutputFormat,
         output_dir: &Path,
     ) -> FirecrawlResult<String> {
-        let mut filename = self.generate_filename(url, format.clone());
+        let mut filename = self.generate_filename(url, format);
         let mut counter = 1;
 
         // Check if file exists and generate unique name if needed
@@ -156,12 +156,12 @@ impl FileService {
         result: &ScrapeResponse,
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> FirecrawlResult<PathBuf> {
         let filename = self
-            .generate_unique_filename(url, format.clone(), output_dir)
+            .generate_unique_filename(url, format, output_dir)
             .await?;
-        let file_path = output_dir.join(filename);
+        let _file_path = output_dir.join(filename);
 
         // Use the repository to save with custom filename logic
         self.save_scrape_result(result, url, format, output_dir)
@@ -227,18 +227,15 @@ impl FileService {
 
         while let Ok(Some(entry)) = entries.next_entry().await {
             let path = entry.path();
-            if path.is_file() {
-                if let Ok(metadata) = entry.metadata().await {
-                    if let Ok(modified) = metadata.modified() {
+            if path.is_file()
+                && let Ok(metadata) = entry.metadata().await
+                    && let Ok(modified) = metadata.modified() {
                         let modified_time = chrono::DateTime::<chrono::Utc>::from(modified);
-                        if modified_time < cutoff_time {
-                            if tokio::fs::remove_file(&path).await.is_ok() {
+                        if modified_time < cutoff_time
+                            && tokio::fs::remove_file(&path).await.is_ok() {
                                 removed_files.push(path);
                             }
-                        }
                     }
-                }
-            }
         }
 
         Ok(removed_files)
@@ -2,13 +2,23 @@ pub mod task_service;
 pub mod api_service;
 pub mod file_service;
 pub mod progress_service;
+pub mod terminal_progress;
+pub mod event_stream;
+pub mod webhook;
 pub mod cache_service;
+pub mod watch_service;
+pub mod diff_service;
 
 pub use task_service::*;
 pub use api_service::*;
 pub use file_service::*;
 pub use progress_service::*;
+pub use terminal_progress::*;
+pub use event_stream::*;
+pub use webhook::*;
 pub use cache_service::*;
+pub use watch_service::*;
+pub use diff_service::*;
 
 /// Crawl progress information for monitoring crawl jobs
 #[derive(Debug, Clone)]
@@ -17,4 +27,6 @@ pub struct CrawlProgress {
     pub total: u32,
     pub current_url: Option<String>,
     pub status: String,
+    /// URLs of pages that finished since the previous update
+    pub new_urls: Vec<String>,
 }
\ No newline at end of file
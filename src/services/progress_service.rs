@@ -1,25 +1,82 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
 use tokio::sync::RwLock;
 
-use crate::commands::CommandResult;
-use crate::errors::{FirecrawlError, FirecrawlResult};
+use crate::errors::FirecrawlError;
+use crate::services::{TerminalProgressObserver, Verbosity};
+
+/// Identifies one run of a task across its progress notifications, so runs
+/// for the same URL are told apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    /// An id no other task run of this process has
+    pub fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
 
 /// Trait for progress monitoring and notifications
 #[async_trait]
 pub trait ProgressService {
     /// Notify that a task has started
-    async fn notify_task_started(&self, url: &str, task_type: &str);
+    async fn notify_task_started(&self, task_id: TaskId, url: &str, task_type: &str);
 
     /// Notify task progress (0.0 to 1.0)
-    async fn notify_task_progress(&self, url: &str, task_type: &str, progress: f32);
+    async fn notify_task_progress(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        progress: f32,
+    );
+
+    /// Notify task progress as a count of finished items, e.g. crawled pages
+    async fn notify_task_items(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        completed: u32,
+        total: u32,
+    ) {
+        self.notify_task_progress(task_id, url, task_type, item_ratio(completed, total))
+            .await;
+    }
 
     /// Notify that a task has completed
-    async fn notify_task_completed(&self, url: &str, task_type: &str);
+    async fn notify_task_completed(&self, task_id: TaskId, url: &str, task_type: &str);
+
+    /// Notify that a page of a task's results was written to `path`
+    async fn notify_page_saved(
+        &self,
+        _task_id: TaskId,
+        _url: &str,
+        _task_type: &str,
+        _page_url: &str,
+        _path: &Path,
+    ) {
+    }
 
     /// Notify that a task has failed
-    async fn notify_task_failed(&self, url: &str, task_type: &str, error: &FirecrawlError);
+    async fn notify_task_failed(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    );
+
+    /// Notify whether a cached result was found before running a task
+    async fn notify_cache_lookup(&self, _url: &str, _task_type: &str, _hit: bool) {}
 
     /// Get current statistics
     async fn get_statistics(&self) -> crate::services::task_service::TaskStatistics;
@@ -35,25 +92,70 @@ pub trait ProgressService {
 #[async_trait]
 pub trait ProgressObserver {
     /// Called when a task starts
-    async fn on_task_started(&self, url: &str, task_type: &str);
+    async fn on_task_started(&self, task_id: TaskId, url: &str, task_type: &str);
 
     /// Called when task progress updates
-    async fn on_task_progress(&self, url: &str, task_type: &str, progress: f32);
+    async fn on_task_progress(&self, task_id: TaskId, url: &str, task_type: &str, progress: f32);
+
+    /// Called when a task reports how many of its items are finished
+    async fn on_task_items(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        completed: u32,
+        total: u32,
+    ) {
+        self.on_task_progress(task_id, url, task_type, item_ratio(completed, total))
+            .await;
+    }
 
     /// Called when a task completes
-    async fn on_task_completed(&self, url: &str, task_type: &str);
+    async fn on_task_completed(&self, task_id: TaskId, url: &str, task_type: &str);
+
+    /// Called when a page of a task's results is written to `path`
+    async fn on_page_saved(
+        &self,
+        _task_id: TaskId,
+        _url: &str,
+        _task_type: &str,
+        _page_url: &str,
+        _path: &Path,
+    ) {
+    }
 
     /// Called when a task fails
-    async fn on_task_failed(&self, url: &str, task_type: &str, error: &FirecrawlError);
+    async fn on_task_failed(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    );
 
     /// Get a unique identifier for this observer
     fn observer_id(&self) -> &str;
 }
 
-/// Default implementation of ProgressService
+/// Share of finished items, 0.0 while the total is unknown
+pub(crate) fn item_ratio(completed: u32, total: u32) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        (completed as f32 / total as f32).min(1.0)
+    }
+}
+
+/// Default implementation of ProgressService.
+///
+/// Observers are notified one at a time, in the order events arrive, so each
+/// observer sees the events of a task in sequence and every event has been
+/// handled when the `notify_*` call returns.
 pub struct DefaultProgressService {
     statistics: Arc<RwLock<crate::services::task_service::TaskStatistics>>,
     observers: Arc<RwLock<HashMap<String, Arc<dyn ProgressObserver + Send + Sync>>>>,
+    /// Start times of running tasks
+    started: Arc<RwLock<HashMap<TaskId, Instant>>>,
 }
 
 impl DefaultProgressService {
@@ -64,6 +166,7 @@ impl DefaultProgressService {
                 crate::services::task_service::TaskStatistics::default(),
             )),
             observers: Arc::new(RwLock::new(HashMap::new())),
+            started: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -71,6 +174,18 @@ impl DefaultProgressService {
     pub fn new_arc() -> Arc<Self> {
         Arc::new(Self::new())
     }
+
+    /// Snapshot of the registered observers, so none is notified while the
+    /// registry is locked
+    async fn observers(&self) -> Vec<Arc<dyn ProgressObserver + Send + Sync>> {
+        self.observers.read().await.values().cloned().collect()
+    }
+
+    /// Time since the task was started, if it was
+    async fn take_elapsed(&self, task_id: TaskId) -> Option<std::time::Duration> {
+        let mut started = self.started.write().await;
+        started.remove(&task_id).map(|start| start.elapsed())
+    }
 }
 
 impl Default for DefaultProgressService {
@@ -81,7 +196,7 @@ impl Default for DefaultProgressService {
 
 #[async_trait]
 impl ProgressService for DefaultProgressService {
-    async fn notify_task_started(&self, url: &str, task_type: &str) {
+    async fn notify_task_started(&self, task_id: TaskId, url: &str, task_type: &str) {
         // Update statistics
         {
             let mut stats = self.statistics.write().await;
@@ -92,72 +207,110 @@ impl ProgressService for DefaultProgressService {
                 _ => {}
             }
         }
+        self.started
+            .write()
+            .await
+            .insert(task_id, Instant::now());
 
         // Notify observers
-        let observers = self.observers.read().await;
-        for observer in observers.values() {
-            let url = url.to_string();
-            let task_type = task_type.to_string();
-            let observer_clone = Arc::clone(observer);
-            tokio::spawn(async move {
-                observer_clone.on_task_started(&url, &task_type).await;
-            });
+        for observer in self.observers().await {
+            observer.on_task_started(task_id, url, task_type).await;
         }
     }
 
-    async fn notify_task_progress(&self, url: &str, task_type: &str, progress: f32) {
+    async fn notify_task_progress(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        progress: f32,
+    ) {
         // Notify observers
-        let observers = self.observers.read().await;
-        for observer in observers.values() {
-            let url = url.to_string();
-            let task_type = task_type.to_string();
-            let observer_clone = Arc::clone(observer);
-            tokio::spawn(async move {
-                observer_clone
-                    .on_task_progress(&url, &task_type, progress)
-                    .await;
-            });
+        for observer in self.observers().await {
+            observer.on_task_progress(task_id, url, task_type, progress).await;
         }
     }
 
-    async fn notify_task_completed(&self, url: &str, task_type: &str) {
+    async fn notify_task_items(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        completed: u32,
+        total: u32,
+    ) {
+        // Notify observers
+        for observer in self.observers().await {
+            observer
+                .on_task_items(task_id, url, task_type, completed, total)
+                .await;
+        }
+    }
+
+    async fn notify_task_completed(&self, task_id: TaskId, url: &str, task_type: &str) {
         // Update statistics
+        let elapsed = self.take_elapsed(task_id).await;
         {
             let mut stats = self.statistics.write().await;
             stats.completed_tasks += 1;
+            if let Some(elapsed) = elapsed {
+                stats.record_execution_time(elapsed);
+            }
+        }
+
+        // Notify observers
+        for observer in self.observers().await {
+            observer.on_task_completed(task_id, url, task_type).await;
         }
+    }
 
+    async fn notify_page_saved(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        page_url: &str,
+        path: &Path,
+    ) {
         // Notify observers
-        let observers = self.observers.read().await;
-        for observer in observers.values() {
-            let url = url.to_string();
-            let task_type = task_type.to_string();
-            let observer_clone = Arc::clone(observer);
-            tokio::spawn(async move {
-                observer_clone.on_task_completed(&url, &task_type).await;
-            });
+        for observer in self.observers().await {
+            observer.on_page_saved(task_id, url, task_type, page_url, path).await;
         }
     }
 
-    async fn notify_task_failed(&self, url: &str, task_type: &str, error: &FirecrawlError) {
+    async fn notify_task_failed(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    ) {
         // Update statistics
+        let elapsed = self.take_elapsed(task_id).await;
         {
             let mut stats = self.statistics.write().await;
             stats.failed_tasks += 1;
+            *stats
+                .error_codes
+                .entry(error.error_code().to_string())
+                .or_default() += 1;
+            if let Some(elapsed) = elapsed {
+                stats.record_execution_time(elapsed);
+            }
         }
 
         // Notify observers
-        let observers = self.observers.read().await;
-        for observer in observers.values() {
-            let url = url.to_string();
-            let task_type = task_type.to_string();
-            let error_clone = error.clone();
-            let observer_clone = Arc::clone(observer);
-            tokio::spawn(async move {
-                observer_clone
-                    .on_task_failed(&url, &task_type, &error_clone)
-                    .await;
-            });
+        for observer in self.observers().await {
+            observer.on_task_failed(task_id, url, task_type, error).await;
+        }
+    }
+
+    async fn notify_cache_lookup(&self, _url: &str, _task_type: &str, hit: bool) {
+        let mut stats = self.statistics.write().await;
+        if hit {
+            stats.cache_hits += 1;
+        } else {
+            stats.cache_misses += 1;
         }
     }
 
@@ -200,20 +353,26 @@ impl Default for ConsoleProgressObserver {
 
 #[async_trait]
 impl ProgressObserver for ConsoleProgressObserver {
-    async fn on_task_started(&self, url: &str, task_type: &str) {
+    async fn on_task_started(&self, _task_id: TaskId, url: &str, task_type: &str) {
         println!("🚀 Started {} task for: {}", task_type, url);
     }
 
-    async fn on_task_progress(&self, url: &str, _task_type: &str, progress: f32) {
+    async fn on_task_progress(&self, _task_id: TaskId, url: &str, _task_type: &str, progress: f32) {
         let percentage = (progress * 100.0) as u32;
         println!("⏳ {}: {}% complete", url, percentage);
     }
 
-    async fn on_task_completed(&self, url: &str, task_type: &str) {
+    async fn on_task_completed(&self, _task_id: TaskId, url: &str, task_type: &str) {
         println!("✅ Completed {} task for: {}", task_type, url);
     }
 
-    async fn on_task_failed(&self, url: &str, task_type: &str, error: &FirecrawlError) {
+    async fn on_task_failed(
+        &self,
+        _task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    ) {
         println!("❌ Failed {} task for: {} - {}", task_type, url, error);
     }
 
@@ -246,11 +405,11 @@ impl Default for LoggingProgressObserver {
 
 #[async_trait]
 impl ProgressObserver for LoggingProgressObserver {
-    async fn on_task_started(&self, url: &str, task_type: &str) {
+    async fn on_task_started(&self, _task_id: TaskId, url: &str, task_type: &str) {
         log::info!("Started {} task for URL: {}", task_type, url);
     }
 
-    async fn on_task_progress(&self, url: &str, task_type: &str, progress: f32) {
+    async fn on_task_progress(&self, _task_id: TaskId, url: &str, task_type: &str, progress: f32) {
         log::debug!(
             "Progress for {} {}: {:.1}%",
             task_type,
@@ -259,11 +418,17 @@ impl ProgressObserver for LoggingProgressObserver {
         );
     }
 
-    async fn on_task_completed(&self, url: &str, task_type: &str) {
+    async fn on_task_completed(&self, _task_id: TaskId, url: &str, task_type: &str) {
         log::info!("Completed {} task for URL: {}", task_type, url);
     }
 
-    async fn on_task_failed(&self, url: &str, task_type: &str, error: &FirecrawlError) {
+    async fn on_task_failed(
+        &self,
+        _task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    ) {
         log::error!(
             "Failed {} task for URL: {} - Error: {}",
             task_type,
@@ -310,6 +475,20 @@ impl ProgressServiceFactory {
         service
     }
 
+    /// Create a progress service drawing bars or lines on stderr for the
+    /// plain CLI; a quiet one has no observers
+    pub async fn create_terminal_service(
+        verbosity: Verbosity,
+    ) -> Arc<dyn ProgressService + Send + Sync> {
+        let service = DefaultProgressService::new_arc();
+        if verbosity != Verbosity::Quiet {
+            service
+                .register_observer(Arc::new(TerminalProgressObserver::new(verbosity)))
+                .await;
+        }
+        service
+    }
+
     /// Create a silent progress service (no observers)
     pub fn create_silent_service() -> Arc<dyn ProgressService + Send + Sync> {
         Arc::new(DefaultProgressService::new())
@@ -340,22 +519,34 @@ mod tests {
 
     #[async_trait]
     impl ProgressObserver for TestProgressObserver {
-        async fn on_task_started(&self, url: &str, task_type: &str) {
+        async fn on_task_started(&self, _task_id: TaskId, url: &str, task_type: &str) {
             let mut events = self.events.write().await;
             events.push(format!("started:{}:{}", task_type, url));
         }
 
-        async fn on_task_progress(&self, url: &str, task_type: &str, progress: f32) {
+        async fn on_task_progress(
+            &self,
+            _task_id: TaskId,
+            url: &str,
+            task_type: &str,
+            progress: f32,
+        ) {
             let mut events = self.events.write().await;
             events.push(format!("progress:{}:{}:{}", task_type, url, progress));
         }
 
-        async fn on_task_completed(&self, url: &str, task_type: &str) {
+        async fn on_task_completed(&self, _task_id: TaskId, url: &str, task_type: &str) {
             let mut events = self.events.write().await;
             events.push(format!("completed:{}:{}", task_type, url));
         }
 
-        async fn on_task_failed(&self, url: &str, task_type: &str, error: &FirecrawlError) {
+        async fn on_task_failed(
+            &self,
+            _task_id: TaskId,
+            url: &str,
+            task_type: &str,
+            error: &FirecrawlError,
+        ) {
             let mut events = self.events.write().await;
             events.push(format!("failed:{}:{}:{}", task_type, url, error));
         }
@@ -370,16 +561,17 @@ mod tests {
         let service = DefaultProgressService::new();
         let observer = Arc::new(TestProgressObserver::new());
 
-        service.register_observer(Arc::clone(&observer)).await;
+        service.register_observer(observer.clone()).await;
 
+        let task_id = TaskId::next();
         service
-            .notify_task_started("https://example.com", "scrape")
+            .notify_task_started(task_id, "https://example.com", "scrape")
             .await;
         service
-            .notify_task_progress("https://example.com", "scrape", 0.5)
+            .notify_task_progress(task_id, "https://example.com", "scrape", 0.5)
             .await;
         service
-            .notify_task_completed("https://example.com", "scrape")
+            .notify_task_completed(task_id, "https://example.com", "scrape")
             .await;
 
         // Give time for async tasks to complete
@@ -395,18 +587,20 @@ mod tests {
     #[tokio::test]
     async fn test_statistics() {
         let service = DefaultProgressService::new();
+        let (scrape, crawl) = (TaskId::next(), TaskId::next());
 
         service
-            .notify_task_started("https://example1.com", "scrape")
+            .notify_task_started(scrape, "https://example1.com", "scrape")
             .await;
         service
-            .notify_task_started("https://example2.com", "crawl")
+            .notify_task_started(crawl, "https://example2.com", "crawl")
             .await;
         service
-            .notify_task_completed("https://example1.com", "scrape")
+            .notify_task_completed(scrape, "https://example1.com", "scrape")
             .await;
         service
             .notify_task_failed(
+                crawl,
                 "https://example2.com",
                 "crawl",
                 &FirecrawlError::ValidationError("test error".to_string()),
@@ -421,4 +615,50 @@ mod tests {
         assert_eq!(stats.crawl_tasks, 1);
         assert_eq!(stats.success_rate(), 50.0);
     }
+
+    #[tokio::test]
+    async fn test_execution_times_cache_lookups_and_error_codes() {
+        let service = DefaultProgressService::new();
+
+        service.notify_cache_lookup("https://example1.com", "scrape", true).await;
+        service.notify_cache_lookup("https://example2.com", "scrape", false).await;
+        // Two runs of the same URL are timed separately
+        let tasks = [
+            (TaskId::next(), "https://example2.com"),
+            (TaskId::next(), "https://example3.com"),
+            (TaskId::next(), "https://example3.com"),
+        ];
+        for (task_id, url) in tasks {
+            service.notify_task_started(task_id, url, "scrape").await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        let [(completed, completed_url), failed @ ..] = tasks;
+        service
+            .notify_task_completed(completed, completed_url, "scrape")
+            .await;
+        let timeout = FirecrawlError::TimeoutError("slow".to_string());
+        for (task_id, url) in failed {
+            service
+                .notify_task_failed(task_id, url, "scrape", &timeout)
+                .await;
+        }
+        // A task that never started is counted but not timed
+        service
+            .notify_task_failed(
+                TaskId::next(),
+                "https://example5.com",
+                "crawl",
+                &FirecrawlError::ValidationError("bad".to_string()),
+            )
+            .await;
+
+        let stats = service.get_statistics().await;
+        assert_eq!(stats.cache_hits, 1);
+        assert_eq!(stats.cache_hit_rate(), 50.0);
+        assert_eq!(stats.timed_tasks, 3);
+        assert_eq!(stats.recent_latencies.len(), 3);
+        assert!(stats.average_execution_time >= std::time::Duration::from_millis(20));
+        assert_eq!(stats.error_codes.get("TIMEOUT_ERROR"), Some(&2));
+        assert_eq!(stats.error_codes.get("VALIDATION_ERROR"), Some(&1));
+    }
 }
@@ -1,9 +1,11 @@
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 
 use crate::cli::{OutputFormat, CrawlOptions, ScrapeOptions};
-use crate::commands::{Command, CommandResult, ScrapeCommand, CrawlCommand, TaskQueueFactory};
+use crate::commands::{BatchReport, Command, CommandResult, ScrapeCommand, CrawlCommand, TaskQueueFactory};
 use crate::storage::ContentRepository;
-use crate::services::{ApiService, ProgressService, CacheService};
+use crate::services::{ApiService, ProgressService, CacheService, TaskId};
 use crate::config::AppConfig;
 use crate::errors::{FirecrawlError, FirecrawlResult};
 
@@ -14,6 +16,8 @@ pub struct TaskService {
     cache_service: Option<Arc<dyn CacheService + Send + Sync>>,
     repository: Arc<dyn ContentRepository + Send + Sync>,
     config: AppConfig,
+    cancellation: CancellationToken,
+    fail_fast: bool,
 }
 
 impl TaskService {
@@ -31,9 +35,16 @@ impl TaskService {
             cache_service,
             repository,
             config,
+            cancellation: CancellationToken::new(),
+            fail_fast: false,
         }
     }
 
+    /// Get the API service used by this task service
+    pub fn api_service(&self) -> &Arc<dyn ApiService + Send + Sync> {
+        &self.api_service
+    }
+
     /// Execute a single scrape task
     pub async fn execute_scrape(
         &self,
@@ -45,13 +56,18 @@ impl TaskService {
 
         // Check cache first if enabled
         if let Some(cache_service) = &self.cache_service {
-            if let Some(cached_result) = cache_service.get_scrape_result(&url, &format).await? {
+            let cached = cache_service.get_scrape_result(&url, &format).await?;
+            self.progress_service
+                .notify_cache_lookup(&url, "scrape", cached.is_some())
+                .await;
+            if let Some(cached_result) = cached {
                 return Ok(cached_result);
             }
         }
 
         // Notify progress
-        self.progress_service.notify_task_started(&url, "scrape").await;
+        let task_id = TaskId::next();
+        self.progress_service.notify_task_started(task_id, &url, "scrape").await;
 
         // Execute command
         let result = match command
@@ -59,7 +75,7 @@ impl TaskService {
             .await {
                 Ok(result) => result,
                 Err(e) => {
-                    self.progress_service.notify_task_failed(&url, "scrape", &e).await;
+                    self.progress_service.notify_task_failed(task_id, &url, "scrape", &e).await;
                     return Err(e);
                 }
             };
@@ -70,7 +86,7 @@ impl TaskService {
         }
 
         // Notify completion
-        self.progress_service.notify_task_completed(&url, "scrape").await;
+        self.progress_service.notify_task_completed(task_id, &url, "scrape").await;
 
         Ok(result)
     }
@@ -82,17 +98,23 @@ impl TaskService {
         options: Option<CrawlOptions>,
         format: OutputFormat,
     ) -> FirecrawlResult<CommandResult> {
-        let command = CrawlCommand::new(url.clone(), options, format);
+        let command = CrawlCommand::new(url.clone(), options, format)
+            .with_cancellation(self.cancellation.clone());
 
         // Check cache first if enabled
         if let Some(cache_service) = &self.cache_service {
-            if let Some(cached_result) = cache_service.get_crawl_result(&url, &format).await? {
+            let cached = cache_service.get_crawl_result(&url, &format).await?;
+            self.progress_service
+                .notify_cache_lookup(&url, "crawl", cached.is_some())
+                .await;
+            if let Some(cached_result) = cached {
                 return Ok(cached_result);
             }
         }
 
         // Notify progress
-        self.progress_service.notify_task_started(&url, "crawl").await;
+        let task_id = TaskId::next();
+        self.progress_service.notify_task_started(task_id, &url, "crawl").await;
 
         // Execute command
         let result = match command
@@ -100,7 +122,7 @@ impl TaskService {
             .await {
                 Ok(result) => result,
                 Err(e) => {
-                    self.progress_service.notify_task_failed(&url, "crawl", &e).await;
+                    self.progress_service.notify_task_failed(task_id, &url, "crawl", &e).await;
                     return Err(e);
                 }
             };
@@ -111,20 +133,26 @@ impl TaskService {
         }
 
         // Notify completion
-        self.progress_service.notify_task_completed(&url, "crawl").await;
+        self.progress_service.notify_task_completed(task_id, &url, "crawl").await;
 
         Ok(result)
     }
 
-    /// Execute multiple tasks concurrently
+    /// Execute multiple tasks concurrently. Tasks that fail, and pages their
+    /// crawls could not save, are listed in the report and in `failures.json`,
+    /// unless the service fails fast; [`BatchReport::exit_code`] tells a full
+    /// success from a partial one.
     pub async fn execute_batch(
         &self,
         tasks: Vec<TaskDefinition>,
-    ) -> FirecrawlResult<Vec<CommandResult>> {
+    ) -> FirecrawlResult<BatchReport> {
         // Create task queue based on configuration
-        let queue = TaskQueueFactory::create_normal();
+        let queue = TaskQueueFactory::create_normal()
+            .with_cancellation(self.cancellation.clone())
+            .with_fail_fast(self.fail_fast);
 
         // Add tasks to queue
+        let total = tasks.len();
         for task in tasks {
             match task {
                 TaskDefinition::Scrape { url, options, format } => {
@@ -132,15 +160,32 @@ impl TaskService {
                     queue.enqueue(command).await;
                 }
                 TaskDefinition::Crawl { url, options, format } => {
-                    let command = CrawlCommand::new(url, options, format);
+                    let mut command = CrawlCommand::new(url, options, format)
+                        .with_cancellation(self.cancellation.clone());
+                    command.fail_fast = self.fail_fast;
                     queue.enqueue(command).await;
                 }
             };
         }
 
         // Execute all tasks
-        queue.execute_all(self.repository.as_ref(), &self.config.get_effective_output_dir())
-            .await
+        let output_dir = self.config.get_effective_output_dir();
+        let mut report = queue
+            .execute_all(Arc::clone(&self.repository), &output_dir)
+            .await?;
+        report.failures_file = self
+            .repository
+            .write_failures(&report.failures, &output_dir)
+            .await?;
+
+        // Tasks not started are not failures; report the batch as interrupted
+        if self.cancellation.is_cancelled() {
+            return Err(FirecrawlError::Cancelled(format!(
+                "batch of {} tasks stopped",
+                total
+            )));
+        }
+        Ok(report)
     }
 
     /// Get task execution statistics
@@ -207,6 +252,9 @@ impl TaskDefinition {
     }
 }
 
+/// Number of recent execution times kept in [`TaskStatistics::recent_latencies`]
+pub const RECENT_LATENCY_SAMPLES: usize = 60;
+
 /// Task execution statistics
 #[derive(Debug, Clone, Default)]
 pub struct TaskStatistics {
@@ -218,9 +266,34 @@ pub struct TaskStatistics {
     pub cache_hits: usize,
     pub cache_misses: usize,
     pub average_execution_time: std::time::Duration,
+    /// Tasks whose execution time went into the average
+    pub timed_tasks: usize,
+    /// Execution times of the latest finished tasks, oldest first
+    pub recent_latencies: VecDeque<std::time::Duration>,
+    /// Failed tasks by [`FirecrawlError::error_code`]
+    pub error_codes: BTreeMap<String, usize>,
 }
 
 impl TaskStatistics {
+    /// Tasks that finished, successfully or not
+    pub fn finished_tasks(&self) -> usize {
+        self.completed_tasks + self.failed_tasks
+    }
+
+    /// Record the execution time of a finished task
+    pub fn record_execution_time(&mut self, elapsed: std::time::Duration) {
+        self.timed_tasks += 1;
+        // Running mean over every timed task, not just the recent window
+        let average = self.average_execution_time.as_secs_f64();
+        self.average_execution_time = std::time::Duration::from_secs_f64(
+            average + (elapsed.as_secs_f64() - average) / self.timed_tasks as f64,
+        );
+        if self.recent_latencies.len() == RECENT_LATENCY_SAMPLES {
+            self.recent_latencies.pop_front();
+        }
+        self.recent_latencies.push_back(elapsed);
+    }
+
     /// Get success rate as percentage
     pub fn success_rate(&self) -> f64 {
         if self.total_tasks == 0 {
@@ -248,6 +321,8 @@ pub struct TaskServiceBuilder {
     cache_service: Option<Arc<dyn CacheService + Send + Sync>>,
     repository: Option<Arc<dyn ContentRepository + Send + Sync>>,
     config: Option<AppConfig>,
+    cancellation: CancellationToken,
+    fail_fast: bool,
 }
 
 impl TaskServiceBuilder {
@@ -258,6 +333,8 @@ impl TaskServiceBuilder {
             cache_service: None,
             repository: None,
             config: None,
+            cancellation: CancellationToken::new(),
+            fail_fast: false,
         }
     }
 
@@ -286,6 +363,18 @@ impl TaskServiceBuilder {
         self
     }
 
+    /// Stop starting batch tasks once `cancellation` is cancelled
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
+    /// Stop a batch at the first task, or crawled page, that fails
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
     pub fn build(self) -> FirecrawlResult<TaskService> {
         let api_service = self.api_service
             .ok_or_else(|| FirecrawlError::ConfigurationError(
@@ -313,6 +402,8 @@ impl TaskServiceBuilder {
             cache_service: self.cache_service,
             repository,
             config,
+            cancellation: self.cancellation,
+            fail_fast: self.fail_fast,
         })
     }
 }
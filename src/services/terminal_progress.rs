@@ -0,0 +1,484 @@
+use async_trait::async_trait;
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::errors::FirecrawlError;
+use crate::services::{ProgressObserver, TaskId};
+
+/// Width assumed when the terminal size is unknown
+const DEFAULT_WIDTH: usize = 80;
+
+/// Cells of the bar itself, between the brackets
+const BAR_WIDTH: usize = 24;
+
+/// Least time between two progress lines of a task in line output
+const LINE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How much progress output the CLI prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// No progress output at all
+    Quiet,
+    /// Bars, or throttled progress lines when not on a terminal
+    #[default]
+    Normal,
+    /// Every start, progress update and outcome on its own line
+    Verbose,
+}
+
+/// How progress is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStyle {
+    /// Single-line bars redrawn in place, one per running task
+    Bars,
+    /// Plain lines, for pipes and log files
+    Lines,
+}
+
+impl ProgressStyle {
+    /// Bars when stderr is a terminal, lines otherwise
+    pub fn detect() -> Self {
+        if std::io::stderr().is_terminal() {
+            ProgressStyle::Bars
+        } else {
+            ProgressStyle::Lines
+        }
+    }
+}
+
+/// A running task and what it last reported
+#[derive(Debug, Clone)]
+struct TaskBar {
+    task_id: TaskId,
+    task_type: String,
+    url: String,
+    started: Instant,
+    completed: u32,
+    total: u32,
+    fraction: f32,
+    /// First item count reported and when, the base of the throughput
+    baseline: Option<(Instant, u32)>,
+    last_line: Option<Instant>,
+}
+
+impl TaskBar {
+    fn new(task_id: TaskId, url: &str, task_type: &str, now: Instant) -> Self {
+        Self {
+            task_id,
+            task_type: task_type.to_string(),
+            url: url.to_string(),
+            started: now,
+            completed: 0,
+            total: 0,
+            fraction: 0.0,
+            baseline: None,
+            last_line: None,
+        }
+    }
+
+    /// Items finished per second since the first count was reported. Items
+    /// done before then, e.g. by a job that was already running, don't count.
+    fn throughput(&self, now: Instant) -> Option<f64> {
+        let (since, base) = self.baseline?;
+        let elapsed = now.duration_since(since).as_secs_f64();
+        (elapsed > 0.0 && self.completed > base)
+            .then(|| (self.completed - base) as f64 / elapsed)
+    }
+
+    /// Time left at the current throughput
+    fn eta(&self, now: Instant) -> Option<Duration> {
+        let rate = self.throughput(now).filter(|rate| *rate > 0.0)?;
+        let remaining = self.total.saturating_sub(self.completed) as f64;
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+
+    /// Counts, throughput and ETA, e.g. `5/10 50% 1.2/s ETA 4s`
+    fn figures(&self, now: Instant) -> String {
+        let elapsed = format_elapsed(now.duration_since(self.started));
+        if self.total == 0 && self.fraction == 0.0 {
+            return elapsed;
+        }
+        if self.total == 0 {
+            return format!("{:>3.0}% {}", self.fraction * 100.0, elapsed);
+        }
+        let mut figures = format!(
+            "{}/{} {:>3.0}%",
+            self.completed,
+            self.total,
+            self.fraction * 100.0
+        );
+        if let Some(rate) = self.throughput(now) {
+            figures.push_str(&format!(" {:.1}/s", rate));
+        }
+        match self.eta(now) {
+            Some(eta) if self.completed < self.total => {
+                figures.push_str(&format!(" ETA {}", format_elapsed(eta)))
+            }
+            _ => {}
+        }
+        figures
+    }
+
+    /// Bar line fitted to `width`, the URL shortened to make room
+    fn bar_line(&self, now: Instant, width: usize) -> String {
+        let filled = ((self.fraction * BAR_WIDTH as f32) as usize).min(BAR_WIDTH);
+        let mut bar = "=".repeat(filled);
+        if filled < BAR_WIDTH {
+            bar.push('>');
+            bar.push_str(&" ".repeat(BAR_WIDTH - filled - 1));
+        }
+        let prefix = format!("{:<7}[{}] {} ", self.task_type, bar, self.figures(now));
+        let room = width.saturating_sub(prefix.chars().count());
+        format!("{}{}", prefix, truncate(&self.url, room))
+    }
+}
+
+/// Elapsed time as `4.2s`, `3m05s` or `1h02m`
+fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs / 60) % 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+/// First `width` characters of `text`, ending with `…` when cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+struct TerminalState {
+    writer: Box<dyn Write + Send>,
+    bars: Vec<TaskBar>,
+    /// Bar lines currently on screen, below the cursor's line
+    drawn: usize,
+}
+
+/// Progress observer for the plain CLI. On a terminal it keeps one redrawn
+/// bar per running task, with throughput and ETA once a task reports item
+/// counts; finished tasks leave a summary line above the bars. Elsewhere it
+/// prints progress as plain lines.
+pub struct TerminalProgressObserver {
+    id: String,
+    style: ProgressStyle,
+    verbosity: Verbosity,
+    /// Fixed line width; the terminal is asked for its width when unset
+    width: Option<usize>,
+    state: Mutex<TerminalState>,
+}
+
+impl TerminalProgressObserver {
+    /// Create an observer drawing on stderr in the detected style
+    pub fn new(verbosity: Verbosity) -> Self {
+        let mut observer = Self::with_writer(
+            Box::new(std::io::stderr()),
+            ProgressStyle::detect(),
+            verbosity,
+        );
+        observer.width = None;
+        observer
+    }
+
+    /// Create an observer writing to `writer` in the given style
+    pub fn with_writer(
+        writer: Box<dyn Write + Send>,
+        style: ProgressStyle,
+        verbosity: Verbosity,
+    ) -> Self {
+        Self {
+            id: format!(
+                "terminal-{}",
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+            ),
+            style,
+            verbosity,
+            width: Some(DEFAULT_WIDTH),
+            state: Mutex::new(TerminalState {
+                writer,
+                bars: Vec::new(),
+                drawn: 0,
+            }),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width.unwrap_or_else(|| {
+            crossterm::terminal::size()
+                .map(|(columns, _)| usize::from(columns))
+                .unwrap_or(DEFAULT_WIDTH)
+        })
+    }
+
+    /// Print `lines` for good, then redraw the bars below them
+    fn write(&self, state: &mut TerminalState, lines: &[String]) {
+        let mut out = String::new();
+        match self.style {
+            ProgressStyle::Bars => {
+                // Back to the first bar line, clearing everything below
+                if state.drawn > 0 {
+                    out.push_str(&format!("\r\x1b[{}A\x1b[J", state.drawn));
+                }
+                for line in lines {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                // One column spare, so a full line never wraps and throws off the count
+                let (now, width) = (Instant::now(), self.width().saturating_sub(1));
+                for bar in &state.bars {
+                    out.push_str(&bar.bar_line(now, width));
+                    out.push('\n');
+                }
+                state.drawn = state.bars.len();
+            }
+            ProgressStyle::Lines => {
+                for line in lines {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        // Progress output is best effort; a closed stderr must not fail the task
+        let _ = state.writer.write_all(out.as_bytes());
+        let _ = state.writer.flush();
+    }
+
+    /// Apply a progress update and print or redraw it
+    fn update(&self, task_id: TaskId, apply: impl FnOnce(&mut TaskBar)) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let Some(bar) = state.bars.iter_mut().find(|bar| bar.task_id == task_id) else {
+            return;
+        };
+        apply(bar);
+
+        let line = match self.style {
+            ProgressStyle::Bars => None,
+            ProgressStyle::Lines => {
+                let finished = bar.total > 0 && bar.completed >= bar.total;
+                let due = bar
+                    .last_line
+                    .is_none_or(|last| now.duration_since(last) >= LINE_INTERVAL);
+                (self.verbosity == Verbosity::Verbose || due || finished).then(|| {
+                    bar.last_line = Some(now);
+                    format!("⏳ {} {}: {}", bar.task_type, bar.url, bar.figures(now))
+                })
+            }
+        };
+        self.write(&mut state, line.as_slice());
+    }
+
+    /// Remove a finished task and print its outcome
+    fn finish(&self, task_id: TaskId, outcome: impl FnOnce(&TaskBar, Duration) -> String) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(position) = state.bars.iter().position(|bar| bar.task_id == task_id) else {
+            return;
+        };
+        let bar = state.bars.remove(position);
+        let line = outcome(&bar, bar.started.elapsed());
+        self.write(&mut state, &[line]);
+    }
+}
+
+#[async_trait]
+impl ProgressObserver for TerminalProgressObserver {
+    async fn on_task_started(&self, task_id: TaskId, url: &str, task_type: &str) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.bars.push(TaskBar::new(task_id, url, task_type, Instant::now()));
+        let announce = self.style == ProgressStyle::Lines || self.verbosity == Verbosity::Verbose;
+        let line = announce.then(|| format!("🚀 Started {} task for: {}", task_type, url));
+        self.write(&mut state, line.as_slice());
+    }
+
+    async fn on_task_progress(&self, task_id: TaskId, _url: &str, _task_type: &str, progress: f32) {
+        self.update(task_id, |bar| bar.fraction = progress.clamp(0.0, 1.0));
+    }
+
+    async fn on_task_items(
+        &self,
+        task_id: TaskId,
+        _url: &str,
+        _task_type: &str,
+        completed: u32,
+        total: u32,
+    ) {
+        self.update(task_id, |bar| {
+            bar.baseline.get_or_insert((Instant::now(), completed));
+            bar.completed = completed;
+            bar.total = total;
+            bar.fraction = if total == 0 {
+                0.0
+            } else {
+                (completed as f32 / total as f32).min(1.0)
+            };
+        });
+    }
+
+    async fn on_task_completed(&self, task_id: TaskId, url: &str, task_type: &str) {
+        self.finish(task_id, |bar, elapsed| {
+            let items = if bar.total > 0 {
+                format!(" ({} items)", bar.completed)
+            } else {
+                String::new()
+            };
+            format!(
+                "✅ Completed {} task for: {} in {}{}",
+                task_type,
+                url,
+                format_elapsed(elapsed),
+                items
+            )
+        });
+    }
+
+    async fn on_task_failed(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    ) {
+        self.finish(task_id, |_, elapsed| {
+            format!(
+                "❌ Failed {} task for: {} after {} - {}",
+                task_type,
+                url,
+                format_elapsed(elapsed),
+                error
+            )
+        });
+    }
+
+    fn observer_id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Writer whose output the test can read back
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Output {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bars_redraw_concurrent_tasks_in_place() {
+        let output = Output::default();
+        let observer = TerminalProgressObserver::with_writer(
+            Box::new(output.clone()),
+            ProgressStyle::Bars,
+            Verbosity::Normal,
+        );
+        let (a, b) = (TaskId::next(), TaskId::next());
+
+        observer.on_task_started(a, "https://a.com", "crawl").await;
+        observer.on_task_started(b, "https://b.com", "scrape").await;
+        // Throughput and ETA need a second count to measure from
+        observer.on_task_items(a, "https://a.com", "crawl", 2, 10).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        observer.on_task_items(a, "https://a.com", "crawl", 5, 10).await;
+        let text = output.text();
+        // The second draw moves up over the first bar, the third over both
+        assert!(text.contains("\x1b[1A\x1b[J"));
+        assert!(text.contains("\x1b[2A\x1b[J"));
+        let last_frame = text.rsplit("\x1b[J").next().unwrap();
+        let lines: Vec<&str> = last_frame.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("crawl  [============>           ] 5/10  50% "));
+        assert!(lines[0].contains(" ETA ") && lines[0].ends_with("https://a.com"));
+        assert!(lines[1].starts_with("scrape [> "));
+        assert!(lines.iter().all(|line| line.chars().count() <= DEFAULT_WIDTH));
+
+        observer.on_task_completed(a, "https://a.com", "crawl").await;
+        let last_frame = output.text().rsplit("\x1b[J").next().unwrap().to_string();
+        let lines: Vec<&str> = last_frame.lines().collect();
+        assert!(lines[0].starts_with("✅ Completed crawl task for: https://a.com in "));
+        assert!(lines[0].ends_with("(5 items)"));
+        assert!(lines[1].starts_with("scrape [> "));
+    }
+
+    #[tokio::test]
+    async fn test_runs_of_the_same_url_keep_their_own_bars() {
+        let output = Output::default();
+        let observer = TerminalProgressObserver::with_writer(
+            Box::new(output.clone()),
+            ProgressStyle::Bars,
+            Verbosity::Normal,
+        );
+        let (first, second) = (TaskId::next(), TaskId::next());
+
+        observer.on_task_started(first, "https://a.com", "crawl").await;
+        observer.on_task_started(second, "https://a.com", "crawl").await;
+        observer.on_task_items(second, "https://a.com", "crawl", 3, 10).await;
+        observer.on_task_completed(first, "https://a.com", "crawl").await;
+
+        let last_frame = output.text().rsplit("\x1b[J").next().unwrap().to_string();
+        let lines: Vec<&str> = last_frame.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("✅ Completed crawl task for: https://a.com in "));
+        assert!(!lines[0].contains("items"));
+        assert!(lines[1].starts_with("crawl  [=======>                ] 3/10  30% "));
+    }
+
+    #[tokio::test]
+    async fn test_lines_are_throttled_unless_verbose() {
+        for (verbosity, progress_lines) in [(Verbosity::Normal, 2), (Verbosity::Verbose, 3)] {
+            let output = Output::default();
+            let observer = TerminalProgressObserver::with_writer(
+                Box::new(output.clone()),
+                ProgressStyle::Lines,
+                verbosity,
+            );
+
+            let task_id = TaskId::next();
+            observer.on_task_started(task_id, "https://a.com", "crawl").await;
+            observer.on_task_items(task_id, "https://a.com", "crawl", 1, 4).await;
+            observer.on_task_items(task_id, "https://a.com", "crawl", 2, 4).await;
+            // The last update always shows
+            observer.on_task_items(task_id, "https://a.com", "crawl", 4, 4).await;
+            observer
+                .on_task_failed(
+                    task_id,
+                    "https://a.com",
+                    "crawl",
+                    &FirecrawlError::TimeoutError("slow".to_string()),
+                )
+                .await;
+
+            let text = output.text();
+            assert!(!text.contains('\x1b'));
+            let lines: Vec<&str> = text.lines().collect();
+            assert_eq!(lines[0], "🚀 Started crawl task for: https://a.com");
+            assert_eq!(lines.iter().filter(|line| line.starts_with("⏳")).count(), progress_lines);
+            assert_eq!(lines[1], "⏳ crawl https://a.com: 1/4  25%");
+            assert!(lines.last().unwrap().ends_with("Operation timed out: slow"));
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+use crate::api::models::scrape_model::{
+    ChangeStatus, ChangeTrackingFormat, ChangeTrackingMode, ScrapeData, ScrapeRequest,
+};
+use crate::api::services::client::FirecrawlClient;
+use crate::errors::{ApiError, FirecrawlError, FirecrawlResult};
+use crate::storage::StorageResult;
+
+/// Directory (inside the output directory) that change snapshots are stored in
+pub const CHANGES_DIR_NAME: &str = "changes";
+
+/// Options for watching a URL for changes
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Time between two checks
+    pub every: Duration,
+
+    /// Change tracking modes requested from the API
+    pub modes: Vec<ChangeTrackingMode>,
+
+    /// Keep a separate change history under this tag
+    pub tag: Option<String>,
+
+    /// Stop after this many checks (runs forever when `None`)
+    pub max_checks: Option<u32>,
+
+    /// Directory snapshots are stored under
+    pub output_dir: PathBuf,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            every: Duration::from_secs(3600),
+            modes: vec![ChangeTrackingMode::GitDiff],
+            tag: None,
+            max_checks: None,
+            output_dir: PathBuf::from("./output"),
+        }
+    }
+}
+
+/// Outcome of a single check of a watched URL
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEvent {
+    /// URL that was checked
+    pub url: String,
+
+    /// When the check ran
+    pub checked_at: chrono::DateTime<chrono::Utc>,
+
+    /// Change status reported by the API
+    pub status: ChangeStatus,
+
+    /// When the page was previously scraped, if ever
+    pub previous_scrape_at: Option<String>,
+
+    /// Visibility reported by the API
+    pub visibility: Option<String>,
+
+    /// Unified diff against the previous scrape (git-diff mode)
+    pub diff: Option<String>,
+
+    /// Structured comparison (json mode)
+    pub json: Option<HashMap<String, Value>>,
+
+    /// Snapshot file the event was stored in
+    pub snapshot: Option<PathBuf>,
+}
+
+impl ChangeEvent {
+    /// Build an event from a scrape that requested the changeTracking format
+    pub fn from_scrape(url: &str, data: &ScrapeData) -> FirecrawlResult<Self> {
+        let tracking = data.change_tracking.as_ref().ok_or_else(|| {
+            FirecrawlError::ApiError(ApiError::InvalidResponse(
+                "Response did not include change tracking data".to_string(),
+            ))
+        })?;
+
+        Ok(Self {
+            url: url.to_string(),
+            checked_at: chrono::Utc::now(),
+            status: tracking.change_status.unwrap_or(ChangeStatus::New),
+            previous_scrape_at: tracking.previous_scrape_at.clone(),
+            visibility: tracking.visibility.clone(),
+            diff: tracking.diff.clone(),
+            json: tracking.json.clone(),
+            snapshot: None,
+        })
+    }
+
+    /// Whether the event reports a difference from the previous scrape
+    pub fn is_change(&self) -> bool {
+        self.status != ChangeStatus::Same
+    }
+}
+
+/// Periodically rescrapes a URL with change tracking and records every diff
+pub struct ChangeWatcher {
+    client: FirecrawlClient,
+    options: WatchOptions,
+}
+
+impl ChangeWatcher {
+    /// Create a new watcher
+    pub fn new(client: FirecrawlClient, options: WatchOptions) -> Self {
+        Self { client, options }
+    }
+
+    /// Build the scrape request used for every check
+    pub fn request(&self, url: &str) -> ScrapeRequest {
+        ScrapeRequest::builder()
+            .url(url)
+            .change_tracking(ChangeTrackingFormat {
+                modes: self.options.modes.clone(),
+                tag: self.options.tag.clone(),
+                ..Default::default()
+            })
+            .build()
+    }
+
+    /// Directory the snapshots of `url` are stored in
+    pub fn snapshot_dir(&self, url: &str) -> PathBuf {
+        let mut name = slug::slugify(url);
+        if let Some(tag) = &self.options.tag {
+            name.push('-');
+            name.push_str(&slug::slugify(tag));
+        }
+        self.options.output_dir.join(CHANGES_DIR_NAME).join(name)
+    }
+
+    /// Scrape `url` once and store the resulting snapshot
+    pub async fn check(&self, url: &str) -> FirecrawlResult<ChangeEvent> {
+        let data = self
+            .client
+            .scrape_with_request(self.request(url))
+            .await
+            .map_err(|e| FirecrawlError::from_client_error(&e))?;
+
+        let mut event = ChangeEvent::from_scrape(url, &data)?;
+        event.snapshot = Some(store_snapshot(&self.snapshot_dir(url), &event).await?);
+        Ok(event)
+    }
+
+    /// Check `url` every `options.every`, passing each outcome to `on_event`.
+    ///
+    /// Failed checks are reported but do not stop the loop.
+    pub async fn watch<F>(&self, url: &str, mut on_event: F)
+    where
+        F: FnMut(FirecrawlResult<ChangeEvent>),
+    {
+        let mut interval = tokio::time::interval(self.options.every);
+        // A check that overruns the interval delays the next one instead of
+        // triggering a burst of catch-up checks
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut checks = 0;
+
+        loop {
+            interval.tick().await;
+            on_event(self.check(url).await);
+
+            checks += 1;
+            if self.options.max_checks.is_some_and(|max| checks >= max) {
+                break;
+            }
+        }
+    }
+}
+
+/// Store an event as `<timestamp>.json`, plus `<timestamp>.diff` when it carries a text diff.
+/// The timestamp has millisecond precision; a counter is appended if a snapshot
+/// with the same name already exists, so no snapshot is ever overwritten.
+pub async fn store_snapshot(dir: &Path, event: &ChangeEvent) -> StorageResult<PathBuf> {
+    tokio::fs::create_dir_all(dir).await?;
+
+    let timestamp = event.checked_at.format("%Y%m%d_%H%M%S_%3f").to_string();
+    let mut stem = timestamp.clone();
+    let mut counter = 1;
+    while tokio::fs::metadata(dir.join(format!("{}.json", stem))).await.is_ok() {
+        stem = format!("{}-{}", timestamp, counter);
+        counter += 1;
+    }
+
+    if let Some(diff) = &event.diff {
+        tokio::fs::write(dir.join(format!("{}.diff", stem)), diff).await?;
+    }
+
+    let path = dir.join(format!("{}.json", stem));
+    tokio::fs::write(&path, serde_json::to_string_pretty(event)?).await?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_request_includes_change_tracking_format() {
+        let client = FirecrawlClient::new("http://localhost:3002/v2", None).unwrap();
+        let watcher = ChangeWatcher::new(
+            client,
+            WatchOptions {
+                modes: vec![ChangeTrackingMode::GitDiff, ChangeTrackingMode::Json],
+                tag: Some("prod".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let json = serde_json::to_value(watcher.request("https://example.com")).unwrap();
+        assert_eq!(
+            json["formats"],
+            serde_json::json!([
+                "markdown",
+                {"type": "changeTracking", "modes": ["git-diff", "json"], "tag": "prod"}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_check_keeps_the_error_type() {
+        let client = FirecrawlClient::new("http://127.0.0.1:9/v2", None).unwrap();
+        let watcher = ChangeWatcher::new(client, WatchOptions::default());
+
+        let error = watcher.check("https://example.com").await.unwrap_err();
+        assert_eq!(error.error_code(), "NETWORK_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_event_from_scrape_and_snapshot() {
+        let data: ScrapeData = serde_json::from_value(serde_json::json!({
+            "markdown": "# Hello",
+            "changeTracking": {
+                "previousScrapeAt": "2025-01-01T00:00:00Z",
+                "changeStatus": "changed",
+                "visibility": "visible",
+                "diff": {"text": "-old\n+new"}
+            }
+        }))
+        .unwrap();
+
+        let event = ChangeEvent::from_scrape("https://example.com", &data).unwrap();
+        assert_eq!(event.status, ChangeStatus::Changed);
+        assert_eq!(event.diff.as_deref(), Some("-old\n+new"));
+        assert!(event.is_change());
+
+        let dir = tempdir().unwrap();
+        let path = store_snapshot(dir.path(), &event).await.unwrap();
+        assert!(path.exists());
+        assert!(path.with_extension("diff").exists());
+
+        // Storing the same check again keeps both snapshots
+        let again = store_snapshot(dir.path(), &event).await.unwrap();
+        assert_ne!(again, path);
+        assert!(again.exists() && again.with_extension("diff").exists());
+    }
+}
@@ -0,0 +1,392 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+use crate::errors::FirecrawlError;
+use crate::services::{ProgressObserver, TaskClock, TaskEvent, TaskEventKind, TaskId};
+
+/// Header carrying `sha256=<hex>`, the HMAC-SHA256 of the request body keyed
+/// with the webhook secret
+pub const SIGNATURE_HEADER: &str = "X-Fc-Cli-Signature";
+
+/// Header carrying the event name, e.g. `task_completed`
+pub const EVENT_HEADER: &str = "X-Fc-Cli-Event";
+
+/// Delivery settings for webhook notifications
+#[derive(Debug, Clone)]
+pub struct WebhookOptions {
+    /// Secret the payloads are signed with; payloads go unsigned without one
+    pub secret: Option<String>,
+
+    /// Attempts per URL before a notification is given up
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled for every further one
+    pub initial_backoff: Duration,
+
+    /// Timeout of a single request
+    pub timeout: Duration,
+}
+
+impl Default for WebhookOptions {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// HMAC-SHA256 signature of `body`, as sent in [`SIGNATURE_HEADER`]
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Progress observer POSTing a JSON [`TaskEvent`] to every configured URL
+/// when a task completes or fails. Tasks complete once their output is saved,
+/// so a crawl with pages that could not be saved is reported as `task_failed`
+/// with `PARTIAL_SUCCESS`. Notifications are sent in the background;
+/// [`flush`](Self::flush) waits for the ones still going out.
+pub struct WebhookProgressObserver {
+    id: String,
+    sender: Arc<WebhookSender>,
+    pending: Mutex<JoinSet<()>>,
+    clock: TaskClock,
+}
+
+/// URLs, settings and HTTP client the notifications are sent with, shared by
+/// the background deliveries
+struct WebhookSender {
+    urls: Vec<String>,
+    options: WebhookOptions,
+    client: reqwest::Client,
+}
+
+impl WebhookProgressObserver {
+    pub fn new(urls: Vec<String>, options: WebhookOptions) -> Result<Self, FirecrawlError> {
+        let client = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .map_err(|e| FirecrawlError::ConfigurationError(e.to_string()))?;
+        Ok(Self {
+            id: format!(
+                "webhook-{}",
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+            ),
+            sender: Arc::new(WebhookSender {
+                urls,
+                options,
+                client,
+            }),
+            pending: Mutex::new(JoinSet::new()),
+            clock: TaskClock::default(),
+        })
+    }
+
+    /// Send `event` to every URL. Failures are logged rather than returned,
+    /// since a notification must not fail the task it reports on.
+    pub async fn deliver(&self, event: &TaskEvent) {
+        self.sender.deliver(event).await;
+    }
+
+    /// Send `event` in a background task, so retries against a slow or
+    /// failing endpoint never hold up the task being reported on
+    pub fn deliver_in_background(&self, event: TaskEvent) {
+        let sender = Arc::clone(&self.sender);
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        // Forget the deliveries that already finished
+        while pending.try_join_next().is_some() {}
+        pending.spawn(async move { sender.deliver(&event).await });
+    }
+
+    /// Wait up to `timeout` for the notifications still being sent, then give
+    /// up on the rest. Returns how many were given up.
+    pub async fn flush(&self, timeout: Duration) -> usize {
+        let mut pending = std::mem::take(
+            &mut *self.pending.lock().unwrap_or_else(PoisonError::into_inner),
+        );
+        let drained = tokio::time::timeout(timeout, async {
+            while pending.join_next().await.is_some() {}
+        })
+        .await;
+        if drained.is_ok() {
+            return 0;
+        }
+
+        let abandoned = pending.len();
+        log::warn!(
+            "Gave up on {} webhook notification(s) still being sent after {:?}",
+            abandoned,
+            timeout
+        );
+        pending.abort_all();
+        abandoned
+    }
+}
+
+impl WebhookSender {
+    /// Send `event` to every URL, logging the ones it could not reach
+    async fn deliver(&self, event: &TaskEvent) {
+        let body = match serde_json::to_vec(event) {
+            Ok(body) => body,
+            Err(e) => {
+                log::error!("Failed to serialize webhook payload: {}", e);
+                return;
+            }
+        };
+        let signature = self
+            .options
+            .secret
+            .as_deref()
+            .map(|secret| sign_payload(secret, &body));
+
+        for url in &self.urls {
+            if let Err(e) = self
+                .deliver_to(url, event.name(), &body, signature.as_deref())
+                .await
+            {
+                log::warn!("Webhook notification to {} failed: {}", url, e);
+            }
+        }
+    }
+
+    /// POST `body` to `url`, retrying with exponential backoff on network
+    /// errors, rate limiting and server errors
+    async fn deliver_to(
+        &self,
+        url: &str,
+        event: &str,
+        body: &[u8],
+        signature: Option<&str>,
+    ) -> Result<(), FirecrawlError> {
+        let mut backoff = self.options.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let mut request = self
+                .client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, event)
+                .body(body.to_vec());
+            if let Some(signature) = signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    let error = FirecrawlError::from_client_error(&anyhow::Error::new(
+                        response.error_for_status().unwrap_err(),
+                    ));
+                    if !(status.is_server_error() || status.as_u16() == 429) {
+                        return Err(error);
+                    }
+                    error
+                }
+                Err(e) => FirecrawlError::from_client_error(&anyhow::Error::new(e)),
+            };
+
+            if attempt >= self.options.max_attempts {
+                return Err(error);
+            }
+            log::debug!(
+                "Webhook attempt {} to {} failed, retrying in {:?}: {}",
+                attempt,
+                url,
+                backoff,
+                error
+            );
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+
+#[async_trait]
+impl ProgressObserver for WebhookProgressObserver {
+    async fn on_task_started(&self, task_id: TaskId, _url: &str, _task_type: &str) {
+        self.clock.start(task_id);
+    }
+
+    async fn on_task_progress(
+        &self,
+        _task_id: TaskId,
+        _url: &str,
+        _task_type: &str,
+        _progress: f32,
+    ) {
+    }
+
+    async fn on_task_completed(&self, task_id: TaskId, url: &str, task_type: &str) {
+        let elapsed_ms = self.clock.elapsed_ms(task_id, true);
+        let event =
+            TaskEvent::new(task_id, url, task_type, elapsed_ms, TaskEventKind::TaskCompleted);
+        self.deliver_in_background(event);
+    }
+
+    async fn on_task_failed(
+        &self,
+        task_id: TaskId,
+        url: &str,
+        task_type: &str,
+        error: &FirecrawlError,
+    ) {
+        let elapsed_ms = self.clock.elapsed_ms(task_id, true);
+        self.deliver_in_background(TaskEvent::failed(task_id, url, task_type, elapsed_ms, error));
+    }
+
+    fn observer_id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::NetworkError;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// Request received by the test listener
+    #[derive(Debug, Clone)]
+    struct Received {
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    }
+
+    impl Received {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Local HTTP listener answering with `statuses` in turn (200 once they
+    /// run out) and recording every request
+    async fn listen(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<Received>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&received);
+        tokio::spawn(async move {
+            let mut statuses = statuses.into_iter();
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                let mut headers = Vec::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).await.unwrap();
+                    let Some((key, value)) = line.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    headers.push((key.to_string(), value.to_string()));
+                }
+                let length = headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                    .map_or(0, |(_, value)| value.parse().unwrap());
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.unwrap();
+                log.lock().unwrap().push(Received { headers, body });
+
+                let status = statuses.next().unwrap_or(200);
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                    status
+                );
+                let _ = reader.into_inner().write_all(response.as_bytes()).await;
+            }
+        });
+        (url, received)
+    }
+
+    fn options() -> WebhookOptions {
+        WebhookOptions {
+            secret: Some("s3cret".to_string()),
+            initial_backoff: Duration::from_millis(10),
+            ..WebhookOptions::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_signed_payload_is_retried_after_server_errors() {
+        let (url, received) = listen(vec![503, 500]).await;
+        let observer = WebhookProgressObserver::new(vec![url], options()).unwrap();
+
+        let task_id = TaskId::next();
+        observer.on_task_started(task_id, "https://a.com", "crawl").await;
+        observer.on_task_progress(task_id, "https://a.com", "crawl", 0.5).await;
+        observer.on_task_completed(task_id, "https://a.com", "crawl").await;
+        assert_eq!(observer.flush(Duration::from_secs(5)).await, 0);
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 3);
+        let request = &received[2];
+        assert_eq!(request.header(EVENT_HEADER), Some("task_completed"));
+        assert_eq!(
+            request.header(SIGNATURE_HEADER),
+            Some(sign_payload("s3cret", &request.body).as_str())
+        );
+        let event: TaskEvent = serde_json::from_slice(&request.body).unwrap();
+        assert_eq!(event.kind, TaskEventKind::TaskCompleted);
+        assert_eq!(event.url, "https://a.com");
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let (url, received) = listen(vec![400]).await;
+        let observer = WebhookProgressObserver::new(vec![url], options()).unwrap();
+
+        let error = FirecrawlError::NetworkError(NetworkError::ConnectionFailed(
+            "refused".to_string(),
+        ));
+        observer.on_task_failed(TaskId::next(), "https://b.com", "scrape", &error).await;
+        assert_eq!(observer.flush(Duration::from_secs(5)).await, 0);
+
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 1);
+        let payload: serde_json::Value = serde_json::from_slice(&received[0].body).unwrap();
+        assert_eq!(payload["event"], "task_failed");
+        assert_eq!(payload["error_code"], "NETWORK_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_slow_endpoints_do_not_hold_up_tasks() {
+        // Accepts connections but never answers them
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        let observer = WebhookProgressObserver::new(vec![url], options()).unwrap();
+
+        let started = std::time::Instant::now();
+        observer.on_task_completed(TaskId::next(), "https://c.com", "scrape").await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        assert_eq!(observer.flush(Duration::from_millis(100)).await, 1);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -1,8 +1,8 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::api::models::{scrape_model::ScrapeResponse, crawl_model::CrawlResponse};
-use super::{StorageError, StorageResult};
+use super::{CrawlSaveReport, StorageError, StorageResult};
 
 pub mod savers;
 
@@ -14,16 +14,47 @@ pub trait ContentSaver: Send + Sync {
         &self,
         result: &ScrapeResponse,
         url: &str,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf>;
 
-    /// Save multiple crawl results
+    /// Save multiple crawl results, one file per page. A page that cannot be
+    /// saved is recorded in the report and the others are still written,
+    /// unless `fail_fast` is set.
     async fn save_crawl_results(
         &self,
         results: &[CrawlResponse],
         url: &str,
-        output_dir: &PathBuf,
-    ) -> StorageResult<Vec<PathBuf>>;
+        output_dir: &Path,
+        fail_fast: bool,
+    ) -> StorageResult<CrawlSaveReport> {
+        self.ensure_directory(output_dir).await?;
+        let mut report = CrawlSaveReport::default();
+
+        for (index, result) in results.iter().enumerate() {
+            let filename = self.generate_filename(&result.url, Some(index));
+            let file_path = output_dir.join(filename);
+
+            let saved = match self.render_crawl_page(result, url) {
+                Ok(content) => self.write_file(&file_path, &content).await,
+                Err(e) => Err(e),
+            };
+            match saved {
+                Err(e) if fail_fast => return Err(e),
+                saved => report.record(&result.url, saved.map(|()| file_path)),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Render the file content for a single crawled page
+    fn render_crawl_page(&self, result: &CrawlResponse, url: &str) -> StorageResult<String>;
+
+    /// Recover the page content of `url` from a file this saver wrote,
+    /// dropping anything the saver added around it
+    fn extract_page_body(&self, content: &str, _url: &str) -> String {
+        content.to_string()
+    }
 
     /// Get the file extension for this format
     fn file_extension(&self) -> &'static str;
@@ -40,7 +71,7 @@ pub trait ContentSaver: Send + Sync {
     }
 
     /// Ensure output directory exists
-    async fn ensure_directory(&self, output_dir: &PathBuf) -> StorageResult<()> {
+    async fn ensure_directory(&self, output_dir: &Path) -> StorageResult<()> {
         if !output_dir.exists() {
             tokio::fs::create_dir_all(output_dir).await?;
         }
@@ -48,7 +79,7 @@ pub trait ContentSaver: Send + Sync {
     }
 
     /// Write content to file
-    async fn write_file(&self, path: &PathBuf, content: &str) -> StorageResult<()> {
+    async fn write_file(&self, path: &Path, content: &str) -> StorageResult<()> {
         tokio::fs::write(path, content).await?;
         Ok(())
     }
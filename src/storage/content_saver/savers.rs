@@ -1,7 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::{ContentSaver, StorageError, StorageResult};
 use crate::api::models::{crawl_model::CrawlResponse, scrape_model::ScrapeResponse};
+use crate::storage::CrawlSaveReport;
+
+/// Separator between the header MarkdownSaver writes and the page content
+const MARKDOWN_HEADER_END: &str = "\n\n---\n\n";
 
 /// Markdown content saver
 pub struct MarkdownSaver;
@@ -12,7 +16,7 @@ impl ContentSaver for MarkdownSaver {
         &self,
         result: &ScrapeResponse,
         url: &str,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf> {
         self.ensure_directory(output_dir).await?;
 
@@ -37,38 +41,27 @@ impl ContentSaver for MarkdownSaver {
         Ok(file_path)
     }
 
-    async fn save_crawl_results(
-        &self,
-        results: &[CrawlResponse],
-        url: &str,
-        output_dir: &PathBuf,
-    ) -> StorageResult<Vec<PathBuf>> {
-        self.ensure_directory(output_dir).await?;
-        let mut saved_files = Vec::new();
-
-        for (index, result) in results.iter().enumerate() {
-            let filename = self.generate_filename(&result.url, Some(index));
-            let file_path = output_dir.join(filename);
-
-            let markdown = result
-                .markdown
-                .as_ref()
-                .cloned()
-                .unwrap_or_else(|| "No content available".to_string());
-
-            let content = format!(
-                "# \n\n**Source:** {}\n\n**Crawl from:** {}\n\n**Timestamp:** {}\n\n---\n\n{}",
-                result.url,
-                url,
-                chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
-                markdown
-            );
-
-            self.write_file(&file_path, &content).await?;
-            saved_files.push(file_path);
-        }
+    fn render_crawl_page(&self, result: &CrawlResponse, url: &str) -> StorageResult<String> {
+        let markdown = result
+            .markdown
+            .as_deref()
+            .unwrap_or("No content available");
 
-        Ok(saved_files)
+        Ok(format!(
+            "# \n\n**Source:** {}\n\n**Crawl from:** {}\n\n**Timestamp:** {}\n\n---\n\n{}",
+            result.url,
+            url,
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+            markdown
+        ))
+    }
+
+    fn extract_page_body(&self, content: &str, _url: &str) -> String {
+        // Skip the Source/Timestamp header written by render_crawl_page
+        match content.split_once(MARKDOWN_HEADER_END) {
+            Some((header, body)) if header.starts_with("# \n") => body.to_string(),
+            _ => content.to_string(),
+        }
     }
 
     fn file_extension(&self) -> &'static str {
@@ -85,7 +78,7 @@ impl ContentSaver for HtmlSaver {
         &self,
         result: &ScrapeResponse,
         url: &str,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf> {
         self.ensure_directory(output_dir).await?;
 
@@ -108,31 +101,13 @@ impl ContentSaver for HtmlSaver {
         Ok(file_path)
     }
 
-    async fn save_crawl_results(
-        &self,
-        results: &[CrawlResponse],
-        url: &str,
-        output_dir: &PathBuf,
-    ) -> StorageResult<Vec<PathBuf>> {
-        self.ensure_directory(output_dir).await?;
-        let mut saved_files = Vec::new();
-
-        for (index, result) in results.iter().enumerate() {
-            let filename = self.generate_filename(&result.url, Some(index));
-            let file_path = output_dir.join(filename);
-
-            let html_content = result.html.as_ref().ok_or_else(|| {
-                StorageError::UnsupportedContentType(format!(
-                    "HTML content not available for {}",
-                    result.url
-                ))
-            })?;
-
-            self.write_file(&file_path, html_content).await?;
-            saved_files.push(file_path);
-        }
-
-        Ok(saved_files)
+    fn render_crawl_page(&self, result: &CrawlResponse, _url: &str) -> StorageResult<String> {
+        result.html.clone().ok_or_else(|| {
+            StorageError::UnsupportedContentType(format!(
+                "HTML content not available for {}",
+                result.url
+            ))
+        })
     }
 
     fn file_extension(&self) -> &'static str {
@@ -149,7 +124,7 @@ impl ContentSaver for JsonSaver {
         &self,
         result: &ScrapeResponse,
         url: &str,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf> {
         self.ensure_directory(output_dir).await?;
 
@@ -165,8 +140,9 @@ impl ContentSaver for JsonSaver {
         &self,
         results: &[CrawlResponse],
         _base_url: &str,
-        output_dir: &PathBuf,
-    ) -> StorageResult<Vec<PathBuf>> {
+        output_dir: &Path,
+        _fail_fast: bool,
+    ) -> StorageResult<CrawlSaveReport> {
         self.ensure_directory(output_dir).await?;
 
         // Save crawl results as a single JSON file containing all results
@@ -176,9 +152,29 @@ impl ContentSaver for JsonSaver {
         );
         let file_path = output_dir.join(filename);
 
+        // All pages share the file, so they are saved or fail together
         let json_content = serde_json::to_string_pretty(results)?;
         self.write_file(&file_path, &json_content).await?;
-        Ok(vec![file_path])
+        Ok(CrawlSaveReport {
+            files: vec![Some(file_path); results.len()],
+            failures: Vec::new(),
+        })
+    }
+
+    fn render_crawl_page(&self, result: &CrawlResponse, _url: &str) -> StorageResult<String> {
+        Ok(serde_json::to_string_pretty(result)?)
+    }
+
+    fn extract_page_body(&self, content: &str, url: &str) -> String {
+        // Files hold either a single page or every page of a crawl
+        let page = serde_json::from_str::<CrawlResponse>(content).ok().or_else(|| {
+            serde_json::from_str::<Vec<CrawlResponse>>(content)
+                .ok()
+                .and_then(|pages| pages.into_iter().find(|page| page.url == url))
+        });
+
+        page.and_then(|page| page.markdown.or(page.html))
+            .unwrap_or_else(|| content.to_string())
     }
 
     fn file_extension(&self) -> &'static str {
@@ -195,7 +191,7 @@ impl ContentSaver for RawSaver {
         &self,
         result: &ScrapeResponse,
         url: &str,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf> {
         self.ensure_directory(output_dir).await?;
 
@@ -218,31 +214,13 @@ impl ContentSaver for RawSaver {
         Ok(file_path)
     }
 
-    async fn save_crawl_results(
-        &self,
-        results: &[CrawlResponse],
-        _base_url: &str,
-        output_dir: &PathBuf,
-    ) -> StorageResult<Vec<PathBuf>> {
-        self.ensure_directory(output_dir).await?;
-        let mut saved_files = Vec::new();
-
-        for (index, result) in results.iter().enumerate() {
-            let filename = self.generate_filename(&result.url, Some(index));
-            let file_path = output_dir.join(filename);
-
-            let content = result
-                .markdown
-                .as_ref()
-                .or(result.html.as_ref())
-                .map(|s| s.as_str())
-                .unwrap_or("No content available");
-
-            self.write_file(&file_path, content).await?;
-            saved_files.push(file_path);
-        }
-
-        Ok(saved_files)
+    fn render_crawl_page(&self, result: &CrawlResponse, _url: &str) -> StorageResult<String> {
+        Ok(result
+            .markdown
+            .as_ref()
+            .or(result.html.as_ref())
+            .cloned()
+            .unwrap_or_else(|| "No content available".to_string()))
     }
 
     fn file_extension(&self) -> &'static str {
@@ -0,0 +1,227 @@
+use std::path::{Path, PathBuf};
+
+use super::failures::FailureReport;
+use super::manifest::{INDEX_FILE_NAME, MANIFEST_FILE_NAME, Manifest, ManifestConfig};
+use super::sync::{RemovedPagePolicy, SyncOptions, SyncReport};
+use super::{ContentRepository, StorageResult};
+use crate::api::models::crawl_model::CrawlResponse;
+
+/// How the pages of a crawl are written to the output directory
+#[derive(Debug, Clone, Default)]
+pub struct CrawlRunOptions {
+    /// Whether to write `index.md` alongside the manifest
+    pub write_index: bool,
+
+    /// Only rewrite pages that changed since the previous run, handling pages
+    /// that disappeared with this policy
+    pub sync: Option<RemovedPagePolicy>,
+
+    /// Stop at the first page that cannot be saved instead of listing it in
+    /// `failures.json`
+    pub fail_fast: bool,
+}
+
+/// Everything a crawl run wrote to its output directory
+#[derive(Debug, Clone)]
+pub struct CrawlRunReport {
+    /// Manifest describing the directory after the run
+    pub manifest: Manifest,
+
+    /// URL and file of every page written by this run
+    pub pages: Vec<(String, PathBuf)>,
+
+    /// `manifest.json`, followed by `index.md` when it was requested
+    pub manifest_files: Vec<PathBuf>,
+
+    /// Added, changed, unchanged and removed pages of an incremental sync
+    pub sync: Option<SyncReport>,
+
+    /// Pages that could not be saved
+    pub failures: FailureReport,
+
+    /// `failures.json`, written when any page failed
+    pub failures_file: Option<PathBuf>,
+}
+
+impl CrawlRunReport {
+    /// Paths of every page file described by the manifest, once each
+    pub fn page_files(&self, output_dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for entry in &self.manifest.entries {
+            let path = output_dir.join(&entry.file);
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        files
+    }
+}
+
+/// Save the pages of a crawl, record the run in `manifest.json` and list the
+/// pages that could not be saved in `failures.json`.
+///
+/// This is the single place the CLI and [`CrawlCommand`](crate::commands::CrawlCommand)
+/// write crawl output through, so both produce the same directory layout.
+pub async fn save_crawl_run<R: ContentRepository + ?Sized>(
+    repository: &R,
+    results: &[CrawlResponse],
+    url: &str,
+    job_id: Option<String>,
+    config: ManifestConfig,
+    output_dir: &Path,
+    options: &CrawlRunOptions,
+) -> StorageResult<CrawlRunReport> {
+    let (manifest, pages, manifest_files, sync, failures) = match options.sync {
+        Some(removed) => {
+            let report = repository
+                .sync_crawl_results(
+                    results,
+                    url,
+                    job_id,
+                    config,
+                    output_dir,
+                    &SyncOptions {
+                        removed,
+                        write_index: options.write_index,
+                        fail_fast: options.fail_fast,
+                    },
+                )
+                .await?;
+
+            let pages = report
+                .manifest
+                .entries
+                .iter()
+                .filter(|entry| {
+                    report.added.contains(&entry.url) || report.changed.contains(&entry.url)
+                })
+                .map(|entry| (entry.url.clone(), output_dir.join(&entry.file)))
+                .collect();
+            let mut manifest_files = vec![output_dir.join(MANIFEST_FILE_NAME)];
+            if options.write_index {
+                manifest_files.push(output_dir.join(INDEX_FILE_NAME));
+            }
+            let attempted = report.added.len() + report.changed.len() + report.failures.len();
+            let failures = FailureReport::new(Some(url), attempted, report.failures.clone());
+            (
+                report.manifest.clone(),
+                pages,
+                manifest_files,
+                Some(report),
+                failures,
+            )
+        }
+        None => {
+            let saved = repository
+                .save_crawl_results(
+                    results,
+                    url,
+                    config.output_format,
+                    output_dir,
+                    options.fail_fast,
+                )
+                .await?;
+
+            let pages = results
+                .iter()
+                .zip(&saved.files)
+                .filter_map(|(result, file)| Some((result.url.clone(), file.clone()?)))
+                .collect();
+            let manifest = Manifest::from_crawl_results(
+                url,
+                job_id,
+                config,
+                results,
+                &saved.files,
+                output_dir,
+            )
+            .await?;
+            let manifest_files = repository
+                .write_manifest(&manifest, output_dir, options.write_index)
+                .await?;
+            let failures = FailureReport::new(Some(url), results.len(), saved.failures);
+            (manifest, pages, manifest_files, None, failures)
+        }
+    };
+
+    let failures_file = repository.write_failures(&failures, output_dir).await?;
+    Ok(CrawlRunReport {
+        manifest,
+        pages,
+        manifest_files,
+        sync,
+        failures,
+        failures_file,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::crawl_model::CrawlMetadata;
+    use crate::cli::OutputFormat;
+    use crate::storage::savers::MarkdownSaver;
+    use crate::storage::{ContentSaver, FAILURES_FILE_NAME, FileSystemRepository};
+    use tempfile::tempdir;
+
+    fn crawl_response(url: &str, markdown: &str) -> CrawlResponse {
+        CrawlResponse {
+            id: "1".to_string(),
+            url: url.to_string(),
+            status: "completed".to_string(),
+            completed_at: None,
+            markdown: Some(markdown.to_string()),
+            html: None,
+            metadata: CrawlMetadata {
+                keywords: None,
+                robots: None,
+                og_image: None,
+                author: None,
+                published_date: None,
+                modified_date: None,
+                site_name: None,
+                title: None,
+                status_code: Some(200),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_records_saved_pages_and_failures() {
+        let dir = tempdir().unwrap();
+        let repository = FileSystemRepository::new(dir.path().to_path_buf());
+        let results = vec![
+            crawl_response("https://example.com/a", "A"),
+            crawl_response("https://example.com/b", "B"),
+        ];
+        // A directory where the second page's file goes makes its write fail
+        let blocked = MarkdownSaver.generate_filename("https://example.com/b", Some(1));
+        std::fs::create_dir(dir.path().join(blocked)).unwrap();
+
+        let config = ManifestConfig {
+            output_format: OutputFormat::Markdown,
+            options: None,
+        };
+        let run = save_crawl_run(
+            &repository,
+            &results,
+            "https://example.com",
+            None,
+            config,
+            dir.path(),
+            &CrawlRunOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(run.pages.len(), 1);
+        assert_eq!(run.pages[0].0, "https://example.com/a");
+        assert_eq!(run.manifest.entries.len(), 1);
+        assert_eq!(
+            run.manifest_files,
+            vec![dir.path().join(MANIFEST_FILE_NAME)]
+        );
+        assert_eq!(run.failures.to_string(), "1 of 2 pages could not be saved");
+        assert_eq!(run.failures_file, Some(dir.path().join(FAILURES_FILE_NAME)));
+    }
+}
@@ -1,4 +1,3 @@
-use std::fmt;
 use thiserror::Error;
 
 /// Domain-specific errors for storage operations
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::StorageError;
+
+/// File (inside the output directory) listing the pages a run could not save
+pub const FAILURES_FILE_NAME: &str = "failures.json";
+
+/// Page that could not be saved, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageFailure {
+    /// URL of the page
+    pub url: String,
+
+    /// What went wrong while saving it
+    pub reason: String,
+}
+
+impl PageFailure {
+    pub fn new(url: &str, reason: impl ToString) -> Self {
+        Self {
+            url: url.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Outcome of saving the pages of a crawl one by one
+#[derive(Debug, Clone, Default)]
+pub struct CrawlSaveReport {
+    /// File each result was written to, in the order of the results;
+    /// `None` for pages that failed
+    pub files: Vec<Option<PathBuf>>,
+
+    /// Pages that could not be saved
+    pub failures: Vec<PageFailure>,
+}
+
+impl CrawlSaveReport {
+    /// Record the outcome of saving the next page
+    pub fn record(&mut self, url: &str, saved: Result<PathBuf, StorageError>) {
+        match saved {
+            Ok(path) => self.files.push(Some(path)),
+            Err(e) => {
+                self.files.push(None);
+                self.failures.push(PageFailure::new(url, e));
+            }
+        }
+    }
+
+    /// Every file written, once each (results may share a file)
+    pub fn written_files(&self) -> Vec<PathBuf> {
+        let mut written: Vec<PathBuf> = Vec::new();
+        for path in self.files.iter().flatten() {
+            if !written.contains(path) {
+                written.push(path.clone());
+            }
+        }
+        written
+    }
+}
+
+/// Pages a run could not save, written to `failures.json` so they can be
+/// inspected or retried
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailureReport {
+    /// URL the crawl started from, when the run was a single crawl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+
+    /// When the report was written
+    pub generated_at: DateTime<Utc>,
+
+    /// Number of pages the run tried to save
+    pub attempted: usize,
+
+    pub failures: Vec<PageFailure>,
+}
+
+impl FailureReport {
+    pub fn new(source_url: Option<&str>, attempted: usize, failures: Vec<PageFailure>) -> Self {
+        Self {
+            source_url: source_url.map(str::to_string),
+            generated_at: Utc::now(),
+            attempted,
+            failures,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl std::fmt::Display for FailureReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} pages could not be saved",
+            self.failures.len(),
+            self.attempted
+        )
+    }
+}
@@ -0,0 +1,312 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+use super::StorageResult;
+use crate::api::models::crawl_model::{CrawlMetadata, CrawlOptions, CrawlResponse};
+use crate::cli::OutputFormat;
+
+/// File name of the manifest written next to the saved pages
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// File name of the optional human-readable index
+pub const INDEX_FILE_NAME: &str = "index.md";
+
+/// Version of the manifest schema, bumped on incompatible changes
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Description of everything a single run wrote to an output directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Manifest schema version
+    pub version: u32,
+
+    /// URL the run was started from
+    pub source_url: String,
+
+    /// Crawl job identifier assigned by the API, if any
+    pub job_id: Option<String>,
+
+    /// When the manifest was generated
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+
+    /// Configuration the run was executed with
+    pub config: ManifestConfig,
+
+    /// One entry per saved page, sorted by URL
+    pub entries: Vec<ManifestEntry>,
+}
+
+/// Configuration recorded in the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestConfig {
+    /// Output format the pages were saved in
+    pub output_format: OutputFormat,
+
+    /// Crawl options sent to the API
+    pub options: Option<CrawlOptions>,
+}
+
+/// A single saved page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// URL of the page
+    pub url: String,
+
+    /// Saved file, relative to the output directory
+    pub file: PathBuf,
+
+    /// Page title, if the API reported one
+    pub title: Option<String>,
+
+    /// HTTP status of the page, if the API reported one
+    pub status_code: Option<u16>,
+
+    /// Format the page was saved in; manifests predating the field only
+    /// ever recorded Markdown
+    #[serde(default)]
+    pub format: OutputFormat,
+
+    /// SHA-256 of the content the format is built from (independent of any
+    /// header the saver adds)
+    pub content_hash: String,
+
+    /// Size of the saved file in bytes
+    pub bytes: u64,
+
+    /// Metadata the API reported for the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CrawlMetadata>,
+}
+
+impl ManifestEntry {
+    /// Build the entry for a crawled page saved at `file_path` in `format`
+    pub async fn for_crawl_page(
+        result: &CrawlResponse,
+        format: OutputFormat,
+        file_path: &Path,
+        output_dir: &Path,
+    ) -> StorageResult<Self> {
+        Ok(Self {
+            url: result.url.clone(),
+            file: relative_to(file_path, output_dir),
+            title: result.metadata.title.clone(),
+            status_code: result.metadata.status_code,
+            format,
+            content_hash: crawl_page_hash(result, format),
+            bytes: tokio::fs::metadata(file_path).await?.len(),
+            metadata: Some(result.metadata.clone()),
+        })
+    }
+}
+
+impl Manifest {
+    /// Create an empty manifest for a run
+    pub fn new(source_url: &str, job_id: Option<String>, config: ManifestConfig) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            source_url: source_url.to_string(),
+            job_id,
+            generated_at: chrono::Utc::now(),
+            config,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Build a manifest from crawl results and the files they were saved to,
+    /// as reported by [`ContentSaver::save_crawl_results`](super::ContentSaver::save_crawl_results).
+    ///
+    /// `files` is in the same order as `results`; pages that could not be
+    /// saved (`None`) get no entry.
+    pub async fn from_crawl_results(
+        source_url: &str,
+        job_id: Option<String>,
+        config: ManifestConfig,
+        results: &[CrawlResponse],
+        files: &[Option<PathBuf>],
+        output_dir: &Path,
+    ) -> StorageResult<Self> {
+        let format = config.output_format;
+        let mut manifest = Self::new(source_url, job_id, config);
+
+        for (result, file_path) in results.iter().zip(files) {
+            let Some(file_path) = file_path else {
+                continue;
+            };
+
+            let entry = ManifestEntry::for_crawl_page(result, format, file_path, output_dir).await?;
+            manifest.entries.push(entry);
+        }
+
+        manifest.sort_entries();
+        Ok(manifest)
+    }
+
+    /// Sort entries by URL so that repeated runs produce stable output
+    pub fn sort_entries(&mut self) {
+        self.entries.sort_by(|a, b| a.url.cmp(&b.url));
+    }
+
+    /// Look up the entry for a URL
+    pub fn entry_for_url(&self, url: &str) -> Option<&ManifestEntry> {
+        self.entries.iter().find(|entry| entry.url == url)
+    }
+
+    /// Load a manifest previously written to `output_dir`
+    pub async fn load(output_dir: &Path) -> StorageResult<Option<Self>> {
+        let path = output_dir.join(MANIFEST_FILE_NAME);
+        if tokio::fs::metadata(&path).await.is_err() {
+            return Ok(None);
+        }
+
+        let content = tokio::fs::read_to_string(&path).await?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Render a Markdown table of contents linking to every saved page
+    pub fn render_index(&self) -> String {
+        let mut index = format!(
+            "# Index of {}\n\n**Generated:** {}\n\n",
+            self.source_url,
+            self.generated_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+
+        if let Some(job_id) = &self.job_id {
+            index.push_str(&format!("**Crawl job:** {}\n\n", job_id));
+        }
+
+        index.push_str("| Title | URL | Status |\n|---|---|---|\n");
+        for entry in &self.entries {
+            let title = entry.title.as_deref().unwrap_or(&entry.url);
+            let status = entry
+                .status_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            index.push_str(&format!(
+                "| [{}]({}) | {} | {} |\n",
+                escape_table_cell(title),
+                entry.file.to_string_lossy().replace(' ', "%20"),
+                escape_table_cell(&entry.url),
+                status
+            ));
+        }
+
+        index
+    }
+}
+
+/// Compute the content hash recorded in manifest entries
+pub fn content_hash(content: &str) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(content.as_bytes())))
+}
+
+/// Compute the content hash of a crawled page saved in `format`: its HTML for
+/// the HTML formats, otherwise its Markdown, each falling back to the other
+pub fn crawl_page_hash(result: &CrawlResponse, format: OutputFormat) -> String {
+    let markdown = result.markdown.as_deref();
+    let html = result.html.as_deref();
+    let content = match format {
+        OutputFormat::Html | OutputFormat::RawHtml => html.or(markdown),
+        _ => markdown.or(html),
+    };
+    content_hash(content.unwrap_or_default())
+}
+
+/// Express `path` relative to `base`, falling back to the path itself
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    path.strip_prefix(base)
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Escape characters that would break a Markdown table cell
+fn escape_table_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn crawl_response(url: &str, markdown: &str, title: &str) -> CrawlResponse {
+        CrawlResponse {
+            id: "1".to_string(),
+            url: url.to_string(),
+            status: "completed".to_string(),
+            completed_at: None,
+            markdown: Some(markdown.to_string()),
+            html: None,
+            metadata: CrawlMetadata {
+                keywords: None,
+                robots: None,
+                og_image: None,
+                author: None,
+                published_date: None,
+                modified_date: None,
+                site_name: None,
+                title: Some(title.to_string()),
+                status_code: Some(200),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_manifest_from_crawl_results() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("b.md");
+        let second = dir.path().join("a.md");
+        tokio::fs::write(&first, "header\nB").await.unwrap();
+        tokio::fs::write(&second, "A").await.unwrap();
+
+        let results = vec![
+            crawl_response("https://example.com/b", "B", "Page B"),
+            crawl_response("https://example.com/a", "A", "Page A"),
+        ];
+
+        let manifest = Manifest::from_crawl_results(
+            "https://example.com",
+            Some("job-1".to_string()),
+            ManifestConfig {
+                output_format: OutputFormat::Markdown,
+                options: None,
+            },
+            &results,
+            &[Some(first), Some(second)],
+            dir.path(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(manifest.entries.len(), 2);
+        assert_eq!(manifest.entries[0].url, "https://example.com/a");
+        assert_eq!(manifest.entries[0].file, PathBuf::from("a.md"));
+        assert_eq!(manifest.entries[0].bytes, 1);
+        assert_eq!(manifest.entries[1].bytes, 8);
+        assert_eq!(manifest.entries[1].content_hash, content_hash("B"));
+        assert_eq!(manifest.entries[1].status_code, Some(200));
+        assert_eq!(manifest.entries[1].format, OutputFormat::Markdown);
+        let metadata = manifest.entries[1].metadata.as_ref().unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Page B"));
+
+        let index = manifest.render_index();
+        assert!(index.contains("[Page A](a.md)"));
+        assert!(index.contains("**Crawl job:** job-1"));
+    }
+
+    #[test]
+    fn test_page_hash_follows_output_format() {
+        let mut page = crawl_response("https://example.com", "# Title", "Title");
+        page.html = Some("<h1>Title</h1>".to_string());
+
+        assert_eq!(crawl_page_hash(&page, OutputFormat::Markdown), content_hash("# Title"));
+        assert_eq!(crawl_page_hash(&page, OutputFormat::Html), content_hash("<h1>Title</h1>"));
+
+        // Entries written before the format was recorded are Markdown
+        let entry: ManifestEntry = serde_json::from_str(
+            r#"{"url":"https://example.com","file":"a.md","title":null,"status_code":200,"content_hash":"sha256:00","bytes":1}"#,
+        )
+        .unwrap();
+        assert_eq!(entry.format, OutputFormat::Markdown);
+    }
+}
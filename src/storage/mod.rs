@@ -1,7 +1,15 @@
 pub mod repository;
 pub mod content_saver;
+pub mod crawl_run;
 pub mod errors;
+pub mod failures;
+pub mod manifest;
+pub mod sync;
 
 pub use repository::*;
 pub use content_saver::*;
-pub use errors::*;
\ No newline at end of file
+pub use crawl_run::*;
+pub use errors::*;
+pub use failures::*;
+pub use manifest::*;
+pub use sync::*;
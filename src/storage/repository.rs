@@ -1,9 +1,14 @@
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::api::models::{scrape_model::ScrapeResponse, crawl_model::CrawlResponse};
 use crate::cli::OutputFormat;
-use super::{StorageError, StorageResult};
+use super::StorageResult;
+use super::content_saver::ContentSaver;
+use super::failures::{CrawlSaveReport, FAILURES_FILE_NAME, FailureReport};
+use super::content_saver::savers::{HtmlSaver, JsonSaver, MarkdownSaver, RawSaver};
+use super::manifest::{INDEX_FILE_NAME, MANIFEST_FILE_NAME, Manifest, ManifestConfig};
+use super::sync::{self, SyncOptions, SyncReport};
 
 /// Repository trait for abstracting file operations
 #[async_trait]
@@ -14,23 +19,62 @@ pub trait ContentRepository: Send + Sync {
         result: &ScrapeResponse,
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf>;
 
-    /// Save crawl results in the specified format
+    /// Save crawl results in the specified format, carrying on past pages
+    /// that cannot be saved unless `fail_fast` is set
     async fn save_crawl_results(
         &self,
         results: &[CrawlResponse],
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
+        output_dir: &Path,
+        fail_fast: bool,
+    ) -> StorageResult<CrawlSaveReport>;
+
+    /// Write `manifest.json` (and optionally `index.md`) describing a run
+    async fn write_manifest(
+        &self,
+        manifest: &Manifest,
+        output_dir: &Path,
+        write_index: bool,
     ) -> StorageResult<Vec<PathBuf>>;
 
+    /// Write `failures.json` listing the pages a run could not save, or remove
+    /// the one left by an earlier run when nothing failed
+    async fn write_failures(
+        &self,
+        report: &FailureReport,
+        output_dir: &Path,
+    ) -> StorageResult<Option<PathBuf>>;
+
+    /// Save crawl results incrementally against the manifest of a previous run,
+    /// rewriting only pages whose content changed, then write the new manifest
+    async fn sync_crawl_results(
+        &self,
+        results: &[CrawlResponse],
+        url: &str,
+        job_id: Option<String>,
+        config: ManifestConfig,
+        output_dir: &Path,
+        options: &SyncOptions,
+    ) -> StorageResult<SyncReport>;
+
+    /// Read back the content of `url` as recorded in the manifest of `output_dir`,
+    /// or `None` if the manifest has no entry for it
+    async fn read_crawl_page(
+        &self,
+        manifest: &Manifest,
+        output_dir: &Path,
+        url: &str,
+    ) -> StorageResult<Option<String>>;
+
     /// Create directory if it doesn't exist
-    async fn ensure_directory(&self, path: &PathBuf) -> StorageResult<()>;
+    async fn ensure_directory(&self, path: &Path) -> StorageResult<()>;
 
     /// Check if file exists
-    async fn file_exists(&self, path: &PathBuf) -> bool;
+    async fn file_exists(&self, path: &Path) -> bool;
 
     /// Generate filename from URL and format
     fn generate_filename(&self, url: &str, format: OutputFormat) -> String;
@@ -51,6 +95,19 @@ impl FileSystemRepository {
     pub fn base_dir(&self) -> &PathBuf {
         &self.base_dir
     }
+
+    /// Pick the content saver for an output format
+    fn saver_for(format: OutputFormat) -> Box<dyn ContentSaver> {
+        match format {
+            OutputFormat::Markdown => Box::new(MarkdownSaver),
+            OutputFormat::Html => Box::new(HtmlSaver),
+            OutputFormat::Json => Box::new(JsonSaver),
+            OutputFormat::Raw => Box::new(RawSaver),
+            OutputFormat::RawHtml => Box::new(HtmlSaver), // Use HtmlSaver for RawHtml
+            OutputFormat::Links => Box::new(JsonSaver), // Use JsonSaver for Links
+            OutputFormat::Images => Box::new(JsonSaver), // Use JsonSaver for Images
+        }
+    }
 }
 
 #[async_trait]
@@ -60,21 +117,9 @@ impl ContentRepository for FileSystemRepository {
         result: &ScrapeResponse,
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
+        output_dir: &Path,
     ) -> StorageResult<PathBuf> {
-        use super::content_saver::ContentSaver;
-        use super::content_saver::savers::{MarkdownSaver, HtmlSaver, JsonSaver, RawSaver};
-
-        let saver: Box<dyn ContentSaver> = match format {
-            OutputFormat::Markdown => Box::new(MarkdownSaver),
-            OutputFormat::Html => Box::new(HtmlSaver),
-            OutputFormat::Json => Box::new(JsonSaver),
-            OutputFormat::Raw => Box::new(RawSaver),
-            OutputFormat::RawHtml => Box::new(HtmlSaver), // Use HtmlSaver for RawHtml
-            OutputFormat::Links => Box::new(JsonSaver), // Use JsonSaver for Links
-            OutputFormat::Images => Box::new(JsonSaver), // Use JsonSaver for Images
-        };
-
+        let saver = Self::saver_for(format);
         saver.save_scrape_result(result, url, output_dir).await
     }
 
@@ -83,32 +128,107 @@ impl ContentRepository for FileSystemRepository {
         results: &[CrawlResponse],
         url: &str,
         format: OutputFormat,
-        output_dir: &PathBuf,
+        output_dir: &Path,
+        fail_fast: bool,
+    ) -> StorageResult<CrawlSaveReport> {
+        let saver = Self::saver_for(format);
+        saver
+            .save_crawl_results(results, url, output_dir, fail_fast)
+            .await
+    }
+
+    async fn write_manifest(
+        &self,
+        manifest: &Manifest,
+        output_dir: &Path,
+        write_index: bool,
     ) -> StorageResult<Vec<PathBuf>> {
-        use super::content_saver::ContentSaver;
-        use super::content_saver::savers::{MarkdownSaver, HtmlSaver, JsonSaver, RawSaver};
+        self.ensure_directory(output_dir).await?;
+        let mut written = Vec::new();
+
+        let manifest_path = output_dir.join(MANIFEST_FILE_NAME);
+        let json_content = serde_json::to_string_pretty(manifest)?;
+        tokio::fs::write(&manifest_path, json_content).await?;
+        written.push(manifest_path);
+
+        if write_index {
+            let index_path = output_dir.join(INDEX_FILE_NAME);
+            tokio::fs::write(&index_path, manifest.render_index()).await?;
+            written.push(index_path);
+        }
 
-        let saver: Box<dyn ContentSaver> = match format {
-            OutputFormat::Markdown => Box::new(MarkdownSaver),
-            OutputFormat::Html => Box::new(HtmlSaver),
-            OutputFormat::Json => Box::new(JsonSaver),
-            OutputFormat::Raw => Box::new(RawSaver),
-            OutputFormat::RawHtml => Box::new(HtmlSaver), // Use HtmlSaver for RawHtml
-            OutputFormat::Links => Box::new(JsonSaver), // Use JsonSaver for Links
-            OutputFormat::Images => Box::new(JsonSaver), // Use JsonSaver for Images
+        Ok(written)
+    }
+
+    async fn write_failures(
+        &self,
+        report: &FailureReport,
+        output_dir: &Path,
+    ) -> StorageResult<Option<PathBuf>> {
+        let path = output_dir.join(FAILURES_FILE_NAME);
+        if report.is_empty() {
+            if self.file_exists(&path).await {
+                tokio::fs::remove_file(&path).await?;
+            }
+            return Ok(None);
+        }
+
+        self.ensure_directory(output_dir).await?;
+        tokio::fs::write(&path, serde_json::to_string_pretty(report)?).await?;
+        Ok(Some(path))
+    }
+
+    async fn sync_crawl_results(
+        &self,
+        results: &[CrawlResponse],
+        url: &str,
+        job_id: Option<String>,
+        config: ManifestConfig,
+        output_dir: &Path,
+        options: &SyncOptions,
+    ) -> StorageResult<SyncReport> {
+        let saver = Self::saver_for(config.output_format);
+        let mut report = sync::sync_crawl_results(
+            saver.as_ref(),
+            results,
+            url,
+            job_id,
+            config,
+            output_dir,
+            options,
+        )
+        .await?;
+
+        let written = self
+            .write_manifest(&report.manifest, output_dir, options.write_index)
+            .await?;
+        report.written_files.extend(written);
+        Ok(report)
+    }
+
+    async fn read_crawl_page(
+        &self,
+        manifest: &Manifest,
+        output_dir: &Path,
+        url: &str,
+    ) -> StorageResult<Option<String>> {
+        let Some(entry) = manifest.entry_for_url(url) else {
+            return Ok(None);
         };
 
-        saver.save_crawl_results(results, url, output_dir).await
+        let content = tokio::fs::read_to_string(output_dir.join(&entry.file)).await?;
+        let saver = Self::saver_for(manifest.config.output_format);
+        Ok(Some(saver.extract_page_body(&content, url)))
     }
 
-    async fn ensure_directory(&self, path: &PathBuf) -> StorageResult<()> {
+    async fn ensure_directory(&self, path: &Path) -> StorageResult<()> {
         if !path.exists() {
             tokio::fs::create_dir_all(path).await?;
         }
         Ok(())
     }
 
-    async fn file_exists(&self, path: &PathBuf) -> bool {
+    async fn file_exists(&self, path: &Path) -> bool {
         tokio::fs::metadata(path).await.is_ok()
     }
 
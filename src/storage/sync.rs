@@ -0,0 +1,520 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::failures::PageFailure;
+use super::manifest::{Manifest, ManifestConfig, ManifestEntry, crawl_page_hash};
+use super::{ContentSaver, StorageResult};
+use crate::api::models::crawl_model::CrawlResponse;
+
+/// Directory (inside the output directory) that archived pages are moved to
+pub const ARCHIVE_DIR_NAME: &str = ".archive";
+
+/// What to do with pages from the previous run that the new crawl did not return
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RemovedPagePolicy {
+    /// Leave the file in place but drop it from the manifest
+    #[default]
+    Keep,
+    /// Delete the file
+    Delete,
+    /// Move the file under `.archive/<timestamp>/`
+    Archive,
+}
+
+/// Options controlling an incremental sync
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Handling of pages that disappeared since the previous run
+    pub removed: RemovedPagePolicy,
+
+    /// Whether to write `index.md` alongside the manifest
+    pub write_index: bool,
+
+    /// Stop at the first page that cannot be written instead of carrying on
+    pub fail_fast: bool,
+}
+
+/// Outcome of an incremental sync
+#[derive(Debug, Clone)]
+pub struct SyncReport {
+    /// URLs that were not in the previous manifest
+    pub added: Vec<String>,
+
+    /// URLs whose content hash changed
+    pub changed: Vec<String>,
+
+    /// URLs whose content was identical and were left untouched
+    pub unchanged: Vec<String>,
+
+    /// URLs from the previous manifest that the crawl no longer returned
+    pub removed: Vec<String>,
+
+    /// Files written during this sync (pages and manifest)
+    pub written_files: Vec<PathBuf>,
+
+    /// Pages that could not be written; those already on disk keep their
+    /// previous entry so the next sync retries them
+    pub failures: Vec<PageFailure>,
+
+    /// Manifest describing the directory after the sync
+    pub manifest: Manifest,
+}
+
+impl SyncReport {
+    /// Whether the sync modified any page
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.changed.is_empty() || !self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for SyncReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} changed, {} unchanged, {} removed",
+            self.added.len(),
+            self.changed.len(),
+            self.unchanged.len(),
+            self.removed.len()
+        )
+    }
+}
+
+/// Write crawl results into `output_dir`, only touching pages whose content changed
+/// since the manifest left by the previous run.
+///
+/// The returned report carries the new manifest; writing it is left to the caller.
+pub(crate) async fn sync_crawl_results(
+    saver: &dyn ContentSaver,
+    results: &[CrawlResponse],
+    url: &str,
+    job_id: Option<String>,
+    config: ManifestConfig,
+    output_dir: &Path,
+    options: &SyncOptions,
+) -> StorageResult<SyncReport> {
+    saver.ensure_directory(output_dir).await?;
+
+    let previous = Manifest::load(output_dir).await?;
+    let previous_entries: HashMap<&str, &ManifestEntry> = previous
+        .iter()
+        .flat_map(|manifest| manifest.entries.iter())
+        .map(|entry| (entry.url.as_str(), entry))
+        .collect();
+
+    // Files owned by previous entries stay reserved for their URL
+    let mut used_files: HashSet<PathBuf> = previous_entries
+        .values()
+        .map(|entry| entry.file.clone())
+        .collect();
+    // Pages of a JSON run share one bundle file; such a file is never
+    // rewritten for a single page
+    let mut file_refs: HashMap<&Path, usize> = HashMap::new();
+    for entry in previous_entries.values() {
+        *file_refs.entry(entry.file.as_path()).or_default() += 1;
+    }
+    let is_shared = |file: &Path| file_refs.get(file).is_some_and(|refs| *refs > 1);
+
+    let format = config.output_format;
+    let mut manifest = Manifest::new(url, job_id, config);
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    let mut unchanged = Vec::new();
+    let mut written_files = Vec::new();
+    let mut failures = Vec::new();
+    let mut seen = HashSet::new();
+    // Files of the previous run that pages moved away from, removed at the
+    // end unless another page still uses them
+    let mut replaced_files = Vec::new();
+
+    for result in results {
+        if !seen.insert(result.url.as_str()) {
+            continue;
+        }
+
+        let previous_entry = previous_entries.get(result.url.as_str());
+        // File of the previous run to remove once the page is rewritten
+        let mut replaced_file = None;
+        let relative_file = match previous_entry {
+            Some(entry) if entry.format != format => {
+                // Saved in another format last time: every such page counts as
+                // changed and moves to a file named for the new format
+                if !is_shared(&entry.file) {
+                    used_files.remove(&entry.file);
+                }
+                let file = unique_filename(saver, &result.url, &used_files);
+                used_files.insert(file.clone());
+                if file != entry.file {
+                    replaced_file = Some(entry.file.clone());
+                }
+                file
+            }
+            Some(entry) => {
+                let existing = output_dir.join(&entry.file);
+                if entry.content_hash == crawl_page_hash(result, format)
+                    && tokio::fs::metadata(&existing).await.is_ok()
+                {
+                    let mut entry = (*entry).clone();
+                    entry.title = result.metadata.title.clone();
+                    entry.status_code = result.metadata.status_code;
+                    entry.metadata = Some(result.metadata.clone());
+                    manifest.entries.push(entry);
+                    unchanged.push(result.url.clone());
+                    continue;
+                }
+                if is_shared(&entry.file) {
+                    // Move the page to a file of its own rather than
+                    // overwrite the other pages of the bundle
+                    let file = unique_filename(saver, &result.url, &used_files);
+                    used_files.insert(file.clone());
+                    replaced_file = Some(entry.file.clone());
+                    file
+                } else {
+                    entry.file.clone()
+                }
+            }
+            None => {
+                let file = unique_filename(saver, &result.url, &used_files);
+                used_files.insert(file.clone());
+                file
+            }
+        };
+
+        let file_path = output_dir.join(&relative_file);
+        let written = match saver.render_crawl_page(result, url) {
+            Ok(content) => saver.write_file(&file_path, &content).await,
+            Err(e) => Err(e),
+        };
+        match written {
+            Ok(()) => {}
+            Err(e) if options.fail_fast => return Err(e),
+            Err(e) => {
+                failures.push(PageFailure::new(&result.url, e));
+                if let Some(entry) = previous_entry {
+                    manifest.entries.push((*entry).clone());
+                }
+                continue;
+            }
+        }
+
+        replaced_files.extend(replaced_file);
+
+        if previous_entry.is_some() {
+            changed.push(result.url.clone());
+        } else {
+            added.push(result.url.clone());
+        }
+        manifest
+            .entries
+            .push(ManifestEntry::for_crawl_page(result, format, &file_path, output_dir).await?);
+        written_files.push(file_path);
+    }
+
+    let mut removed_entries: Vec<&ManifestEntry> = previous_entries
+        .values()
+        .filter(|entry| !seen.contains(entry.url.as_str()))
+        .copied()
+        .collect();
+    removed_entries.sort_by(|a, b| a.url.cmp(&b.url));
+
+    // Only files no page of the new manifest uses are removed or archived
+    let kept: HashSet<&Path> = manifest
+        .entries
+        .iter()
+        .map(|entry| entry.file.as_path())
+        .collect();
+    let mut released = HashSet::new();
+    for file in &replaced_files {
+        let path = output_dir.join(file);
+        if !kept.contains(file.as_path())
+            && released.insert(file.as_path())
+            && tokio::fs::metadata(&path).await.is_ok()
+        {
+            tokio::fs::remove_file(&path).await?;
+        }
+    }
+    handle_removed_pages(&removed_entries, options.removed, output_dir, &kept).await?;
+
+    manifest.sort_entries();
+    Ok(SyncReport {
+        added,
+        changed,
+        unchanged,
+        removed: removed_entries.iter().map(|e| e.url.clone()).collect(),
+        written_files,
+        failures,
+        manifest,
+    })
+}
+
+/// Apply the removed-page policy to pages the crawl no longer returned,
+/// leaving alone the files of `kept` pages
+async fn handle_removed_pages(
+    removed: &[&ManifestEntry],
+    policy: RemovedPagePolicy,
+    output_dir: &Path,
+    kept: &HashSet<&Path>,
+) -> StorageResult<()> {
+    let archive_dir = output_dir
+        .join(ARCHIVE_DIR_NAME)
+        .join(chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string());
+
+    let mut handled = HashSet::new();
+    for entry in removed {
+        let path = output_dir.join(&entry.file);
+        if kept.contains(entry.file.as_path())
+            || !handled.insert(entry.file.as_path())
+            || tokio::fs::metadata(&path).await.is_err()
+        {
+            continue;
+        }
+
+        match policy {
+            RemovedPagePolicy::Keep => {}
+            RemovedPagePolicy::Delete => tokio::fs::remove_file(&path).await?,
+            RemovedPagePolicy::Archive => {
+                let target = archive_dir.join(&entry.file);
+                if let Some(parent) = target.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::rename(&path, &target).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Pick a stable filename for a new page that no other URL already owns
+fn unique_filename(saver: &dyn ContentSaver, url: &str, used: &HashSet<PathBuf>) -> PathBuf {
+    let mut candidate = PathBuf::from(saver.generate_filename(url, None));
+    let mut counter = 1;
+    while used.contains(&candidate) {
+        candidate = PathBuf::from(saver.generate_filename(url, Some(counter)));
+        counter += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::crawl_model::CrawlMetadata;
+    use crate::cli::OutputFormat;
+    use crate::storage::{ContentRepository, FileSystemRepository};
+    use tempfile::tempdir;
+
+    fn crawl_response(url: &str, markdown: &str) -> CrawlResponse {
+        CrawlResponse {
+            id: "1".to_string(),
+            url: url.to_string(),
+            status: "completed".to_string(),
+            completed_at: None,
+            markdown: Some(markdown.to_string()),
+            html: None,
+            metadata: CrawlMetadata {
+                keywords: None,
+                robots: None,
+                og_image: None,
+                author: None,
+                published_date: None,
+                modified_date: None,
+                site_name: None,
+                title: None,
+                status_code: Some(200),
+            },
+        }
+    }
+
+    async fn sync(
+        repository: &FileSystemRepository,
+        results: &[CrawlResponse],
+        output_dir: &Path,
+        format: OutputFormat,
+        removed: RemovedPagePolicy,
+    ) -> SyncReport {
+        repository
+            .sync_crawl_results(
+                results,
+                "https://example.com",
+                None,
+                ManifestConfig {
+                    output_format: format,
+                    options: None,
+                },
+                output_dir,
+                &SyncOptions {
+                    removed,
+                    write_index: false,
+                    fail_fast: false,
+                },
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sync_only_rewrites_changed_pages() {
+        let dir = tempdir().unwrap();
+        let repository = FileSystemRepository::new(dir.path().to_path_buf());
+
+        let first = vec![
+            crawl_response("https://example.com/a", "A"),
+            crawl_response("https://example.com/b", "B"),
+            crawl_response("https://example.com/c", "C"),
+        ];
+        let report = sync(
+            &repository,
+            &first,
+            dir.path(),
+            OutputFormat::Markdown,
+            RemovedPagePolicy::Keep,
+        )
+        .await;
+        assert_eq!(report.added.len(), 3);
+        assert!(dir.path().join(crate::storage::MANIFEST_FILE_NAME).exists());
+
+        let file_a = dir.path().join(&report.manifest.entries[0].file);
+        let file_c = dir.path().join(&report.manifest.entries[2].file);
+        let before_a = tokio::fs::read_to_string(&file_a).await.unwrap();
+
+        let second = vec![
+            crawl_response("https://example.com/a", "A"),
+            crawl_response("https://example.com/b", "B changed"),
+            crawl_response("https://example.com/d", "D"),
+        ];
+        let report = sync(
+            &repository,
+            &second,
+            dir.path(),
+            OutputFormat::Markdown,
+            RemovedPagePolicy::Archive,
+        )
+        .await;
+
+        assert_eq!(report.unchanged, vec!["https://example.com/a".to_string()]);
+        assert_eq!(report.changed, vec!["https://example.com/b".to_string()]);
+        assert_eq!(report.added, vec!["https://example.com/d".to_string()]);
+        assert_eq!(report.removed, vec!["https://example.com/c".to_string()]);
+        assert_eq!(report.to_string(), "1 added, 1 changed, 1 unchanged, 1 removed");
+
+        // Unchanged pages keep their file (and its timestamp header) untouched
+        assert_eq!(tokio::fs::read_to_string(&file_a).await.unwrap(), before_a);
+        assert!(!file_c.exists());
+        assert!(dir.path().join(ARCHIVE_DIR_NAME).exists());
+        assert_eq!(report.manifest.entries.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_format_change_rewrites_every_page() {
+        let dir = tempdir().unwrap();
+        let repository = FileSystemRepository::new(dir.path().to_path_buf());
+        let mut page = crawl_response("https://example.com/a", "A");
+        page.html = Some("<p>A</p>".to_string());
+        let pages = vec![page];
+
+        let first = sync(
+            &repository,
+            &pages,
+            dir.path(),
+            OutputFormat::Markdown,
+            RemovedPagePolicy::Keep,
+        )
+        .await;
+        let markdown_file = dir.path().join(&first.manifest.entries[0].file);
+
+        let second = sync(
+            &repository,
+            &pages,
+            dir.path(),
+            OutputFormat::Html,
+            RemovedPagePolicy::Keep,
+        )
+        .await;
+
+        assert_eq!(second.changed, vec!["https://example.com/a".to_string()]);
+        assert!(second.unchanged.is_empty());
+        let entry = &second.manifest.entries[0];
+        assert_eq!(entry.format, OutputFormat::Html);
+        assert_eq!(entry.file.extension().unwrap(), "html");
+        assert!(dir.path().join(&entry.file).exists());
+        assert!(!markdown_file.exists());
+    }
+
+    #[tokio::test]
+    async fn test_sync_over_a_json_bundle() {
+        let dir = tempdir().unwrap();
+        let repository = FileSystemRepository::new(dir.path().to_path_buf());
+
+        // A plain JSON run writes every page into one bundle file
+        let first = vec![
+            crawl_response("https://example.com/a", "A"),
+            crawl_response("https://example.com/b", "B"),
+            crawl_response("https://example.com/c", "C"),
+        ];
+        let run = crate::storage::save_crawl_run(
+            &repository,
+            &first,
+            "https://example.com",
+            None,
+            ManifestConfig {
+                output_format: OutputFormat::Json,
+                options: None,
+            },
+            dir.path(),
+            &crate::storage::CrawlRunOptions::default(),
+        )
+        .await
+        .unwrap();
+        let bundle = run.manifest.entries[0].file.clone();
+        assert!(run.manifest.entries.iter().all(|entry| entry.file == bundle));
+
+        let second = vec![
+            crawl_response("https://example.com/a", "A"),
+            crawl_response("https://example.com/b", "B2"),
+        ];
+        let report = sync(
+            &repository,
+            &second,
+            dir.path(),
+            OutputFormat::Json,
+            RemovedPagePolicy::Delete,
+        )
+        .await;
+
+        assert_eq!(report.changed, vec!["https://example.com/b".to_string()]);
+        assert_eq!(report.removed, vec!["https://example.com/c".to_string()]);
+        assert_eq!(report.manifest.entries[0].file, bundle);
+        let file_b = &report.manifest.entries[1].file;
+        assert_ne!(file_b, &bundle);
+        // The bundle still holds the unchanged page, so it is neither
+        // overwritten nor deleted
+        let bundled = tokio::fs::read_to_string(dir.path().join(&bundle))
+            .await
+            .unwrap();
+        assert!(bundled.contains("https://example.com/a"));
+        assert!(bundled.contains("https://example.com/c"));
+        let saved_b = tokio::fs::read_to_string(dir.path().join(file_b))
+            .await
+            .unwrap();
+        assert!(saved_b.contains("B2"));
+
+        // Once no page uses the bundle it goes away
+        let third = sync(
+            &repository,
+            &second,
+            dir.path(),
+            OutputFormat::Markdown,
+            RemovedPagePolicy::Delete,
+        )
+        .await;
+        assert_eq!(third.changed.len(), 2);
+        assert!(!dir.path().join(&bundle).exists());
+        assert!(!dir.path().join(file_b).exists());
+    }
+}
@@ -1,21 +1,63 @@
-use crate::api::{FirecrawlClient, ScrapeData};
+use crate::api::models::scrape_model::{Location, ProxyType, ScrapeOptions, ScrapeRequest};
+use crate::api::{CrawlRequest, CrawlResponse, FirecrawlClient, ScrapeData};
+use crate::cli::OutputFormat;
+use crate::config::AppConfig;
+use crate::errors::{FirecrawlError, FirecrawlResult};
+use crate::services::{
+    CrawlMonitorService, CrawlProgress, DefaultProgressService, ProgressService, TaskId,
+    TaskStatistics,
+};
+use crate::storage::FileSystemRepository;
+use crate::tui::dashboard::{Dashboard, Tab};
+use crate::tui::export::{self, SaveForm, SaveScope, SavedResults};
+use crate::tui::filter::{self, SortKey, StatusFilter, TaskFilter};
+use crate::tui::form::OptionsForm;
+use crate::tui::keymap::Keymap;
+use crate::tui::logger::{self, LogBuffer};
+use crate::tui::mouse::{PaneLayout, Split, SplitSizes};
+use crate::tui::session::{self, SESSION_VERSION, Session};
+use crate::tui::sitemap::SiteMap;
+use crate::tui::theme::Theme;
 use anyhow::Result;
-use std::collections::VecDeque;
+use chrono::{DateTime, Utc};
+use log::LevelFilter;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Mode {
     Normal,
     Input,
+    EditOptions,
+    Save,
+    Filter,
+    SiteMap,
     Processing,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Operation {
     Scrape,
     Crawl,
 }
 
-#[derive(Debug, Clone)]
+impl Operation {
+    // Task type reported to the progress service
+    pub fn task_type(self) -> &'static str {
+        match self {
+            Operation::Scrape => "scrape",
+            Operation::Crawl => "crawl",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Task {
     pub id: usize,
     pub operation: Operation,
@@ -23,20 +65,265 @@ pub struct Task {
     pub status: TaskStatus,
     pub progress: String,
     pub result: Option<TaskResult>,
+    pub options: TaskOptions,
+    #[serde(skip)]
+    pub crawl_stats: Option<CrawlStats>,
+    // Bumped whenever a worker starts or is cancelled; updates stamped with
+    // an earlier run are stale
+    #[serde(skip)]
+    pub run: u64,
+    #[serde(default)]
+    pub job_id: Option<String>, // Remote crawl job, once started
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Task {
+    // Time spent running, up to `now` while still running
+    pub fn duration(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        let started_at = self.started_at?;
+        Some(self.finished_at.unwrap_or(now) - started_at)
+    }
+}
+
+// Request options of a task, edited in the option form before it runs.
+// Crawl-only options are ignored by scrape tasks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOptions {
+    pub formats: Vec<OutputFormat>,
+    pub only_main_content: bool,
+    pub include_tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub limit: u32,
+    pub include_paths: Vec<String>,
+    pub exclude_paths: Vec<String>,
+    pub wait_for: Option<u64>,
+    pub mobile: bool,
+    pub proxy: Option<ProxyType>,
+    pub location: Option<Location>,
+}
+
+impl Default for TaskOptions {
+    fn default() -> Self {
+        Self {
+            formats: vec![OutputFormat::Markdown, OutputFormat::RawHtml, OutputFormat::Html],
+            only_main_content: true,
+            include_tags: Vec::new(),
+            exclude_tags: Vec::new(),
+            limit: DEFAULT_CRAWL_LIMIT,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            wait_for: None,
+            mobile: false,
+            proxy: None,
+            location: None,
+        }
+    }
+}
+
+impl TaskOptions {
+    // Per-page options shared by scrape and crawl requests
+    pub fn scrape_options(&self) -> ScrapeOptions {
+        ScrapeOptions {
+            only_main_content: Some(self.only_main_content),
+            include_tags: non_empty(&self.include_tags),
+            exclude_tags: non_empty(&self.exclude_tags),
+            formats: Some(self.formats.clone()),
+            wait_for: self.wait_for,
+            mobile: self.mobile.then_some(true),
+            proxy: self.proxy.clone(),
+            location: self.location.clone(),
+        }
+    }
+
+    pub fn scrape_request(&self, url: &str) -> ScrapeRequest {
+        let mut builder = ScrapeRequest::builder()
+            .url(url)
+            .formats(self.formats.clone())
+            .only_main_content(self.only_main_content)
+            .mobile(self.mobile);
+        if !self.include_tags.is_empty() {
+            builder = builder.include_tags(self.include_tags.clone());
+        }
+        if !self.exclude_tags.is_empty() {
+            builder = builder.exclude_tags(self.exclude_tags.clone());
+        }
+        if let Some(wait_for) = self.wait_for {
+            builder = builder.wait_for(wait_for);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        if let Some(location) = &self.location {
+            builder = builder.location(location.clone());
+        }
+        builder.build()
+    }
+
+    pub fn crawl_request(&self, url: &str) -> Result<CrawlRequest, String> {
+        CrawlRequest::builder()
+            .url(url.to_string())
+            .limit(Some(self.limit))
+            .include_paths(non_empty(&self.include_paths))
+            .exclude_paths(non_empty(&self.exclude_paths))
+            .scrape_options(Some(self.scrape_options()))
+            .build()
+    }
 }
 
+fn non_empty(values: &[String]) -> Option<Vec<String>> {
+    (!values.is_empty()).then(|| values.to_vec())
+}
+
+// Crawl page limit used when the configuration does not set one
+pub const DEFAULT_CRAWL_LIMIT: u32 = 10;
+
+// Step used when adjusting a task's crawl limit from the keyboard
+pub const CRAWL_LIMIT_STEP: u32 = 5;
+
+// Live statistics of a running crawl
 #[derive(Debug, Clone)]
+pub struct CrawlStats {
+    pub completed: u32,
+    pub total: u32,
+    pub started_at: Instant,
+    pub pages: Vec<String>,
+}
+
+impl Default for CrawlStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrawlStats {
+    pub fn new() -> Self {
+        Self {
+            completed: 0,
+            total: 0,
+            started_at: Instant::now(),
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, progress: CrawlProgress) {
+        self.completed = progress.completed;
+        self.total = progress.total.max(progress.completed);
+        self.pages.extend(progress.new_urls);
+    }
+
+    // Fraction of the crawl that is done, between 0 and 1
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed as f64 / self.total as f64).clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn pages_per_minute(&self) -> f64 {
+        self.pages_per_minute_after(self.started_at.elapsed())
+    }
+
+    // Estimated time until every page is crawled, once the rate is known
+    pub fn eta(&self) -> Option<Duration> {
+        self.eta_after(self.started_at.elapsed())
+    }
+
+    fn pages_per_minute_after(&self, elapsed: Duration) -> f64 {
+        let minutes = elapsed.as_secs_f64() / 60.0;
+        if minutes <= 0.0 {
+            0.0
+        } else {
+            self.completed as f64 / minutes
+        }
+    }
+
+    fn eta_after(&self, elapsed: Duration) -> Option<Duration> {
+        let rate = self.pages_per_minute_after(elapsed);
+        if rate <= 0.0 || self.total == 0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.completed) as f64;
+        Some(Duration::from_secs_f64(remaining / rate * 60.0))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TaskStatus {
     Pending,
     Processing,
     Completed,
     Failed(String),
+    Cancelled,
+    // Was running when the previous session ended; can be resumed
+    Interrupted,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TaskResult {
-    Scrape(ScrapeData),
-    Crawl(Vec<ScrapeData>),
+    Scrape(Box<ScrapeData>),
+    Crawl(Vec<CrawlResponse>),
+}
+
+// Content shown in the result preview pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PreviewView {
+    #[default]
+    Markdown,
+    Html,
+    Links,
+    Metadata,
+}
+
+impl PreviewView {
+    pub fn next(self) -> Self {
+        match self {
+            PreviewView::Markdown => PreviewView::Html,
+            PreviewView::Html => PreviewView::Links,
+            PreviewView::Links => PreviewView::Metadata,
+            PreviewView::Metadata => PreviewView::Markdown,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            PreviewView::Markdown => "Markdown",
+            PreviewView::Html => "HTML",
+            PreviewView::Links => "Links",
+            PreviewView::Metadata => "Metadata",
+        }
+    }
+}
+
+// Lines scrolled by PageUp/PageDown in the preview pane
+pub const PREVIEW_PAGE_SIZE: usize = 10;
+
+// How long a toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+// Short-lived notification shown over the UI
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub is_error: bool,
+    pub shown_at: Instant,
+}
+
+// Messages sent by background workers and applied to the app on the next tick.
+// Task updates carry the run of the worker that sent them.
+#[derive(Debug)]
+pub enum TaskUpdate {
+    Status { id: usize, run: u64, status: TaskStatus },
+    Progress { id: usize, run: u64, progress: String },
+    CrawlStarted { id: usize, run: u64, job_id: String },
+    CrawlProgress { id: usize, run: u64, progress: CrawlProgress },
+    Result { id: usize, run: u64, result: TaskResult },
+    Saved(Result<SavedResults, String>),
+    Statistics(TaskStatistics),
 }
 
 pub struct App {
@@ -44,108 +331,892 @@ pub struct App {
     pub tasks: VecDeque<Task>,
     pub current_task_id: usize,
     pub input: String,
+    pub input_operation: Operation,
+    pub options_form: Option<OptionsForm>,
+    pub save_form: Option<SaveForm>,
+    pub site_map: Option<SiteMap>,
+    pub toast: Option<Toast>,
     pub selected_task: usize,
+    // Index of the first task row on screen, kept between frames
+    pub list_offset: usize,
+    pub filter: TaskFilter,
+    pub sort: SortKey,
+    pub sort_descending: bool,
     pub scroll_offset: usize,
+    pub preview_view: PreviewView,
+    pub tab: Tab,
+    pub dashboard: Dashboard,
     pub client: FirecrawlClient,
+    // Workers report task starts and outcomes here for the dashboard
+    pub progress: Arc<dyn ProgressService + Send + Sync>,
+    pub config: AppConfig,
+    pub status_message: Option<String>,
+    // Ids of tasks waiting for a free worker slot, in start order
+    pub queue: VecDeque<usize>,
+    // Cancellation token of every running worker
+    workers: HashMap<usize, CancellationToken>,
+    // Session file the task list is saved to on quit
+    pub session_path: Option<PathBuf>,
+    pub logs: LogBuffer,
+    pub theme: Theme,
+    pub keymap: Keymap,
+    pub show_help: bool,
+    pub show_logs: bool,
+    pub log_filter: LevelFilter,
+    // Lines the log panel is scrolled back from its newest record
+    pub log_scroll: usize,
+    pub splits: SplitSizes,
+    pub dragging: Option<Split>,
+    // Pane areas of the last frame, set by the renderer
+    pub layout: PaneLayout,
+    update_sender: UnboundedSender<TaskUpdate>,
+    update_receiver: UnboundedReceiver<TaskUpdate>,
 }
 
 impl App {
     pub fn new(client: FirecrawlClient) -> Self {
+        Self::with_config(client, AppConfig::default())
+    }
+
+    pub fn with_config(client: FirecrawlClient, config: AppConfig) -> Self {
+        let (update_sender, update_receiver) = mpsc::unbounded_channel();
+        let logs = LogBuffer::new(config.ui.tui.max_log_lines);
+        // An invalid theme or keymap is reported by `from_config`; fall back quietly here
+        let theme = Theme::from_config(&config.ui).unwrap_or_default();
+        let keymap = Keymap::from_config(&config.ui.tui.keybindings).unwrap_or_default();
+        let show_help = config.ui.tui.show_help_by_default;
         Self {
             mode: Mode::Normal,
             tasks: VecDeque::new(),
             current_task_id: 0,
             input: String::new(),
+            input_operation: Operation::Scrape,
+            options_form: None,
+            save_form: None,
+            site_map: None,
+            toast: None,
             selected_task: 0,
+            list_offset: 0,
+            filter: TaskFilter::default(),
+            sort: SortKey::default(),
+            sort_descending: false,
             scroll_offset: 0,
+            preview_view: PreviewView::default(),
+            tab: Tab::default(),
+            dashboard: Dashboard::new(Instant::now()),
             client,
+            progress: DefaultProgressService::new_arc(),
+            config,
+            status_message: None,
+            queue: VecDeque::new(),
+            workers: HashMap::new(),
+            session_path: None,
+            logs,
+            theme,
+            keymap,
+            show_help,
+            show_logs: false,
+            log_filter: LevelFilter::Info,
+            log_scroll: 0,
+            splits: SplitSizes::default(),
+            dragging: None,
+            layout: PaneLayout::default(),
+            update_sender,
+            update_receiver,
+        }
+    }
+
+    // Build the app and its API client from the given configuration
+    pub fn from_config(config: AppConfig) -> FirecrawlResult<Self> {
+        Theme::from_config(&config.ui).map_err(FirecrawlError::ConfigurationError)?;
+        Keymap::from_config(&config.ui.tui.keybindings)
+            .map_err(FirecrawlError::ConfigurationError)?;
+        let client = FirecrawlClient::new(&config.api.base_url, config.api.api_key.as_deref())
+            .map_err(|e| FirecrawlError::ConfigurationError(e.to_string()))?;
+        Ok(Self::with_config(client, config))
+    }
+
+    fn default_crawl_limit(&self) -> u32 {
+        self.config
+            .execution
+            .default_crawl_limit
+            .unwrap_or(DEFAULT_CRAWL_LIMIT)
+    }
+
+    // Raise or lower the page limit of the selected crawl task before it runs
+    pub fn adjust_crawl_limit(&mut self, increase: bool) {
+        if let Some(task) = self.selected_index().and_then(|index| self.tasks.get_mut(index))
+            && matches!(task.operation, Operation::Crawl)
+            && matches!(task.status, TaskStatus::Pending)
+        {
+            task.options.limit = if increase {
+                task.options.limit.saturating_add(CRAWL_LIMIT_STEP)
+            } else {
+                task.options.limit.saturating_sub(CRAWL_LIMIT_STEP).max(1)
+            };
         }
     }
 
     pub fn add_scrape_task(&mut self, url: String) {
-        let task = Task {
-            id: self.current_task_id,
-            operation: Operation::Scrape,
-            url: url.clone(),
-            status: TaskStatus::Pending,
-            progress: "Pending".to_string(),
-            result: None,
-        };
-        self.tasks.push_back(task);
-        self.current_task_id += 1;
+        self.add_task(Operation::Scrape, url);
     }
 
     pub fn add_crawl_task(&mut self, url: String) {
+        self.add_task(Operation::Crawl, url);
+    }
+
+    fn add_task(&mut self, operation: Operation, url: String) {
         let task = Task {
             id: self.current_task_id,
-            operation: Operation::Crawl,
-            url: url.clone(),
+            operation,
+            url,
             status: TaskStatus::Pending,
             progress: "Pending".to_string(),
             result: None,
+            options: TaskOptions {
+                limit: self.default_crawl_limit(),
+                ..TaskOptions::default()
+            },
+            crawl_stats: None,
+            run: 0,
+            job_id: None,
+            started_at: None,
+            finished_at: None,
         };
         self.tasks.push_back(task);
         self.current_task_id += 1;
     }
 
-    pub async fn process_next_task(&mut self) -> Result<()> {
-        if let Some(task) = self.tasks.get_mut(self.selected_task) {
-            if matches!(task.status, TaskStatus::Pending) {
-                task.status = TaskStatus::Processing;
-                task.progress = "Processing...".to_string();
+    // Start typing the URL of a new task of the given operation
+    pub fn begin_input(&mut self, operation: Operation) {
+        self.mode = Mode::Input;
+        self.input_operation = operation;
+        self.input.clear();
+    }
+
+    // Switch the operation of the task being entered between scrape and crawl
+    pub fn toggle_input_operation(&mut self) {
+        self.input_operation = match self.input_operation {
+            Operation::Scrape => Operation::Crawl,
+            Operation::Crawl => Operation::Scrape,
+        };
+    }
 
-                let result: Result<TaskResult, anyhow::Error> = match task.operation {
-                    Operation::Scrape => {
-                        let scrape_result = self.client.scrape(&task.url).await?;
-                        Ok(TaskResult::Scrape(scrape_result))
-                    }
-                    Operation::Crawl => {
-                        let crawl_result = self.client.crawl(&task.url, Some(10)).await?;
-                        Ok(TaskResult::Crawl(crawl_result))
+    // Add the entered URL as a task of the chosen operation
+    pub fn submit_input(&mut self) {
+        let url = self.input.trim().to_string();
+        if url.is_empty() {
+            return;
+        }
+        self.add_task(self.input_operation, url);
+        self.input.clear();
+        self.mode = Mode::Normal;
+    }
+
+    pub fn cancel_input(&mut self) {
+        self.input.clear();
+        self.mode = Mode::Normal;
+    }
+
+    // Explore the pages of the selected crawl as a tree grouped by host and path
+    pub fn open_site_map(&mut self) {
+        let Some(task) = self.selected() else {
+            return;
+        };
+        let Some(TaskResult::Crawl(pages)) = &task.result else {
+            self.status_message = Some("Select a completed crawl to open its site map".to_string());
+            return;
+        };
+        self.site_map = Some(SiteMap::new(task.id, pages));
+        self.scroll_offset = 0;
+        self.tab = Tab::Tasks;
+        self.mode = Mode::SiteMap;
+    }
+
+    pub fn close_site_map(&mut self) {
+        self.site_map = None;
+        self.scroll_offset = 0;
+        self.mode = Mode::Normal;
+    }
+
+    // Add a task rooted at the selected site map node: a scrape of its URL,
+    // or a crawl from it limited to the paths below it
+    pub fn add_site_map_task(&mut self, operation: Operation) {
+        let Some(node) = self.site_map.as_ref().and_then(|map| map.selected_node()) else {
+            return;
+        };
+        let (url, path) = (node.url.clone(), node.path.clone());
+        self.add_task(operation, url.clone());
+        let Some(task) = self.tasks.back_mut() else {
+            return;
+        };
+        if operation == Operation::Crawl && !path.is_empty() {
+            task.options.include_paths = vec![format!("^{}(/.*)?$", regex::escape(&path))];
+        }
+        let message = format!("Added {} task {} for {}", operation.task_type(), task.id, url);
+        log::info!("{}", message);
+        self.show_toast(message, false);
+    }
+
+    // Open the option form for the selected task; options can only change
+    // before the task runs
+    pub fn open_options_form(&mut self) {
+        let Some(task) = self.selected() else {
+            return;
+        };
+        if !matches!(task.status, TaskStatus::Pending) {
+            self.status_message = Some("Only pending tasks can be edited".to_string());
+            return;
+        }
+        self.options_form = Some(OptionsForm::new(task));
+        self.mode = Mode::EditOptions;
+    }
+
+    // Validate the form and store its options on the task. The form stays
+    // open, showing the field errors, when validation fails.
+    pub fn save_options_form(&mut self) {
+        let Some(form) = self.options_form.as_mut() else {
+            return;
+        };
+        let Some(task) = self.tasks.iter_mut().find(|task| task.id == form.task_id) else {
+            self.close_options_form();
+            return;
+        };
+        if let Some(options) = form.validate(&task.options) {
+            task.options = options;
+            self.close_options_form();
+        }
+    }
+
+    pub fn close_options_form(&mut self) {
+        self.options_form = None;
+        self.mode = Mode::Normal;
+    }
+
+    fn max_concurrent_tasks(&self) -> usize {
+        self.config.execution.max_concurrent_tasks.max(1)
+    }
+
+    fn task_index(&self, id: usize) -> Option<usize> {
+        self.tasks.iter().position(|task| task.id == id)
+    }
+
+    // Queue the selected task; it starts as soon as a worker slot is free
+    pub fn start_selected_task(&mut self) {
+        if let Some(task) = self.selected() {
+            self.queue_task(task.id);
+            self.start_queued_tasks();
+        }
+    }
+
+    // Queue every pending task, in list order
+    pub fn run_all_pending(&mut self) {
+        let pending: Vec<usize> = self
+            .tasks
+            .iter()
+            .filter(|task| matches!(task.status, TaskStatus::Pending))
+            .map(|task| task.id)
+            .collect();
+        for id in pending {
+            self.queue_task(id);
+        }
+        self.start_queued_tasks();
+    }
+
+    fn queue_task(&mut self, id: usize) {
+        let Some(index) = self.task_index(id) else {
+            return;
+        };
+        let task = &mut self.tasks[index];
+        if matches!(task.status, TaskStatus::Pending) && !self.queue.contains(&id) {
+            task.progress = "Queued".to_string();
+            self.queue.push_back(id);
+        }
+    }
+
+    // Start queued tasks while fewer than `max_concurrent_tasks` are running
+    pub fn start_queued_tasks(&mut self) {
+        while self.running_task_count() < self.max_concurrent_tasks() {
+            let Some(id) = self.queue.pop_front() else {
+                break;
+            };
+            if let Some(index) = self.task_index(id) {
+                self.start_task(index);
+            }
+        }
+    }
+
+    // Stop the selected task. A running task's worker is cancelled and, for a
+    // crawl, cancels the remote job once it knows its id; a queued task leaves
+    // the queue.
+    pub fn cancel_selected_task(&mut self) {
+        let Some(task) = self.selected_index().and_then(|index| self.tasks.get_mut(index)) else {
+            return;
+        };
+        let id = task.id;
+        match task.status {
+            TaskStatus::Pending => {
+                if let Some(position) = self.queue.iter().position(|queued| *queued == id) {
+                    self.queue.remove(position);
+                    task.progress = "Pending".to_string();
+                }
+            }
+            TaskStatus::Processing => {
+                if let Some(worker) = self.workers.remove(&id) {
+                    worker.cancel();
+                }
+                task.status = TaskStatus::Cancelled;
+                task.progress = "Cancelled".to_string();
+                task.run += 1;
+                task.finished_at = Some(Utc::now());
+                log::warn!("Task {} cancelled", id);
+                self.start_queued_tasks();
+            }
+            _ => {}
+        }
+    }
+
+    // Reset a failed, cancelled or interrupted task and queue it again. An
+    // interrupted crawl keeps its job id and reattaches to the running job.
+    pub fn retry_selected_task(&mut self) {
+        if let Some(task) = self.selected() {
+            self.resume_task(task.id);
+        }
+    }
+
+    fn resume_task(&mut self, id: usize) {
+        let Some(index) = self.task_index(id) else {
+            return;
+        };
+        let task = &mut self.tasks[index];
+        match task.status {
+            TaskStatus::Failed(_) | TaskStatus::Cancelled => {
+                task.job_id = None;
+                task.started_at = None;
+            }
+            TaskStatus::Interrupted => {}
+            _ => return,
+        }
+        task.status = TaskStatus::Pending;
+        task.progress = "Pending".to_string();
+        task.result = None;
+        task.crawl_stats = None;
+        self.queue_task(id);
+        self.start_queued_tasks();
+    }
+
+    // Remove the selected task, cancelling it first if it is running
+    pub fn delete_selected_task(&mut self) {
+        if self.selected_index().is_none() {
+            return;
+        }
+        self.cancel_selected_task();
+        if let Some(task) = self.tasks.remove(self.selected_task) {
+            self.queue.retain(|id| *id != task.id);
+        }
+        self.selected_task = self.selected_task.min(self.tasks.len().saturating_sub(1));
+        self.scroll_offset = 0;
+        self.sync_selection();
+    }
+
+    // Add a pending copy of the selected task and open its option form
+    pub fn duplicate_selected_task(&mut self) {
+        let Some(task) = self.selected() else {
+            return;
+        };
+        let (operation, url, options) = (task.operation, task.url.clone(), task.options.clone());
+        self.add_task(operation, url);
+        if let Some(copy) = self.tasks.back_mut() {
+            copy.options = options;
+        }
+        self.selected_task = self.tasks.len() - 1;
+        self.scroll_offset = 0;
+        self.open_options_form();
+    }
+
+    // Start a pending task on a background worker; its progress and result
+    // come back through the update channel
+    pub fn start_task(&mut self, index: usize) {
+        let Some(task) = self.tasks.get_mut(index) else {
+            return;
+        };
+        if !matches!(task.status, TaskStatus::Pending) {
+            return;
+        }
+
+        task.status = TaskStatus::Processing;
+        task.progress = "Processing...".to_string();
+        task.run += 1;
+        // A reattached crawl keeps the time it first started
+        task.started_at.get_or_insert_with(Utc::now);
+        task.finished_at = None;
+        log::info!("Task {} started: {:?} {}", task.id, task.operation, task.url);
+        if matches!(task.operation, Operation::Crawl) {
+            task.crawl_stats = Some(CrawlStats::new());
+        }
+
+        let id = task.id;
+        let run = task.run;
+        let operation = task.operation;
+        let url = task.url.clone();
+        let options = task.options.clone();
+        let job_id = task.job_id.clone();
+        let cancellation = CancellationToken::new();
+        let client = self.client.clone().with_cancellation(cancellation.clone());
+        let progress = Arc::clone(&self.progress);
+        let sender = self.update_sender.clone();
+
+        tokio::spawn(async move {
+            let task_type = operation.task_type();
+            let task_id = TaskId::next();
+            progress.notify_task_started(task_id, &url, task_type).await;
+            let _ = sender.send(TaskUpdate::Statistics(progress.get_statistics().await));
+            let task = run_task(&client, operation, &url, &options, job_id, (id, run), &sender);
+            let update = match task.await {
+                // A cancelled task counts as failed, whatever the request
+                // returned. The app already marked it cancelled and may have
+                // restarted it since, so no update is sent for it.
+                _ if client.is_cancelled() => {
+                    let error = FirecrawlError::Cancelled(url.clone());
+                    progress.notify_task_failed(task_id, &url, task_type, &error).await;
+                    None
+                }
+                Ok(result) => {
+                    progress.notify_task_completed(task_id, &url, task_type).await;
+                    Some(TaskUpdate::Result { id, run, result })
+                }
+                Err(e) => {
+                    let error = FirecrawlError::from_client_error(&e);
+                    progress.notify_task_failed(task_id, &url, task_type, &error).await;
+                    Some(TaskUpdate::Status {
+                        id,
+                        run,
+                        status: TaskStatus::Failed(e.to_string()),
+                    })
+                }
+            };
+            // The app may have quit in the meantime
+            let _ = sender.send(TaskUpdate::Statistics(progress.get_statistics().await));
+            if let Some(update) = update {
+                let _ = sender.send(update);
+            }
+        });
+        self.workers.insert(id, cancellation);
+    }
+
+    // Restore the task list saved in `path`, if any, and save back to it on
+    // quit. Tasks that were running are marked interrupted; crawls among them
+    // reattach to their remote jobs right away.
+    pub fn open_session(&mut self, path: PathBuf) -> FirecrawlResult<()> {
+        if let Some(session) = session::load_session(&path)? {
+            self.restore_session(session);
+        }
+        self.session_path = Some(path);
+        Ok(())
+    }
+
+    fn restore_session(&mut self, session: Session) {
+        let mut reattach = Vec::new();
+        for mut task in session.tasks {
+            if matches!(task.status, TaskStatus::Processing) {
+                task.status = TaskStatus::Interrupted;
+                task.progress = "Interrupted - [r] to resume".to_string();
+                if task.operation == Operation::Crawl && task.job_id.is_some() {
+                    reattach.push(task.id);
+                }
+            }
+            self.tasks.push_back(task);
+        }
+        self.current_task_id = self.current_task_id.max(session.next_task_id);
+        for id in reattach {
+            self.resume_task(id);
+        }
+    }
+
+    // Snapshot of the task list for the session file
+    pub fn session(&self) -> Session {
+        Session {
+            version: SESSION_VERSION,
+            saved_at: chrono::Utc::now(),
+            next_task_id: self.current_task_id,
+            tasks: self.tasks.iter().cloned().collect(),
+        }
+    }
+
+    // Write the task list to the session file, if the app has one
+    pub fn save_session(&self) -> FirecrawlResult<()> {
+        if let Some(path) = &self.session_path {
+            session::save_session(path, &self.session())?;
+        }
+        Ok(())
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    // Close the help overlay if open, otherwise clear messages
+    pub fn dismiss(&mut self) {
+        if self.show_help {
+            self.show_help = false;
+        } else {
+            self.status_message = None;
+            self.toast = None;
+        }
+    }
+
+    pub fn next_tab(&mut self) {
+        self.tab = self.tab.next();
+    }
+
+    pub fn toggle_log_panel(&mut self) {
+        self.show_logs = !self.show_logs;
+    }
+
+    // Show more verbose log levels, wrapping back to errors only
+    pub fn cycle_log_filter(&mut self) {
+        self.log_filter = logger::next_level_filter(self.log_filter);
+    }
+
+    pub fn show_toast(&mut self, message: impl Into<String>, is_error: bool) {
+        self.toast = Some(Toast {
+            message: message.into(),
+            is_error,
+            shown_at: Instant::now(),
+        });
+    }
+
+    // Results that a save of the given scope writes, with their task URLs
+    fn results_to_save(&self, scope: SaveScope) -> Vec<(String, TaskResult)> {
+        let tasks: Vec<&Task> = match scope {
+            SaveScope::Selected => self.selected().into_iter().collect(),
+            SaveScope::AllCompleted => self.tasks.iter().collect(),
+        };
+        tasks
+            .into_iter()
+            .filter_map(|task| Some((task.url.clone(), task.result.clone()?)))
+            .collect()
+    }
+
+    // Ask for the format and directory to save results to
+    pub fn open_save_form(&mut self, scope: SaveScope) {
+        if self.results_to_save(scope).is_empty() {
+            self.show_toast("No completed results to save", true);
+            return;
+        }
+        self.save_form = Some(SaveForm::new(
+            scope,
+            self.config.output.default_format,
+            &self.config.get_effective_output_dir(),
+        ));
+        self.mode = Mode::Save;
+    }
+
+    pub fn close_save_form(&mut self) {
+        self.save_form = None;
+        self.mode = Mode::Normal;
+    }
+
+    // Write the chosen results on a background worker; the outcome comes
+    // back as a toast
+    pub fn confirm_save(&mut self) {
+        let Some(form) = self.save_form.take() else {
+            return;
+        };
+        self.mode = Mode::Normal;
+        let directory = form.directory.trim();
+        if directory.is_empty() {
+            self.show_toast("Choose a directory to save to", true);
+            return;
+        }
+
+        let results = self.results_to_save(form.scope);
+        let output_dir = PathBuf::from(directory);
+        let sender = self.update_sender.clone();
+        tokio::spawn(async move {
+            let repository = FileSystemRepository::new(output_dir.clone());
+            let saved = export::save_results(&repository, &results, form.format, &output_dir)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = sender.send(TaskUpdate::Saved(saved));
+        });
+    }
+
+    // OSC 52 sequence copying the selected result's markdown, for the
+    // runner to write to the terminal
+    pub fn copy_selected_markdown(&mut self) -> Option<String> {
+        let markdown = self
+            .selected()
+            .and_then(|task| task.result.as_ref())
+            .and_then(export::result_markdown);
+        match markdown {
+            Some(markdown) => {
+                self.show_toast(
+                    format!("Copied {} characters of markdown to the clipboard", markdown.chars().count()),
+                    false,
+                );
+                Some(export::osc52_copy(&markdown))
+            }
+            None => {
+                self.show_toast("No markdown to copy", true);
+                None
+            }
+        }
+    }
+
+    // Sender that background workers use to report task updates
+    pub fn update_sender(&self) -> UnboundedSender<TaskUpdate> {
+        self.update_sender.clone()
+    }
+
+    // Apply every update received since the last call
+    pub fn apply_updates(&mut self) {
+        while let Ok(update) = self.update_receiver.try_recv() {
+            self.apply_update(update);
+        }
+        self.start_queued_tasks();
+        self.dashboard.sample(Instant::now());
+        // Status changes can hide the selected task from a status filter
+        if self.filter.is_active() {
+            self.sync_selection();
+        }
+
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|toast| toast.shown_at.elapsed() >= TOAST_DURATION)
+        {
+            self.toast = None;
+        }
+    }
+
+    fn apply_update(&mut self, update: TaskUpdate) {
+        let (id, run) = match update {
+            TaskUpdate::Saved(saved) => return self.report_saved(saved),
+            TaskUpdate::Statistics(statistics) => return self.dashboard.update(statistics),
+            TaskUpdate::Status { id, run, .. }
+            | TaskUpdate::Progress { id, run, .. }
+            | TaskUpdate::CrawlStarted { id, run, .. }
+            | TaskUpdate::CrawlProgress { id, run, .. }
+            | TaskUpdate::Result { id, run, .. } => (id, run),
+        };
+        let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) else {
+            return;
+        };
+        // Late updates from a cancelled or earlier worker must not touch the
+        // task, which may have been retried since
+        if run != task.run {
+            return;
+        }
+        if matches!(update, TaskUpdate::Status { .. } | TaskUpdate::Result { .. }) {
+            self.workers.remove(&id);
+        }
+
+        match update {
+            TaskUpdate::Status { status, .. } => {
+                task.progress = match &status {
+                    TaskStatus::Pending => "Pending".to_string(),
+                    TaskStatus::Processing => "Processing...".to_string(),
+                    TaskStatus::Completed => "Completed".to_string(),
+                    TaskStatus::Cancelled => "Cancelled".to_string(),
+                    TaskStatus::Interrupted => "Interrupted".to_string(),
+                    TaskStatus::Failed(error) => {
+                        log::error!("Task {} failed: {}", id, error);
+                        self.status_message = Some(format!("Task {} failed: {}", id, error));
+                        "Failed".to_string()
                     }
                 };
+                if !matches!(status, TaskStatus::Pending | TaskStatus::Processing) {
+                    task.finished_at = Some(Utc::now());
+                }
+                task.status = status;
+            }
+            TaskUpdate::Progress { progress, .. } => {
+                task.progress = progress;
+            }
+            TaskUpdate::CrawlStarted { job_id, .. } => {
+                log::info!("Task {} started crawl job {}", id, job_id);
+                task.job_id = Some(job_id);
+            }
+            TaskUpdate::CrawlProgress { progress, .. } => {
+                for url in &progress.new_urls {
+                    log::debug!("Task {} crawled {}", id, url);
+                }
+                task.progress = format!("Crawling {}/{}", progress.completed, progress.total);
+                task.crawl_stats
+                    .get_or_insert_with(CrawlStats::new)
+                    .update(progress);
+            }
+            TaskUpdate::Result { result, .. } => {
+                log::info!("Task {} completed", id);
+                task.status = TaskStatus::Completed;
+                task.progress = "Completed".to_string();
+                task.result = Some(result);
+                task.finished_at = Some(Utc::now());
+            }
+            TaskUpdate::Saved(_) | TaskUpdate::Statistics(_) => {}
+        }
+    }
 
-                match result {
-                    Ok(task_result) => {
-                        task.status = TaskStatus::Completed;
-                        task.progress = "Completed".to_string();
-                        task.result = Some(task_result);
-                    }
-                    Err(e) => {
-                        task.status = TaskStatus::Failed(e.to_string());
-                        task.progress = "Failed".to_string();
-                    }
+    // Confirm the files a save wrote, or why it failed
+    fn report_saved(&mut self, saved: Result<SavedResults, String>) {
+        match saved {
+            Ok(saved) => {
+                for path in &saved.files {
+                    log::info!("Saved {}", path.display());
+                }
+                for failure in &saved.failures {
+                    log::error!("Could not save {}: {}", failure.url, failure.reason);
+                }
+                let listed: Vec<String> =
+                    saved.files.iter().map(|path| path.display().to_string()).collect();
+                let mut message = format!("Saved {} file(s):\n{}", saved.files.len(), listed.join("\n"));
+                if let Some(failures_file) = &saved.failures_file {
+                    message.push_str(&format!(
+                        "\n{} page(s) could not be saved, see {}",
+                        saved.failures.len(),
+                        failures_file.display()
+                    ));
                 }
+                self.show_toast(message, saved.failures_file.is_some());
+            }
+            Err(e) => {
+                log::error!("Save failed: {}", e);
+                self.show_toast(format!("Save failed: {}", e), true);
             }
         }
-        Ok(())
+    }
+
+    // Number of tasks currently running in the background
+    pub fn running_task_count(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| matches!(task.status, TaskStatus::Processing))
+            .count()
+    }
+
+    // Indices into `tasks` of the rows the list shows, in display order
+    pub fn visible_tasks(&self) -> Vec<usize> {
+        filter::visible_tasks(
+            &self.tasks,
+            &self.filter,
+            self.sort,
+            self.sort_descending,
+            Utc::now(),
+        )
+    }
+
+    // Select the task at `index` in `tasks`, showing its preview from the top
+    pub fn select(&mut self, index: usize) {
+        if index != self.selected_task {
+            self.selected_task = index;
+            self.scroll_offset = 0;
+        }
+    }
+
+    // Index of the selected task, unless a filter hides it. Task actions
+    // only apply to a task on screen.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.visible_tasks()
+            .contains(&self.selected_task)
+            .then_some(self.selected_task)
+    }
+
+    pub fn selected(&self) -> Option<&Task> {
+        self.tasks.get(self.selected_index()?)
+    }
+
+    // Move the selection onto a shown task when filtering hid it; with none
+    // shown the selection stays hidden and actions do nothing
+    pub fn sync_selection(&mut self) {
+        let visible = self.visible_tasks();
+        if !visible.is_empty() && !visible.contains(&self.selected_task) {
+            self.select(visible[0]);
+        }
     }
 
     pub fn select_next_task(&mut self) {
-        if !self.tasks.is_empty() {
-            self.selected_task = (self.selected_task + 1) % self.tasks.len();
+        let visible = self.visible_tasks();
+        if visible.is_empty() {
+            return;
         }
+        let next = match visible.iter().position(|index| *index == self.selected_task) {
+            Some(position) => visible[(position + 1) % visible.len()],
+            None => visible[0],
+        };
+        self.select(next);
     }
 
     pub fn select_previous_task(&mut self) {
-        if !self.tasks.is_empty() {
-            self.selected_task = if self.selected_task == 0 {
-                self.tasks.len() - 1
-            } else {
-                self.selected_task - 1
-            };
+        let visible = self.visible_tasks();
+        if visible.is_empty() {
+            return;
+        }
+        let previous = match visible.iter().position(|index| *index == self.selected_task) {
+            Some(0) | None => visible[visible.len() - 1],
+            Some(position) => visible[position - 1],
+        };
+        self.select(previous);
+    }
+
+    // Start typing a URL filter for the task list; it applies as it is typed
+    pub fn begin_filter(&mut self) {
+        self.mode = Mode::Filter;
+    }
+
+    // Leave the filter prompt, keeping the query or clearing it
+    pub fn close_filter(&mut self, keep: bool) {
+        if !keep {
+            self.filter.clear_query();
         }
+        self.mode = Mode::Normal;
+        self.sync_selection();
+    }
+
+    pub fn cycle_status_filter(&mut self) {
+        self.filter.status = StatusFilter::cycle(self.filter.status);
+        self.sync_selection();
+    }
+
+    pub fn cycle_operation_filter(&mut self) {
+        self.filter.operation = match self.filter.operation {
+            None => Some(Operation::Scrape),
+            Some(Operation::Scrape) => Some(Operation::Crawl),
+            Some(Operation::Crawl) => None,
+        };
+        self.sync_selection();
+    }
+
+    pub fn clear_filters(&mut self) {
+        self.filter = TaskFilter::default();
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort = self.sort.next();
+    }
+
+    pub fn reverse_sort(&mut self) {
+        self.sort_descending = !self.sort_descending;
+    }
+
+    // Switch the preview pane to its next view, starting at the top
+    pub fn cycle_preview_view(&mut self) {
+        self.preview_view = self.preview_view.next();
+        self.scroll_offset = 0;
+    }
+
+    // Scroll the preview pane; the renderer clamps the offset to the content
+    pub fn scroll_preview_down(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_add(lines);
+    }
+
+    pub fn scroll_preview_up(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
     }
 
     pub fn handle_input_char(&mut self, c: char) {
         match c {
-            '\n' => {
-                if !self.input.trim().is_empty() {
-                    self.add_scrape_task(self.input.trim().to_string());
-                    self.input.clear();
-                    self.mode = Mode::Normal;
-                }
-            }
+            '\n' => self.submit_input(),
             '\x08' | '\x7f' => {
                 self.input.pop();
             }
@@ -159,3 +1230,318 @@ impl App {
         &self.client
     }
 }
+
+// Execute a task against the API on a background worker, streaming crawl
+// progress through `sender`. Once the client's cancellation token is
+// cancelled a scrape is dropped, and a crawl cancels its remote job as soon
+// as it has started.
+async fn run_task(
+    client: &FirecrawlClient,
+    operation: Operation,
+    url: &str,
+    options: &TaskOptions,
+    job_id: Option<String>,
+    (id, run): (usize, u64),
+    sender: &UnboundedSender<TaskUpdate>,
+) -> Result<TaskResult> {
+    match operation {
+        Operation::Scrape => {
+            let scrape = client.scrape_with_request(options.scrape_request(url));
+            let scrape_result = tokio::select! {
+                result = scrape => result?,
+                _ = client.cancelled() => return Err(FirecrawlError::Cancelled(url.to_string()).into()),
+            };
+            Ok(TaskResult::Scrape(Box::new(scrape_result)))
+        }
+        Operation::Crawl => {
+            // Reattach to a job started by an earlier session
+            let job_id = match job_id {
+                Some(job_id) => job_id,
+                None => {
+                    let request = options
+                        .crawl_request(url)
+                        .map_err(FirecrawlError::ValidationError)?;
+                    // Not raced against cancellation: the job id is needed to
+                    // cancel a job the server has already started
+                    let start = client.crawl_url(request).await?;
+                    if !client.is_cancelled() {
+                        let _ = sender.send(TaskUpdate::CrawlStarted {
+                            id,
+                            run,
+                            job_id: start.job_id.clone(),
+                        });
+                    }
+                    start.job_id
+                }
+            };
+
+            // A cancelled client cancels the job on its first poll
+            let progress_sender = sender.clone();
+            let crawl_result = client
+                .monitor_crawl_job(
+                    &job_id,
+                    Box::new(move |progress| {
+                        let _ = progress_sender.send(TaskUpdate::CrawlProgress { id, run, progress });
+                    }),
+                )
+                .await?;
+            Ok(TaskResult::Crawl(crawl_result))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app() -> App {
+        App::new(FirecrawlClient::new("http://localhost:3002/v2", None).unwrap())
+    }
+
+    #[test]
+    fn test_apply_updates_from_workers() {
+        let mut app = app();
+        app.add_scrape_task("https://example.com".to_string());
+        app.add_crawl_task("https://example.org".to_string());
+
+        let sender = app.update_sender();
+        sender
+            .send(TaskUpdate::Progress {
+                id: 0,
+                run: 0,
+                progress: "Halfway".to_string(),
+            })
+            .unwrap();
+        sender
+            .send(TaskUpdate::Status {
+                id: 1,
+                run: 0,
+                status: TaskStatus::Failed("boom".to_string()),
+            })
+            .unwrap();
+
+        // Nothing changes until the updates are applied
+        assert_eq!(app.tasks[0].progress, "Pending");
+        app.apply_updates();
+
+        assert_eq!(app.tasks[0].progress, "Halfway");
+        assert!(matches!(app.tasks[1].status, TaskStatus::Failed(ref e) if e == "boom"));
+        assert_eq!(app.status_message.as_deref(), Some("Task 1 failed: boom"));
+    }
+
+    #[test]
+    fn test_actions_ignore_a_task_hidden_by_the_filter() {
+        let mut app = app();
+        app.add_scrape_task("https://example.com".to_string());
+        app.filter.operation = Some(Operation::Crawl);
+        app.sync_selection();
+        assert!(app.selected().is_none());
+
+        app.start_selected_task();
+        app.duplicate_selected_task();
+        app.delete_selected_task();
+        assert_eq!(app.tasks.len(), 1);
+        assert!(matches!(app.tasks[0].status, TaskStatus::Pending));
+        assert!(app.queue.is_empty());
+
+        app.filter.operation = None;
+        assert_eq!(app.selected().map(|task| task.id), Some(0));
+    }
+
+    #[test]
+    fn test_input_adds_task_with_chosen_operation_and_options() {
+        let mut app = app();
+        app.begin_input(Operation::Scrape);
+        for c in "https://acme.co".chars() {
+            app.handle_input_char(c);
+        }
+        app.toggle_input_operation();
+        app.handle_input_char('\n');
+
+        assert_eq!(app.mode, Mode::Normal);
+        let task = &app.tasks[0];
+        assert_eq!(task.operation, Operation::Crawl);
+        assert_eq!(task.url, "https://acme.co");
+
+        let mut options = task.options.clone();
+        options.exclude_paths = vec!["admin/.*".to_string()];
+        options.mobile = true;
+        let request = serde_json::to_value(options.crawl_request(&task.url).unwrap()).unwrap();
+        assert_eq!(request["limit"], 10);
+        assert_eq!(request["excludePaths"][0], "admin/.*");
+        assert_eq!(request["scrapeOptions"]["mobile"], true);
+        assert!(request.get("includePaths").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_all_pending_respects_concurrency_and_cancel() {
+        let mut config = AppConfig::default();
+        config.execution.max_concurrent_tasks = 2;
+        let client = FirecrawlClient::new("http://127.0.0.1:9/v2", None).unwrap();
+        let mut app = App::with_config(client, config);
+        for i in 0..3 {
+            app.add_scrape_task(format!("https://example.com/{}", i));
+        }
+
+        app.run_all_pending();
+        assert_eq!(app.running_task_count(), 2);
+        assert_eq!(app.queue, VecDeque::from([2]));
+        assert_eq!(app.tasks[2].progress, "Queued");
+
+        // Cancelling a running task frees its slot for the queued one
+        app.cancel_selected_task();
+        assert!(matches!(app.tasks[0].status, TaskStatus::Cancelled));
+        assert!(matches!(app.tasks[2].status, TaskStatus::Processing));
+        assert!(app.queue.is_empty());
+
+        app.retry_selected_task();
+        assert!(matches!(app.tasks[0].status, TaskStatus::Pending));
+        assert_eq!(app.queue, VecDeque::from([0]));
+
+        app.delete_selected_task();
+        assert_eq!(app.tasks.len(), 2);
+        assert!(app.queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_updates_of_an_earlier_run_are_dropped() {
+        let client = FirecrawlClient::new("http://127.0.0.1:9/v2", None).unwrap();
+        let mut app = App::new(client);
+        app.add_crawl_task("https://example.com".to_string());
+
+        app.start_task(0);
+        let first_run = app.tasks[0].run;
+        app.cancel_selected_task();
+        app.retry_selected_task();
+        assert!(matches!(app.tasks[0].status, TaskStatus::Processing));
+        assert_ne!(app.tasks[0].run, first_run);
+
+        // The cancelled worker reports late, after the retry started
+        let sender = app.update_sender();
+        sender
+            .send(TaskUpdate::CrawlStarted {
+                id: 0,
+                run: first_run,
+                job_id: "stale-job".to_string(),
+            })
+            .unwrap();
+        sender
+            .send(TaskUpdate::Result {
+                id: 0,
+                run: first_run,
+                result: TaskResult::Crawl(Vec::new()),
+            })
+            .unwrap();
+        app.apply_updates();
+
+        assert!(matches!(app.tasks[0].status, TaskStatus::Processing));
+        assert_eq!(app.tasks[0].job_id, None);
+        assert!(app.tasks[0].result.is_none());
+        assert_eq!(app.running_task_count(), 1);
+    }
+
+    // Local API that answers a crawl start after a short delay, recording the
+    // method and path of every request
+    async fn slow_crawl_api() -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/v2", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                // Connections closed without a request are skipped
+                if request_line.is_empty() {
+                    continue;
+                }
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    match line.trim_end().split_once(": ") {
+                        Some((key, value)) if key.eq_ignore_ascii_case("content-length") => {
+                            length = value.parse().unwrap();
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.unwrap();
+                let request: Vec<&str> = request_line.split_whitespace().take(2).collect();
+                log.lock().unwrap().push(request.join(" "));
+
+                let body = if request[0] == "POST" {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    r#"{"id":"job-1"}"#
+                } else {
+                    r#"{"success":true}"#
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = reader.into_inner().write_all(response.as_bytes()).await;
+            }
+        });
+        (base_url, requests)
+    }
+
+    #[tokio::test]
+    async fn test_crawl_cancelled_before_it_started_is_cancelled_on_the_server() {
+        let (base_url, requests) = slow_crawl_api().await;
+        let mut app = App::new(FirecrawlClient::new(&base_url, None).unwrap());
+        app.add_crawl_task("https://example.com".to_string());
+
+        // Cancel while the crawl start request is still in flight
+        app.start_task(0);
+        app.cancel_selected_task();
+        assert!(matches!(app.tasks[0].status, TaskStatus::Cancelled));
+
+        // The worker cancels the job once it learns its id, then reports the
+        // task as failed
+        let mut statistics = app.progress.get_statistics().await;
+        for _ in 0..100 {
+            if statistics.failed_tasks > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            statistics = app.progress.get_statistics().await;
+        }
+        assert_eq!(statistics.failed_tasks, 1);
+        assert_eq!(statistics.error_codes.get("CANCELLED"), Some(&1));
+        assert!(
+            requests
+                .lock()
+                .unwrap()
+                .contains(&"DELETE /v2/crawl/job-1".to_string())
+        );
+
+        app.apply_updates();
+        assert!(matches!(app.tasks[0].status, TaskStatus::Cancelled));
+        assert_eq!(app.tasks[0].job_id, None);
+    }
+
+    #[test]
+    fn test_crawl_stats_rate_and_eta() {
+        let mut stats = CrawlStats::new();
+        stats.update(CrawlProgress {
+            completed: 10,
+            total: 40,
+            current_url: None,
+            status: "scraping".to_string(),
+            new_urls: vec!["https://example.com/a".to_string()],
+        });
+
+        let elapsed = Duration::from_secs(120);
+        assert_eq!(stats.ratio(), 0.25);
+        assert_eq!(stats.pages_per_minute_after(elapsed), 5.0);
+        assert_eq!(stats.eta_after(elapsed), Some(Duration::from_secs(360)));
+        assert_eq!(stats.pages, vec!["https://example.com/a".to_string()]);
+    }
+}
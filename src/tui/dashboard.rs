@@ -0,0 +1,140 @@
+use crate::services::TaskStatistics;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Throughput samples kept for the sparkline, one per interval
+pub const THROUGHPUT_SAMPLES: usize = 120;
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+// Tabs of the main area
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tab {
+    #[default]
+    Tasks,
+    Dashboard,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 2] = [Tab::Tasks, Tab::Dashboard];
+
+    pub fn next(self) -> Self {
+        match self {
+            Tab::Tasks => Tab::Dashboard,
+            Tab::Dashboard => Tab::Tasks,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tab::Tasks => "Tasks",
+            Tab::Dashboard => "Dashboard",
+        }
+    }
+}
+
+// Figures of the dashboard tab: the latest statistics of the progress
+// service, and how many tasks finished in each of the last intervals
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    pub statistics: TaskStatistics,
+    // Tasks finished per interval, oldest first
+    pub throughput: VecDeque<u64>,
+    // Finished tasks already counted in a sample
+    sampled_finished: usize,
+    sampled_at: Instant,
+}
+
+impl Dashboard {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            statistics: TaskStatistics::default(),
+            throughput: VecDeque::with_capacity(THROUGHPUT_SAMPLES),
+            sampled_finished: 0,
+            sampled_at: now,
+        }
+    }
+
+    pub fn update(&mut self, statistics: TaskStatistics) {
+        self.statistics = statistics;
+    }
+
+    // Close every interval that ended by `now`. Tasks finished since the last
+    // sample go to the first of them; any further ones were idle.
+    pub fn sample(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.sampled_at);
+        let intervals = (elapsed.as_millis() / SAMPLE_INTERVAL.as_millis()) as usize;
+        if intervals == 0 {
+            return;
+        }
+        let finished = self.statistics.finished_tasks();
+        let mut count = finished.saturating_sub(self.sampled_finished) as u64;
+        for _ in 0..intervals.min(THROUGHPUT_SAMPLES) {
+            if self.throughput.len() == THROUGHPUT_SAMPLES {
+                self.throughput.pop_front();
+            }
+            self.throughput.push_back(count);
+            count = 0;
+        }
+        self.sampled_finished = finished;
+        self.sampled_at += SAMPLE_INTERVAL * intervals as u32;
+    }
+
+    // Execution times of the latest finished tasks, in milliseconds
+    pub fn latencies_ms(&self) -> Vec<u64> {
+        self.statistics
+            .recent_latencies
+            .iter()
+            .map(|latency| latency.as_millis() as u64)
+            .collect()
+    }
+
+    // Error codes with their counts, most frequent first
+    pub fn error_breakdown(&self) -> Vec<(&str, usize)> {
+        let mut codes: Vec<(&str, usize)> = self
+            .statistics
+            .error_codes
+            .iter()
+            .map(|(code, count)| (code.as_str(), *count))
+            .collect();
+        codes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        codes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughput_samples_finished_tasks_per_interval() {
+        let start = Instant::now();
+        let mut dashboard = Dashboard::new(start);
+        let mut statistics = TaskStatistics {
+            completed_tasks: 3,
+            failed_tasks: 1,
+            ..TaskStatistics::default()
+        };
+        statistics.error_codes.insert("NETWORK_ERROR".to_string(), 1);
+        statistics.error_codes.insert("TIMEOUT_ERROR".to_string(), 2);
+        dashboard.update(statistics.clone());
+
+        // Nothing is sampled before the first interval ends
+        dashboard.sample(start + Duration::from_millis(500));
+        assert!(dashboard.throughput.is_empty());
+
+        dashboard.sample(start + Duration::from_millis(1200));
+        statistics.completed_tasks += 2;
+        dashboard.update(statistics);
+        // Two idle intervals follow the one the tasks finished in
+        dashboard.sample(start + Duration::from_millis(4100));
+        assert_eq!(dashboard.throughput, vec![4, 2, 0, 0]);
+        assert_eq!(
+            dashboard.error_breakdown(),
+            vec![("TIMEOUT_ERROR", 2), ("NETWORK_ERROR", 1)]
+        );
+
+        dashboard.sample(start + SAMPLE_INTERVAL * 1000);
+        assert_eq!(dashboard.throughput.len(), THROUGHPUT_SAMPLES);
+        assert!(dashboard.throughput.iter().all(|&count| count == 0));
+    }
+}
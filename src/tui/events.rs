@@ -1,5 +1,5 @@
 use anyhow::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyEventKind};
+use crossterm::event::{self, Event as CrosstermEvent, KeyEventKind, MouseEventKind};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -7,6 +7,8 @@ use std::time::Duration;
 
 pub enum Event {
     Key(crossterm::event::KeyEvent),
+    Mouse(crossterm::event::MouseEvent),
+    Resize(u16, u16),
     Tick,
 }
 
@@ -36,14 +38,19 @@ impl EventHandler {
                     .unwrap_or_else(|| Duration::from_millis(1));
 
                 if event::poll(timeout).expect("no events available") {
-                    match event::read().expect("unable to read event") {
+                    let forwarded = match event::read().expect("unable to read event") {
                         CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => {
-                            if sender_clone.send(Event::Key(key)).is_err() {
-                                break;
-                            }
+                            Some(Event::Key(key))
                         }
-                        CrosstermEvent::Resize(_, _) => {}
-                        _ => {}
+                        // Bare pointer motion would only cause redraws
+                        CrosstermEvent::Mouse(mouse) if mouse.kind != MouseEventKind::Moved => {
+                            Some(Event::Mouse(mouse))
+                        }
+                        CrosstermEvent::Resize(width, height) => Some(Event::Resize(width, height)),
+                        _ => None,
+                    };
+                    if forwarded.is_some_and(|event| sender_clone.send(event).is_err()) {
+                        break;
                     }
                 }
 
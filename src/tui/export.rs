@@ -0,0 +1,235 @@
+use crate::api::models::scrape_model::ScrapeResponse;
+use crate::cli::OutputFormat;
+use crate::storage::{ContentRepository, FailureReport, PageFailure, StorageResult};
+use crate::tui::app::TaskResult;
+use base64::Engine;
+use std::path::{Path, PathBuf};
+
+// Formats offered when saving results from the TUI
+pub const SAVE_FORMATS: [OutputFormat; 4] = [
+    OutputFormat::Markdown,
+    OutputFormat::Html,
+    OutputFormat::Json,
+    OutputFormat::Raw,
+];
+
+// Which results a save writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveScope {
+    Selected,
+    AllCompleted,
+}
+
+// Prompt for the format and directory of a save
+#[derive(Debug, Clone)]
+pub struct SaveForm {
+    pub scope: SaveScope,
+    pub format: OutputFormat,
+    pub directory: String,
+}
+
+impl SaveForm {
+    pub fn new(scope: SaveScope, format: OutputFormat, directory: &Path) -> Self {
+        let format = if SAVE_FORMATS.contains(&format) {
+            format
+        } else {
+            OutputFormat::Markdown
+        };
+        Self {
+            scope,
+            format,
+            directory: directory.display().to_string(),
+        }
+    }
+
+    pub fn cycle_format(&mut self, forward: bool) {
+        let current = SAVE_FORMATS
+            .iter()
+            .position(|format| *format == self.format)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % SAVE_FORMATS.len()
+        } else {
+            current.checked_sub(1).unwrap_or(SAVE_FORMATS.len() - 1)
+        };
+        self.format = SAVE_FORMATS[next];
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        self.directory.push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.directory.pop();
+    }
+}
+
+// Files a save wrote, and the pages it could not write
+#[derive(Debug, Clone, Default)]
+pub struct SavedResults {
+    pub files: Vec<PathBuf>,
+    pub failures: Vec<PageFailure>,
+    // failures.json listing the failures, if there were any
+    pub failures_file: Option<PathBuf>,
+}
+
+// Write task results through the repository. A page that cannot be written
+// does not stop the others; it is listed in failures.json instead.
+pub async fn save_results(
+    repository: &dyn ContentRepository,
+    results: &[(String, TaskResult)],
+    format: OutputFormat,
+    output_dir: &Path,
+) -> StorageResult<SavedResults> {
+    repository.ensure_directory(output_dir).await?;
+
+    let mut saved = SavedResults::default();
+    let mut attempted = 0;
+    for (url, result) in results {
+        match result {
+            TaskResult::Scrape(data) => {
+                let response = ScrapeResponse {
+                    success: true,
+                    data: Some(data.as_ref().clone()),
+                    error: None,
+                };
+                attempted += 1;
+                match repository
+                    .save_scrape_result(&response, url, format, output_dir)
+                    .await
+                {
+                    Ok(path) => saved.files.push(path),
+                    Err(e) => saved.failures.push(PageFailure::new(url, e)),
+                }
+            }
+            TaskResult::Crawl(pages) => {
+                attempted += pages.len();
+                let report = repository
+                    .save_crawl_results(pages, url, format, output_dir, false)
+                    .await?;
+                saved.files.extend(report.written_files());
+                saved.failures.extend(report.failures);
+            }
+        }
+    }
+
+    let report = FailureReport::new(None, attempted, saved.failures.clone());
+    saved.failures_file = repository.write_failures(&report, output_dir).await?;
+    Ok(saved)
+}
+
+// Markdown of a result; crawl pages are joined under a heading per page
+pub fn result_markdown(result: &TaskResult) -> Option<String> {
+    match result {
+        TaskResult::Scrape(data) => data.markdown.clone(),
+        TaskResult::Crawl(pages) => {
+            let sections: Vec<String> = pages
+                .iter()
+                .filter_map(|page| {
+                    page.markdown
+                        .as_ref()
+                        .map(|markdown| format!("<!-- {} -->\n\n{}", page.url, markdown))
+                })
+                .collect();
+            (!sections.is_empty()).then(|| sections.join("\n\n"))
+        }
+    }
+}
+
+// OSC 52 escape sequence asking the terminal to put `text` on the clipboard.
+// It works over SSH, as the terminal rather than this process owns the clipboard.
+pub fn osc52_copy(text: &str) -> String {
+    format!(
+        "\x1b]52;c;{}\x07",
+        base64::engine::general_purpose::STANDARD.encode(text)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::crawl_model::{CrawlMetadata, CrawlResponse};
+    use crate::storage::FileSystemRepository;
+    use tempfile::tempdir;
+
+    fn page(url: &str, markdown: &str) -> CrawlResponse {
+        CrawlResponse {
+            id: "1".to_string(),
+            url: url.to_string(),
+            status: "completed".to_string(),
+            completed_at: None,
+            markdown: Some(markdown.to_string()),
+            html: None,
+            metadata: CrawlMetadata {
+                keywords: None,
+                robots: None,
+                og_image: None,
+                author: None,
+                published_date: None,
+                modified_date: None,
+                site_name: None,
+                title: None,
+                status_code: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_results_writes_through_repository() {
+        let dir = tempdir().unwrap();
+        let output_dir = dir.path().join("export");
+        let repository = FileSystemRepository::new(output_dir.clone());
+        let results = vec![(
+            "https://example.com".to_string(),
+            TaskResult::Crawl(vec![
+                page("https://example.com/a", "# A"),
+                page("https://example.com/b", "# B"),
+            ]),
+        )];
+
+        let saved = save_results(&repository, &results, OutputFormat::Markdown, &output_dir)
+            .await
+            .unwrap();
+
+        assert_eq!(saved.files.len(), 2);
+        assert!(saved.files.iter().all(|path| path.starts_with(&output_dir) && path.exists()));
+        assert!(saved.failures_file.is_none());
+        assert_eq!(
+            result_markdown(&results[0].1).unwrap(),
+            "<!-- https://example.com/a -->\n\n# A\n\n<!-- https://example.com/b -->\n\n# B"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_results_lists_pages_that_cannot_be_saved() {
+        let dir = tempdir().unwrap();
+        let output_dir = dir.path().to_path_buf();
+        let repository = FileSystemRepository::new(output_dir.clone());
+        let mut with_html = page("https://example.com/a", "# A");
+        with_html.html = Some("<h1>A</h1>".to_string());
+        let results = vec![(
+            "https://example.com".to_string(),
+            TaskResult::Crawl(vec![page("https://example.com/b", "# B"), with_html]),
+        )];
+
+        // HTML is missing for /b, which must not keep /a from being saved
+        let saved = save_results(&repository, &results, OutputFormat::Html, &output_dir)
+            .await
+            .unwrap();
+
+        assert_eq!(saved.files.len(), 1);
+        assert_eq!(saved.failures.len(), 1);
+        assert_eq!(saved.failures[0].url, "https://example.com/b");
+        let report: FailureReport = serde_json::from_str(
+            &std::fs::read_to_string(saved.failures_file.unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(report.attempted, 2);
+        assert_eq!(report.failures, saved.failures);
+    }
+
+    #[test]
+    fn test_osc52_copy_encodes_text() {
+        assert_eq!(osc52_copy("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
@@ -0,0 +1,313 @@
+use crate::tui::app::{Operation, Task, TaskStatus};
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexBuilder};
+use std::cmp::Ordering;
+
+// Quick filter of the task list by status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFilter {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+    Interrupted,
+}
+
+impl StatusFilter {
+    const ALL: [StatusFilter; 6] = [
+        StatusFilter::Pending,
+        StatusFilter::Running,
+        StatusFilter::Completed,
+        StatusFilter::Failed,
+        StatusFilter::Cancelled,
+        StatusFilter::Interrupted,
+    ];
+
+    pub fn matches(self, status: &TaskStatus) -> bool {
+        matches!(
+            (self, status),
+            (StatusFilter::Pending, TaskStatus::Pending)
+                | (StatusFilter::Running, TaskStatus::Processing)
+                | (StatusFilter::Completed, TaskStatus::Completed)
+                | (StatusFilter::Failed, TaskStatus::Failed(_))
+                | (StatusFilter::Cancelled, TaskStatus::Cancelled)
+                | (StatusFilter::Interrupted, TaskStatus::Interrupted)
+        )
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusFilter::Pending => "pending",
+            StatusFilter::Running => "running",
+            StatusFilter::Completed => "completed",
+            StatusFilter::Failed => "failed",
+            StatusFilter::Cancelled => "cancelled",
+            StatusFilter::Interrupted => "interrupted",
+        }
+    }
+
+    // Next quick filter, with `None` (every status) between the last and first
+    pub fn cycle(filter: Option<Self>) -> Option<Self> {
+        match filter {
+            None => Some(Self::ALL[0]),
+            Some(current) => {
+                let position = Self::ALL.iter().position(|f| *f == current).unwrap_or(0);
+                Self::ALL.get(position + 1).copied()
+            }
+        }
+    }
+}
+
+// Column the task list is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    #[default]
+    Id,
+    Status,
+    Url,
+    Duration,
+}
+
+impl SortKey {
+    pub fn next(self) -> Self {
+        match self {
+            SortKey::Id => SortKey::Status,
+            SortKey::Status => SortKey::Url,
+            SortKey::Url => SortKey::Duration,
+            SortKey::Duration => SortKey::Id,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Id => "id",
+            SortKey::Status => "status",
+            SortKey::Url => "url",
+            SortKey::Duration => "duration",
+        }
+    }
+}
+
+// Running tasks first, finished ones last
+fn status_rank(status: &TaskStatus) -> u8 {
+    match status {
+        TaskStatus::Processing => 0,
+        TaskStatus::Pending => 1,
+        TaskStatus::Interrupted => 2,
+        TaskStatus::Failed(_) => 3,
+        TaskStatus::Cancelled => 4,
+        TaskStatus::Completed => 5,
+    }
+}
+
+// Which tasks the list shows: a URL query, either a substring or a regex
+// and case-insensitive both ways, plus the status and operation quick filters
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    pub query: String,
+    pub regex: bool,
+    pub status: Option<StatusFilter>,
+    pub operation: Option<Operation>,
+    // Why the query is not a valid regex; the query is ignored meanwhile
+    pub error: Option<String>,
+    compiled: Option<Regex>,
+}
+
+impl TaskFilter {
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.compile();
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.compile();
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.compile();
+    }
+
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+        self.compile();
+    }
+
+    fn compile(&mut self) {
+        self.compiled = None;
+        self.error = None;
+        if !self.regex || self.query.is_empty() {
+            return;
+        }
+        match RegexBuilder::new(&self.query).case_insensitive(true).build() {
+            Ok(regex) => self.compiled = Some(regex),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty() || self.status.is_some() || self.operation.is_some()
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        if self.status.is_some_and(|status| !status.matches(&task.status)) {
+            return false;
+        }
+        if self.operation.is_some_and(|operation| operation != task.operation) {
+            return false;
+        }
+        match (&self.compiled, self.regex) {
+            (Some(regex), _) => regex.is_match(&task.url),
+            // An invalid regex filters nothing until it is fixed
+            (None, true) => true,
+            (None, false) => task
+                .url
+                .to_lowercase()
+                .contains(&self.query.to_lowercase()),
+        }
+    }
+
+    // Active filters for the task list title, e.g. `failed crawl /docs/`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(status) = self.status {
+            parts.push(status.label().to_string());
+        }
+        if let Some(operation) = self.operation {
+            parts.push(match operation {
+                Operation::Scrape => "scrape".to_string(),
+                Operation::Crawl => "crawl".to_string(),
+            });
+        }
+        if !self.query.is_empty() {
+            let kind = if self.regex { "~" } else { "" };
+            parts.push(format!("{}/{}/", kind, self.query));
+        }
+        parts.join(" ")
+    }
+}
+
+// Indices of the tasks the list shows, in display order. Sorting is stable,
+// so equal keys keep the order the tasks were added in.
+pub fn visible_tasks<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    filter: &TaskFilter,
+    sort: SortKey,
+    descending: bool,
+    now: DateTime<Utc>,
+) -> Vec<usize> {
+    let mut visible: Vec<(usize, &Task)> = tasks
+        .into_iter()
+        .enumerate()
+        .filter(|(_, task)| filter.matches(task))
+        .collect();
+
+    let compare = |a: &Task, b: &Task| -> Ordering {
+        match sort {
+            SortKey::Id => a.id.cmp(&b.id),
+            SortKey::Status => status_rank(&a.status).cmp(&status_rank(&b.status)),
+            SortKey::Url => a.url.cmp(&b.url),
+            // Tasks that never ran go last either way
+            SortKey::Duration => match (a.duration(now), b.duration(now)) {
+                (Some(a), Some(b)) if descending => b.cmp(&a),
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        }
+    };
+    visible.sort_by(|(_, a), (_, b)| {
+        let ordering = compare(a, b);
+        if descending && sort != SortKey::Duration {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    visible.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::FirecrawlClient;
+    use crate::tui::app::App;
+
+    fn app_with_tasks() -> App {
+        let mut app = App::new(FirecrawlClient::new("http://127.0.0.1:9/v2", None).unwrap());
+        app.add_scrape_task("https://example.com/docs/intro".to_string());
+        app.add_crawl_task("https://Example.com/blog".to_string());
+        app.add_scrape_task("https://other.org/docs".to_string());
+        app.add_crawl_task("https://other.org/".to_string());
+        app
+    }
+
+    #[test]
+    fn test_filter_by_query_status_and_operation() {
+        let mut app = app_with_tasks();
+        app.tasks[3].status = TaskStatus::Failed("boom".to_string());
+        let now = Utc::now();
+        let visible = |filter: &TaskFilter| visible_tasks(&app.tasks, filter, SortKey::Id, false, now);
+
+        let mut filter = TaskFilter::default();
+        "example.COM".chars().for_each(|c| filter.push_char(c));
+        assert_eq!(visible(&filter), vec![0, 1]);
+
+        filter.clear_query();
+        filter.toggle_regex();
+        "docs$".chars().for_each(|c| filter.push_char(c));
+        assert_eq!(visible(&filter), vec![2]);
+
+        // An invalid regex reports why and hides nothing
+        filter.push_char('(');
+        assert!(filter.error.is_some());
+        assert_eq!(visible(&filter).len(), 4);
+
+        filter.clear_query();
+        filter.operation = Some(Operation::Crawl);
+        assert_eq!(visible(&filter), vec![1, 3]);
+        filter.status = Some(StatusFilter::Failed);
+        assert_eq!(visible(&filter), vec![3]);
+        assert_eq!(filter.describe(), "failed crawl");
+
+        assert_eq!(StatusFilter::cycle(None), Some(StatusFilter::Pending));
+        assert_eq!(StatusFilter::cycle(Some(StatusFilter::Interrupted)), None);
+    }
+
+    #[test]
+    fn test_sort_by_url_status_and_duration() {
+        let mut app = app_with_tasks();
+        let now = Utc::now();
+        let filter = TaskFilter::default();
+
+        assert_eq!(
+            visible_tasks(&app.tasks, &filter, SortKey::Url, false, now),
+            vec![1, 0, 3, 2]
+        );
+        assert_eq!(
+            visible_tasks(&app.tasks, &filter, SortKey::Id, true, now),
+            vec![3, 2, 1, 0]
+        );
+
+        app.tasks[2].status = TaskStatus::Processing;
+        app.tasks[2].started_at = Some(now - chrono::Duration::seconds(5));
+        app.tasks[0].status = TaskStatus::Completed;
+        app.tasks[0].started_at = Some(now - chrono::Duration::seconds(60));
+        app.tasks[0].finished_at = Some(now - chrono::Duration::seconds(30));
+        assert_eq!(
+            visible_tasks(&app.tasks, &filter, SortKey::Status, false, now),
+            vec![2, 1, 3, 0]
+        );
+        assert_eq!(
+            visible_tasks(&app.tasks, &filter, SortKey::Duration, false, now),
+            vec![2, 0, 1, 3]
+        );
+        assert_eq!(
+            visible_tasks(&app.tasks, &filter, SortKey::Duration, true, now),
+            vec![0, 2, 1, 3]
+        );
+    }
+}
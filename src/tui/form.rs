@@ -0,0 +1,398 @@
+use crate::api::models::scrape_model::{Location, ProxyType};
+use crate::cli::OutputFormat;
+use crate::tui::app::{Operation, Task, TaskOptions};
+
+// Formats the API accepts for scrape and crawl tasks
+const FORMAT_NAMES: [(&str, OutputFormat); 5] = [
+    ("markdown", OutputFormat::Markdown),
+    ("html", OutputFormat::Html),
+    ("rawHtml", OutputFormat::RawHtml),
+    ("links", OutputFormat::Links),
+    ("images", OutputFormat::Images),
+];
+
+const PROXY_CHOICES: [&str; 4] = ["none", "basic", "stealth", "auto"];
+
+const TOGGLE_CHOICES: [&str; 2] = ["no", "yes"];
+
+// Options edited by the task option form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionField {
+    Formats,
+    OnlyMainContent,
+    IncludeTags,
+    ExcludeTags,
+    Limit,
+    IncludePaths,
+    ExcludePaths,
+    WaitFor,
+    Mobile,
+    Proxy,
+    Location,
+}
+
+impl OptionField {
+    // Fields shown for a task; path filters and the limit only apply to crawls
+    fn for_operation(operation: Operation) -> Vec<OptionField> {
+        let mut fields = vec![
+            OptionField::Formats,
+            OptionField::OnlyMainContent,
+            OptionField::IncludeTags,
+            OptionField::ExcludeTags,
+        ];
+        if operation == Operation::Crawl {
+            fields.extend([
+                OptionField::Limit,
+                OptionField::IncludePaths,
+                OptionField::ExcludePaths,
+            ]);
+        }
+        fields.extend([
+            OptionField::WaitFor,
+            OptionField::Mobile,
+            OptionField::Proxy,
+            OptionField::Location,
+        ]);
+        fields
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OptionField::Formats => "Formats",
+            OptionField::OnlyMainContent => "Only main content",
+            OptionField::IncludeTags => "Include tags",
+            OptionField::ExcludeTags => "Exclude tags",
+            OptionField::Limit => "Page limit",
+            OptionField::IncludePaths => "Include paths",
+            OptionField::ExcludePaths => "Exclude paths",
+            OptionField::WaitFor => "Wait for (ms)",
+            OptionField::Mobile => "Mobile",
+            OptionField::Proxy => "Proxy",
+            OptionField::Location => "Location",
+        }
+    }
+
+    // Short description of the accepted input
+    pub fn hint(self) -> &'static str {
+        match self {
+            OptionField::Formats => "comma-separated: markdown, html, rawHtml, links, images",
+            OptionField::IncludeTags | OptionField::ExcludeTags => "comma-separated tags or selectors",
+            OptionField::Limit => "maximum number of pages, at least 1",
+            OptionField::IncludePaths | OptionField::ExcludePaths => {
+                "comma-separated path patterns, e.g. blog/.*"
+            }
+            OptionField::WaitFor => "milliseconds to wait after load, empty for none",
+            OptionField::Location => "country code with optional languages, e.g. US:en,es",
+            OptionField::OnlyMainContent | OptionField::Mobile | OptionField::Proxy => {
+                "[Space/←→] change"
+            }
+        }
+    }
+
+    // Fixed values cycled through instead of typed, if any
+    fn choices(self) -> Option<&'static [&'static str]> {
+        match self {
+            OptionField::OnlyMainContent | OptionField::Mobile => Some(&TOGGLE_CHOICES),
+            OptionField::Proxy => Some(&PROXY_CHOICES),
+            _ => None,
+        }
+    }
+
+    // Current value of the field as shown in the form
+    fn format_value(self, options: &TaskOptions) -> String {
+        match self {
+            OptionField::Formats => options
+                .formats
+                .iter()
+                .map(|format| format.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            OptionField::OnlyMainContent => toggle_value(options.only_main_content),
+            OptionField::IncludeTags => options.include_tags.join(","),
+            OptionField::ExcludeTags => options.exclude_tags.join(","),
+            OptionField::Limit => options.limit.to_string(),
+            OptionField::IncludePaths => options.include_paths.join(","),
+            OptionField::ExcludePaths => options.exclude_paths.join(","),
+            OptionField::WaitFor => options.wait_for.map(|ms| ms.to_string()).unwrap_or_default(),
+            OptionField::Mobile => toggle_value(options.mobile),
+            OptionField::Proxy => match options.proxy {
+                None => "none",
+                Some(ProxyType::Basic) => "basic",
+                Some(ProxyType::Stealth) => "stealth",
+                Some(ProxyType::Auto) => "auto",
+            }
+            .to_string(),
+            OptionField::Location => match &options.location {
+                None => String::new(),
+                Some(location) if location.languages.is_empty() => location.country.clone(),
+                Some(location) => format!("{}:{}", location.country, location.languages.join(",")),
+            },
+        }
+    }
+
+    // Parse the field's text into `options`
+    fn apply(self, value: &str, options: &mut TaskOptions) -> Result<(), String> {
+        match self {
+            OptionField::Formats => options.formats = parse_formats(value)?,
+            OptionField::OnlyMainContent => options.only_main_content = value == "yes",
+            OptionField::IncludeTags => options.include_tags = split_list(value),
+            OptionField::ExcludeTags => options.exclude_tags = split_list(value),
+            OptionField::Limit => {
+                options.limit = match value.trim().parse::<u32>() {
+                    Ok(limit) if limit >= 1 => limit,
+                    _ => return Err("must be a whole number of at least 1".to_string()),
+                }
+            }
+            OptionField::IncludePaths => options.include_paths = split_list(value),
+            OptionField::ExcludePaths => options.exclude_paths = split_list(value),
+            OptionField::WaitFor => {
+                options.wait_for = match value.trim() {
+                    "" => None,
+                    ms => Some(
+                        ms.parse::<u64>()
+                            .map_err(|_| "must be a number of milliseconds".to_string())?,
+                    ),
+                }
+            }
+            OptionField::Mobile => options.mobile = value == "yes",
+            OptionField::Proxy => {
+                options.proxy = match value {
+                    "basic" => Some(ProxyType::Basic),
+                    "stealth" => Some(ProxyType::Stealth),
+                    "auto" => Some(ProxyType::Auto),
+                    _ => None,
+                }
+            }
+            OptionField::Location => options.location = parse_location(value)?,
+        }
+        Ok(())
+    }
+}
+
+fn toggle_value(enabled: bool) -> String {
+    TOGGLE_CHOICES[usize::from(enabled)].to_string()
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parse_formats(value: &str) -> Result<Vec<OutputFormat>, String> {
+    let mut formats = Vec::new();
+    for name in split_list(value) {
+        let format = FORMAT_NAMES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(&name))
+            .map(|(_, format)| *format)
+            .ok_or_else(|| format!("unknown format '{}'", name))?;
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    if formats.is_empty() {
+        return Err("at least one format is required".to_string());
+    }
+    Ok(formats)
+}
+
+fn parse_location(value: &str) -> Result<Option<Location>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let (country, languages) = value.split_once(':').unwrap_or((value, ""));
+    let country = country.trim();
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err("country must be a two-letter code".to_string());
+    }
+    Ok(Some(Location {
+        country: country.to_ascii_uppercase(),
+        languages: split_list(languages),
+    }))
+}
+
+// A field of the form with its edited text and last validation error
+#[derive(Debug, Clone)]
+pub struct FormEntry {
+    pub field: OptionField,
+    pub value: String,
+    pub error: Option<String>,
+}
+
+impl FormEntry {
+    pub fn is_choice(&self) -> bool {
+        self.field.choices().is_some()
+    }
+}
+
+// Modal form editing the options of a pending task
+#[derive(Debug, Clone)]
+pub struct OptionsForm {
+    pub task_id: usize,
+    pub operation: Operation,
+    pub entries: Vec<FormEntry>,
+    pub focused: usize,
+}
+
+impl OptionsForm {
+    pub fn new(task: &Task) -> Self {
+        let entries = OptionField::for_operation(task.operation)
+            .into_iter()
+            .map(|field| FormEntry {
+                field,
+                value: field.format_value(&task.options),
+                error: None,
+            })
+            .collect();
+        Self {
+            task_id: task.id,
+            operation: task.operation,
+            entries,
+            focused: 0,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.entries.len();
+    }
+
+    pub fn focus_previous(&mut self) {
+        self.focused = self.focused.checked_sub(1).unwrap_or(self.entries.len() - 1);
+    }
+
+    // Type into a text field; Space changes the value of a choice field
+    pub fn input_char(&mut self, c: char) {
+        let entry = &mut self.entries[self.focused];
+        if entry.is_choice() {
+            if c == ' ' {
+                self.cycle(true);
+            }
+        } else {
+            entry.value.push(c);
+            entry.error = None;
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        let entry = &mut self.entries[self.focused];
+        if !entry.is_choice() {
+            entry.value.pop();
+            entry.error = None;
+        }
+    }
+
+    // Move a choice field to its next or previous value
+    pub fn cycle(&mut self, forward: bool) {
+        let entry = &mut self.entries[self.focused];
+        let Some(choices) = entry.field.choices() else {
+            return;
+        };
+        let current = choices.iter().position(|c| *c == entry.value).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % choices.len()
+        } else {
+            current.checked_sub(1).unwrap_or(choices.len() - 1)
+        };
+        entry.value = choices[next].to_string();
+    }
+
+    // Parse every field on top of `base`. Returns the new options, or None
+    // after recording an error on each invalid field and focusing the first.
+    pub fn validate(&mut self, base: &TaskOptions) -> Option<TaskOptions> {
+        let mut options = base.clone();
+        let mut first_invalid = None;
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            entry.error = entry.field.apply(&entry.value, &mut options).err();
+            if entry.error.is_some() && first_invalid.is_none() {
+                first_invalid = Some(index);
+            }
+        }
+        match first_invalid {
+            Some(index) => {
+                self.focused = index;
+                None
+            }
+            None => Some(options),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::app::TaskStatus;
+
+    fn task(operation: Operation) -> Task {
+        Task {
+            id: 3,
+            operation,
+            url: "https://example.com".to_string(),
+            status: TaskStatus::Pending,
+            progress: "Pending".to_string(),
+            result: None,
+            options: TaskOptions::default(),
+            crawl_stats: None,
+            run: 0,
+            job_id: None,
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    fn set(form: &mut OptionsForm, field: OptionField, value: &str) {
+        let entry = form.entries.iter_mut().find(|e| e.field == field).unwrap();
+        entry.value = value.to_string();
+    }
+
+    #[test]
+    fn test_validate_parses_fields() {
+        let mut form = OptionsForm::new(&task(Operation::Crawl));
+        set(&mut form, OptionField::Formats, "markdown, links");
+        set(&mut form, OptionField::Limit, "25");
+        set(&mut form, OptionField::ExcludePaths, "admin/.*, ,login");
+        set(&mut form, OptionField::WaitFor, "1500");
+        set(&mut form, OptionField::Location, "de:de,en");
+        form.focused = form
+            .entries
+            .iter()
+            .position(|e| e.field == OptionField::Proxy)
+            .unwrap();
+        form.input_char(' ');
+
+        let options = form.validate(&TaskOptions::default()).unwrap();
+        assert_eq!(options.formats, vec![OutputFormat::Markdown, OutputFormat::Links]);
+        assert_eq!(options.limit, 25);
+        assert_eq!(options.exclude_paths, vec!["admin/.*", "login"]);
+        assert_eq!(options.wait_for, Some(1500));
+        assert_eq!(options.proxy, Some(ProxyType::Basic));
+        assert_eq!(
+            options.location,
+            Some(Location {
+                country: "DE".to_string(),
+                languages: vec!["de".to_string(), "en".to_string()],
+            })
+        );
+        assert!(options.only_main_content);
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_fields() {
+        let mut form = OptionsForm::new(&task(Operation::Scrape));
+        assert!(!form.entries.iter().any(|e| e.field == OptionField::Limit));
+
+        set(&mut form, OptionField::Formats, "markdown,pdf");
+        set(&mut form, OptionField::Location, "Germany");
+        form.focused = 2;
+
+        assert!(form.validate(&TaskOptions::default()).is_none());
+        assert_eq!(form.focused, 0);
+        assert_eq!(form.entries[0].error.as_deref(), Some("unknown format 'pdf'"));
+        let location = form.entries.last().unwrap();
+        assert_eq!(location.error.as_deref(), Some("country must be a two-letter code"));
+    }
+}
@@ -0,0 +1,550 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+// Views with their own key bindings; the same key may run a different
+// action in each
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    Normal,
+    SiteMap,
+}
+
+// Commands that keys can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    AddScrape,
+    AddCrawl,
+    EditOptions,
+    Process,
+    RunAll,
+    Cancel,
+    Retry,
+    Delete,
+    Duplicate,
+    IncreaseLimit,
+    DecreaseLimit,
+    CycleView,
+    SiteMap,
+    Dashboard,
+    ToggleLogs,
+    CycleLogFilter,
+    Save,
+    SaveAll,
+    Copy,
+    SelectNext,
+    SelectPrevious,
+    ScrollDown,
+    ScrollUp,
+    PageDown,
+    PageUp,
+    ScrollTop,
+    Filter,
+    CycleStatusFilter,
+    CycleOperationFilter,
+    ClearFilters,
+    CycleSort,
+    ReverseSort,
+    Dismiss,
+    Help,
+    SiteMapNext,
+    SiteMapPrevious,
+    SiteMapFold,
+    SiteMapExpand,
+    SiteMapCollapse,
+    SiteMapScrape,
+    SiteMapCrawl,
+    SiteMapClose,
+}
+
+impl Action {
+    // Every action, in the order the help overlay lists them
+    pub const ALL: [Action; 43] = [
+        Action::AddScrape,
+        Action::AddCrawl,
+        Action::EditOptions,
+        Action::Process,
+        Action::RunAll,
+        Action::Cancel,
+        Action::Retry,
+        Action::Delete,
+        Action::Duplicate,
+        Action::IncreaseLimit,
+        Action::DecreaseLimit,
+        Action::SelectNext,
+        Action::SelectPrevious,
+        Action::Filter,
+        Action::CycleStatusFilter,
+        Action::CycleOperationFilter,
+        Action::ClearFilters,
+        Action::CycleSort,
+        Action::ReverseSort,
+        Action::CycleView,
+        Action::SiteMap,
+        Action::Dashboard,
+        Action::ScrollDown,
+        Action::ScrollUp,
+        Action::PageDown,
+        Action::PageUp,
+        Action::ScrollTop,
+        Action::Save,
+        Action::SaveAll,
+        Action::Copy,
+        Action::ToggleLogs,
+        Action::CycleLogFilter,
+        Action::Dismiss,
+        Action::Help,
+        Action::Quit,
+        Action::SiteMapNext,
+        Action::SiteMapPrevious,
+        Action::SiteMapFold,
+        Action::SiteMapExpand,
+        Action::SiteMapCollapse,
+        Action::SiteMapScrape,
+        Action::SiteMapCrawl,
+        Action::SiteMapClose,
+    ];
+
+    // Views the action can be run from
+    pub fn contexts(self) -> &'static [KeyContext] {
+        match self {
+            Action::CycleView | Action::PageDown | Action::PageUp | Action::Help => {
+                &[KeyContext::Normal, KeyContext::SiteMap]
+            }
+            Action::SiteMapNext
+            | Action::SiteMapPrevious
+            | Action::SiteMapFold
+            | Action::SiteMapExpand
+            | Action::SiteMapCollapse
+            | Action::SiteMapScrape
+            | Action::SiteMapCrawl
+            | Action::SiteMapClose => &[KeyContext::SiteMap],
+            _ => &[KeyContext::Normal],
+        }
+    }
+
+    // Name of the action in the `[ui.tui.keybindings]` config table
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::AddScrape => "add-scrape",
+            Action::AddCrawl => "add-crawl",
+            Action::EditOptions => "edit-options",
+            Action::Process => "process",
+            Action::RunAll => "run-all",
+            Action::Cancel => "cancel",
+            Action::Retry => "retry",
+            Action::Delete => "delete",
+            Action::Duplicate => "duplicate",
+            Action::IncreaseLimit => "increase-limit",
+            Action::DecreaseLimit => "decrease-limit",
+            Action::CycleView => "cycle-view",
+            Action::SiteMap => "site-map",
+            Action::Dashboard => "dashboard",
+            Action::ToggleLogs => "toggle-logs",
+            Action::CycleLogFilter => "cycle-log-filter",
+            Action::Save => "save",
+            Action::SaveAll => "save-all",
+            Action::Copy => "copy",
+            Action::SelectNext => "select-next",
+            Action::SelectPrevious => "select-previous",
+            Action::ScrollDown => "scroll-down",
+            Action::ScrollUp => "scroll-up",
+            Action::PageDown => "page-down",
+            Action::PageUp => "page-up",
+            Action::ScrollTop => "scroll-top",
+            Action::Filter => "filter",
+            Action::CycleStatusFilter => "filter-status",
+            Action::CycleOperationFilter => "filter-operation",
+            Action::ClearFilters => "clear-filters",
+            Action::CycleSort => "sort",
+            Action::ReverseSort => "reverse-sort",
+            Action::Dismiss => "dismiss",
+            Action::Help => "help",
+            Action::SiteMapNext => "site-map-next",
+            Action::SiteMapPrevious => "site-map-previous",
+            Action::SiteMapFold => "site-map-fold",
+            Action::SiteMapExpand => "site-map-expand",
+            Action::SiteMapCollapse => "site-map-collapse",
+            Action::SiteMapScrape => "site-map-scrape",
+            Action::SiteMapCrawl => "site-map-crawl",
+            Action::SiteMapClose => "site-map-close",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::AddScrape => "Add a scrape task",
+            Action::AddCrawl => "Add a crawl task",
+            Action::EditOptions => "Edit options of the selected task",
+            Action::Process => "Start the selected task",
+            Action::RunAll => "Run all pending tasks",
+            Action::Cancel => "Cancel the selected task",
+            Action::Retry => "Retry a failed, cancelled or interrupted task",
+            Action::Delete => "Delete the selected task",
+            Action::Duplicate => "Duplicate the selected task",
+            Action::IncreaseLimit => "Raise the crawl page limit",
+            Action::DecreaseLimit => "Lower the crawl page limit",
+            Action::CycleView => "Switch the preview view",
+            Action::SiteMap => "Explore crawled pages as a site map",
+            Action::Dashboard => "Switch between tasks and the dashboard",
+            Action::ToggleLogs => "Show or hide the log panel",
+            Action::CycleLogFilter => "Change the log level filter",
+            Action::Save => "Save the selected result",
+            Action::SaveAll => "Save all completed results",
+            Action::Copy => "Copy the selected markdown",
+            Action::SelectNext => "Select the next task",
+            Action::SelectPrevious => "Select the previous task",
+            Action::ScrollDown => "Scroll the preview down a line",
+            Action::ScrollUp => "Scroll the preview up a line",
+            Action::PageDown => "Scroll the preview down a page",
+            Action::PageUp => "Scroll the preview up a page",
+            Action::ScrollTop => "Scroll the preview to the top",
+            Action::Filter => "Filter tasks by URL substring or regex",
+            Action::CycleStatusFilter => "Show only tasks of the next status",
+            Action::CycleOperationFilter => "Show only scrapes or crawls",
+            Action::ClearFilters => "Clear all task filters",
+            Action::CycleSort => "Sort by id, status, URL or duration",
+            Action::ReverseSort => "Reverse the sort order",
+            Action::Dismiss => "Close help, clear messages",
+            Action::Help => "Toggle this help",
+            Action::SiteMapNext => "Select the next node",
+            Action::SiteMapPrevious => "Select the previous node",
+            Action::SiteMapFold => "Fold or unfold the selected node",
+            Action::SiteMapExpand => "Unfold the selected node",
+            Action::SiteMapCollapse => "Fold the selected node",
+            Action::SiteMapScrape => "Add a scrape of the selected page",
+            Action::SiteMapCrawl => "Add a crawl of the selected subtree",
+            Action::SiteMapClose => "Close the site map",
+        }
+    }
+
+    // Vim-style defaults; arrows and paging keys keep working alongside them
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "ctrl-c"],
+            Action::AddScrape => &["a"],
+            Action::AddCrawl => &["c"],
+            Action::EditOptions => &["o"],
+            Action::Process => &["p"],
+            Action::RunAll => &["P"],
+            Action::Cancel => &["x"],
+            Action::Retry => &["r"],
+            Action::Delete => &["d", "delete"],
+            Action::Duplicate => &["D"],
+            Action::IncreaseLimit => &["+", "="],
+            Action::DecreaseLimit => &["-"],
+            Action::CycleView => &["v"],
+            Action::SiteMap => &["m"],
+            Action::Dashboard => &["tab"],
+            Action::ToggleLogs => &["l"],
+            Action::CycleLogFilter => &["L"],
+            Action::Save => &["s"],
+            Action::SaveAll => &["S"],
+            Action::Copy => &["y"],
+            Action::SelectNext => &["j", "down"],
+            Action::SelectPrevious => &["k", "up"],
+            Action::ScrollDown => &["ctrl-e"],
+            Action::ScrollUp => &["ctrl-y"],
+            Action::PageDown => &["ctrl-d", "pagedown"],
+            Action::PageUp => &["ctrl-u", "pageup"],
+            Action::ScrollTop => &["g", "home"],
+            Action::Filter => &["/"],
+            Action::CycleStatusFilter => &["f"],
+            Action::CycleOperationFilter => &["F"],
+            Action::ClearFilters => &["C"],
+            Action::CycleSort => &["O"],
+            Action::ReverseSort => &["R"],
+            Action::Dismiss => &["esc"],
+            Action::Help => &["?"],
+            Action::SiteMapNext => &["j", "down"],
+            Action::SiteMapPrevious => &["k", "up"],
+            Action::SiteMapFold => &["enter", "space"],
+            Action::SiteMapExpand => &["l", "right"],
+            Action::SiteMapCollapse => &["h", "left"],
+            Action::SiteMapScrape => &["a"],
+            Action::SiteMapCrawl => &["c"],
+            Action::SiteMapClose => &["esc", "q", "m"],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+// A key with its Ctrl/Alt modifiers. Shift is part of the character
+// (`P` rather than `shift-p`), so it is not tracked separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    // Parse keys like `q`, `G`, `?`, `ctrl-d`, `alt-x`, `pagedown` or `f1`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid key '{}'", text);
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        let mut rest = text;
+        // A lone `-` is the minus key, not a modifier separator
+        while rest.len() > 1 {
+            let Some((prefix, tail)) = rest.split_once('-') else {
+                break;
+            };
+            match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => return Err(invalid()),
+            }
+            rest = tail;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                name => name
+                    .strip_prefix('f')
+                    .and_then(|n| n.parse::<u8>().ok())
+                    .filter(|n| (1..=12).contains(n))
+                    .map(KeyCode::F)
+                    .ok_or_else(invalid)?,
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    // Terminals report Shift for uppercase letters and some symbols;
+    // drop it so `P` matches however the terminal encodes it
+    fn from_event(event: &KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::BackTab => write!(f, "shift-tab"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            code => write!(f, "{}", code.to_string().to_ascii_lowercase()),
+        }
+    }
+}
+
+// Keys of every action; config entries replace the defaults of their action
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    keys: HashMap<Action, Vec<KeyBinding>>,
+    actions: HashMap<(KeyContext, KeyBinding), Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).expect("default key bindings are valid")
+    }
+}
+
+impl Keymap {
+    // Build the keymap from `[ui.tui.keybindings]`, e.g. `quit = ["q", "ctrl-q"]`.
+    // An empty list unbinds the action. A key may only be bound once per view.
+    pub fn from_config(overrides: &BTreeMap<String, Vec<String>>) -> Result<Self, String> {
+        for name in overrides.keys() {
+            if Action::from_name(name).is_none() {
+                return Err(format!("unknown key binding action '{}'", name));
+            }
+        }
+
+        let mut keymap = Self {
+            keys: HashMap::new(),
+            actions: HashMap::new(),
+        };
+        for action in Action::ALL {
+            let keys = match overrides.get(action.name()) {
+                Some(keys) => keys
+                    .iter()
+                    .map(|key| KeyBinding::parse(key))
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => action
+                    .default_keys()
+                    .iter()
+                    .map(|key| KeyBinding::parse(key).expect("default key is valid"))
+                    .collect(),
+            };
+            for key in &keys {
+                for context in action.contexts() {
+                    if let Some(other) = keymap.actions.insert((*context, *key), action) {
+                        return Err(format!(
+                            "key '{}' is bound to both '{}' and '{}'",
+                            key,
+                            other.name(),
+                            action.name()
+                        ));
+                    }
+                }
+            }
+            keymap.keys.insert(action, keys);
+        }
+        Ok(keymap)
+    }
+
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        self.action_in(KeyContext::Normal, event)
+    }
+
+    pub fn action_in(&self, context: KeyContext, event: &KeyEvent) -> Option<Action> {
+        self.actions
+            .get(&(context, KeyBinding::from_event(event)))
+            .copied()
+    }
+
+    // Keys of an action for display, e.g. `j/down`
+    pub fn keys_label(&self, action: Action) -> String {
+        self.keys
+            .get(&action)
+            .map(|keys| {
+                keys.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default()
+    }
+
+    // Key labels and descriptions of every action bound in a view, for the help overlay
+    pub fn help_entries(&self, context: KeyContext) -> Vec<(String, &'static str)> {
+        Action::ALL
+            .into_iter()
+            .filter(|action| action.contexts().contains(&context))
+            .map(|action| (self.keys_label(action), action.description()))
+            .filter(|(keys, _)| !keys.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_default_keymap_has_vim_bindings() {
+        let keymap = Keymap::default();
+        let none = KeyModifiers::NONE;
+
+        assert_eq!(keymap.action(&key(KeyCode::Char('j'), none)), Some(Action::SelectNext));
+        assert_eq!(keymap.action(&key(KeyCode::Down, none)), Some(Action::SelectNext));
+        assert_eq!(
+            keymap.action(&key(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(Action::PageDown)
+        );
+        assert_eq!(keymap.action(&key(KeyCode::Char('d'), none)), Some(Action::Delete));
+        // Shift reported alongside an uppercase letter still matches
+        assert_eq!(
+            keymap.action(&key(KeyCode::Char('P'), KeyModifiers::SHIFT)),
+            Some(Action::RunAll)
+        );
+        assert_eq!(keymap.keys_label(Action::PageDown), "ctrl-d/pagedown");
+        assert_eq!(keymap.help_entries(KeyContext::Normal).len(), 35);
+    }
+
+    #[test]
+    fn test_site_map_keys_are_bound_separately() {
+        let keymap = Keymap::default();
+        let none = KeyModifiers::NONE;
+        let site_map = KeyContext::SiteMap;
+
+        assert_eq!(keymap.action(&key(KeyCode::Char('a'), none)), Some(Action::AddScrape));
+        assert_eq!(
+            keymap.action_in(site_map, &key(KeyCode::Char('a'), none)),
+            Some(Action::SiteMapScrape)
+        );
+        assert_eq!(
+            keymap.action_in(site_map, &key(KeyCode::Esc, none)),
+            Some(Action::SiteMapClose)
+        );
+        assert_eq!(
+            keymap.action_in(site_map, &key(KeyCode::Char('v'), none)),
+            Some(Action::CycleView)
+        );
+        assert_eq!(keymap.action_in(site_map, &key(KeyCode::Char('x'), none)), None);
+        assert_eq!(keymap.help_entries(site_map).len(), 12);
+
+        // Shared actions conflict with the site map's own keys
+        let mut overrides = BTreeMap::new();
+        overrides.insert("cycle-view".to_string(), vec!["h".to_string()]);
+        assert_eq!(
+            Keymap::from_config(&overrides).unwrap_err(),
+            "key 'h' is bound to both 'cycle-view' and 'site-map-collapse'"
+        );
+    }
+
+    #[test]
+    fn test_config_overrides_and_conflicts() {
+        let mut overrides = BTreeMap::new();
+        overrides.insert("quit".to_string(), vec!["ctrl-q".to_string()]);
+        overrides.insert("copy".to_string(), vec![]);
+        let keymap = Keymap::from_config(&overrides).unwrap();
+
+        assert_eq!(
+            keymap.action(&key(KeyCode::Char('q'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+        assert_eq!(keymap.action(&key(KeyCode::Char('q'), KeyModifiers::NONE)), None);
+        assert_eq!(keymap.action(&key(KeyCode::Char('y'), KeyModifiers::NONE)), None);
+        assert!(keymap
+            .help_entries(KeyContext::Normal)
+            .iter()
+            .all(|(_, d)| *d != "Copy the selected markdown"));
+
+        overrides.insert("cancel".to_string(), vec!["j".to_string()]);
+        assert_eq!(
+            Keymap::from_config(&overrides).unwrap_err(),
+            "key 'j' is bound to both 'cancel' and 'select-next'"
+        );
+        overrides.remove("cancel");
+        overrides.insert("launch".to_string(), vec!["l".to_string()]);
+        assert!(Keymap::from_config(&overrides).is_err());
+        assert!(KeyBinding::parse("hyper-x").is_err());
+        assert_eq!(KeyBinding::parse("-").unwrap().code, KeyCode::Char('-'));
+        assert_eq!(KeyBinding::parse("shift-tab").unwrap().code, KeyCode::BackTab);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Local};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+// A log record captured for the log panel
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+// Ring buffer of recent log records, shared by the logger and the UI.
+// The oldest records are dropped once `capacity` is reached.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity.min(1024)))),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    // Records at `filter` or more severe, oldest first
+    pub fn entries(&self, filter: LevelFilter) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .filter(|entry| entry.level <= filter)
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// `log` backend writing into a LogBuffer, so records never reach the
+// terminal while the TUI owns it
+struct TuiLogger {
+    buffer: LogBuffer,
+}
+
+impl Log for TuiLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.buffer.push(LogEntry {
+            time: Local::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+// Route `log` records up to `level` into `buffer`. Returns false if another
+// logger is already installed; the process can only have one.
+pub fn install(buffer: LogBuffer, level: LevelFilter) -> bool {
+    let logger = Box::leak(Box::new(TuiLogger { buffer }));
+    if log::set_logger(logger).is_err() {
+        return false;
+    }
+    log::set_max_level(level);
+    true
+}
+
+// Most verbose level captured; trace output of the HTTP stack would
+// evict everything else from the buffer
+pub const CAPTURE_LEVEL: LevelFilter = LevelFilter::Debug;
+
+// Next, more verbose level filter of the log panel, wrapping back to errors
+pub fn next_level_filter(filter: LevelFilter) -> LevelFilter {
+    match filter {
+        LevelFilter::Error => LevelFilter::Warn,
+        LevelFilter::Warn => LevelFilter::Info,
+        LevelFilter::Info => LevelFilter::Debug,
+        LevelFilter::Off | LevelFilter::Debug | LevelFilter::Trace => LevelFilter::Error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, message: &str) -> LogEntry {
+        LogEntry {
+            time: Local::now(),
+            level,
+            target: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_buffer_keeps_latest_entries_and_filters_by_level() {
+        let buffer = LogBuffer::new(3);
+        buffer.push(entry(Level::Info, "one"));
+        buffer.push(entry(Level::Debug, "two"));
+        buffer.push(entry(Level::Error, "three"));
+        buffer.push(entry(Level::Warn, "four"));
+
+        assert_eq!(buffer.len(), 3);
+        let messages = |filter| {
+            buffer
+                .entries(filter)
+                .into_iter()
+                .map(|e| e.message)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(messages(LevelFilter::Trace), vec!["two", "three", "four"]);
+        assert_eq!(messages(LevelFilter::Warn), vec!["three", "four"]);
+        assert_eq!(next_level_filter(LevelFilter::Info), LevelFilter::Debug);
+        assert_eq!(next_level_filter(CAPTURE_LEVEL), LevelFilter::Error);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::tui::theme::Theme;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+// Render Markdown into styled terminal lines. This is a light, line-based
+// renderer: headings, lists, block quotes, fenced code, inline code, bold
+// text and links are styled; everything else is shown as written.
+pub fn render_markdown(markdown: &str, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            lines.push(Line::from(Span::styled(line.to_string(), code_style(theme))));
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(Line::from(Span::styled(line.to_string(), code_style(theme))));
+            continue;
+        }
+
+        lines.push(render_block_line(line, theme));
+    }
+
+    lines
+}
+
+fn render_block_line(line: &str, theme: &Theme) -> Line<'static> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    // Headings
+    let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+        let style = heading_style(hashes, theme);
+        let mut spans = vec![Span::styled(format!("{} ", "#".repeat(hashes)), style)];
+        spans.extend(render_inline(trimmed[hashes..].trim(), style, theme));
+        return Line::from(spans);
+    }
+
+    // Horizontal rules
+    if matches!(trimmed, "---" | "***" | "___") {
+        return Line::styled(theme.rule().repeat(40), theme.fg(theme.palette.muted));
+    }
+
+    // Block quotes
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let style = theme
+            .fg(theme.palette.quote)
+            .add_modifier(Modifier::ITALIC);
+        let mut spans = vec![Span::styled(format!("{}{} ", indent, theme.quote_bar()), style)];
+        spans.extend(render_inline(quote.trim_start(), style, theme));
+        return Line::from(spans);
+    }
+
+    // Unordered list items
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+    {
+        let mut spans = vec![Span::styled(
+            format!("{}{} ", indent, theme.bullet()),
+            theme.fg(theme.palette.highlight),
+        )];
+        spans.extend(render_inline(item, Style::default(), theme));
+        return Line::from(spans);
+    }
+
+    // Ordered list items
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && trimmed[digits..].starts_with(". ") {
+        let mut spans = vec![Span::styled(
+            format!("{}{} ", indent, &trimmed[..digits + 1]),
+            theme.fg(theme.palette.highlight),
+        )];
+        spans.extend(render_inline(&trimmed[digits + 2..], Style::default(), theme));
+        return Line::from(spans);
+    }
+
+    let mut spans = Vec::new();
+    if !indent.is_empty() {
+        spans.push(Span::raw(indent.to_string()));
+    }
+    spans.extend(render_inline(trimmed, Style::default(), theme));
+    Line::from(spans)
+}
+
+// Style inline code, bold text and links within a line
+fn render_inline(text: &str, base: Style, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => inline_code(rest, theme),
+            '*' | '_' => bold(rest, base, theme),
+            '[' | '!' => link(rest, base, theme),
+            _ => None,
+        };
+
+        match styled {
+            Some((new_spans, consumed)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.extend(new_spans);
+                rest = &rest[consumed..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+fn inline_code(text: &str, theme: &Theme) -> Option<(Vec<Span<'static>>, usize)> {
+    let end = text[1..].find('`')? + 1;
+    Some((
+        vec![Span::styled(text[1..end].to_string(), code_style(theme))],
+        end + 1,
+    ))
+}
+
+fn bold(text: &str, base: Style, theme: &Theme) -> Option<(Vec<Span<'static>>, usize)> {
+    let marker = if text.starts_with("**") {
+        "**"
+    } else if text.starts_with("__") {
+        "__"
+    } else {
+        return None;
+    };
+    let end = text[2..].find(marker)? + 2;
+    if end == 2 {
+        return None;
+    }
+    Some((
+        render_inline(&text[2..end], base.add_modifier(Modifier::BOLD), theme),
+        end + 2,
+    ))
+}
+
+fn link(text: &str, base: Style, theme: &Theme) -> Option<(Vec<Span<'static>>, usize)> {
+    let (is_image, body) = match text.strip_prefix('!') {
+        Some(body) => (true, body),
+        None => (false, text),
+    };
+    let body = body.strip_prefix('[')?;
+    let label_end = body.find("](")?;
+    let url_end = body[label_end + 2..].find(')')? + label_end + 2;
+
+    let label = &body[..label_end];
+    let url = &body[label_end + 2..url_end];
+    let label = if is_image {
+        format!("[image: {}]", label)
+    } else {
+        label.to_string()
+    };
+    let consumed = usize::from(is_image) + 1 + url_end + 1;
+
+    Some((
+        vec![
+            Span::styled(
+                label,
+                base.patch(theme.fg(theme.palette.link))
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+            Span::styled(format!(" <{}>", url), theme.fg(theme.palette.muted)),
+        ],
+        consumed,
+    ))
+}
+
+fn heading_style(level: usize, theme: &Theme) -> Style {
+    let color = match level {
+        1 => theme.palette.heading,
+        2 => theme.palette.accent,
+        _ => theme.palette.subheading,
+    };
+    theme.fg(color).add_modifier(Modifier::BOLD)
+}
+
+fn code_style(theme: &Theme) -> Style {
+    theme.fg_bg(theme.palette.code_fg, theme.palette.code_bg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_render_markdown_blocks_and_inline() {
+        let theme = Theme::default();
+        let lines = render_markdown(
+            "# Title\n- item with `code`\n1. first\n```\n# not a heading\n```\nSee [docs](https://x.dev) and **bold**",
+            &theme,
+        );
+
+        assert_eq!(text(&lines[0]), "# Title");
+        assert!(lines[0].spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(text(&lines[1]), "• item with code");
+        assert_eq!(lines[1].spans.last().unwrap().style, code_style(&theme));
+        assert_eq!(text(&lines[2]), "1. first");
+        assert_eq!(text(&lines[4]), "# not a heading");
+        assert_eq!(lines[4].spans[0].style, code_style(&theme));
+        assert_eq!(text(&lines[6]), "See docs <https://x.dev> and bold");
+        assert!(lines[6].spans[1].style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+}
@@ -2,6 +2,17 @@ pub mod app;
 pub mod ui;
 pub mod events;
 pub mod runner;
+pub mod markdown;
+pub mod form;
+pub mod export;
+pub mod session;
+pub mod logger;
+pub mod theme;
+pub mod keymap;
+pub mod mouse;
+pub mod filter;
+pub mod sitemap;
+pub mod dashboard;
 
 pub use app::App;
 pub use events::{Event, EventHandler};
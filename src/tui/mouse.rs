@@ -0,0 +1,201 @@
+use crate::tui::app::App;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+// Lines scrolled per wheel notch
+pub const WHEEL_SCROLL_LINES: usize = 3;
+
+// Bounds of the split percentages, so no pane can be dragged shut
+const MIN_SPLIT_PERCENT: u16 = 10;
+const MAX_SPLIT_PERCENT: u16 = 90;
+
+// Where the panes were drawn last frame, for hit testing mouse events
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PaneLayout {
+    // Area shared by the task panes and the log panel
+    pub body: Rect,
+    pub task_list: Rect,
+    pub details: Rect,
+    pub preview: Option<Rect>,
+    pub logs: Option<Rect>,
+}
+
+// A split being resized by dragging its border
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Split {
+    // Between the task list and the details on its right
+    TaskList,
+    // Between the task panes and the log panel below
+    Logs,
+}
+
+// Width of the task list and height of the log panel, in percent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitSizes {
+    pub task_list: u16,
+    pub logs: u16,
+}
+
+impl Default for SplitSizes {
+    fn default() -> Self {
+        Self {
+            task_list: 30,
+            logs: 30,
+        }
+    }
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    area.contains(Position::new(column, row))
+}
+
+// Percentage of `length` that `offset` cells make, within the split bounds
+fn split_percent(offset: u16, length: u16) -> u16 {
+    let percent = u32::from(offset) * 100 / u32::from(length.max(1));
+    (percent as u16).clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT)
+}
+
+// Split whose border is at the given cell: the right border of the task
+// list or the left one of the details, and the top border of the log panel
+fn split_at(layout: &PaneLayout, column: u16, row: u16) -> Option<Split> {
+    let list = layout.task_list;
+    let on_list_border = (column + 1 == list.right() || column == layout.details.x)
+        && row >= list.y
+        && row < list.bottom();
+    if on_list_border {
+        return Some(Split::TaskList);
+    }
+    let logs = layout.logs?;
+    (row == logs.y && column >= logs.x && column < logs.right()).then_some(Split::Logs)
+}
+
+// Apply a mouse event: click to select a task, wheel to scroll the pane
+// under the pointer, drag a pane border to resize the split
+pub fn handle_mouse(app: &mut App, event: MouseEvent) {
+    let (column, row) = (event.column, event.row);
+    let layout = app.layout;
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(split) = split_at(&layout, column, row) {
+                app.dragging = Some(split);
+                return;
+            }
+            let list = layout.task_list;
+            // Rows inside the border map onto the shown tasks
+            if contains(list, column, row) && row > list.y && row + 1 < list.bottom() {
+                let position = app.list_offset + usize::from(row - list.y - 1);
+                if let Some(&index) = app.visible_tasks().get(position) {
+                    app.select(index);
+                }
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) => match app.dragging {
+            Some(Split::TaskList) => {
+                let body = layout.body;
+                app.splits.task_list = split_percent(column.saturating_sub(body.x), body.width);
+            }
+            Some(Split::Logs) => {
+                let body = layout.body;
+                app.splits.logs = split_percent(body.bottom().saturating_sub(row), body.height);
+            }
+            None => {}
+        },
+        MouseEventKind::Up(_) => app.dragging = None,
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            let down = event.kind == MouseEventKind::ScrollDown;
+            if layout.logs.is_some_and(|area| contains(area, column, row)) {
+                // The log panel scrolls back from its newest record
+                app.log_scroll = if down {
+                    app.log_scroll.saturating_sub(WHEEL_SCROLL_LINES)
+                } else {
+                    app.log_scroll.saturating_add(WHEEL_SCROLL_LINES)
+                };
+            } else if contains(layout.task_list, column, row) {
+                if down {
+                    app.select_next_task();
+                } else {
+                    app.select_previous_task();
+                }
+            } else if layout.preview.is_some_and(|area| contains(area, column, row)) {
+                if down {
+                    app.scroll_preview_down(WHEEL_SCROLL_LINES);
+                } else {
+                    app.scroll_preview_up(WHEEL_SCROLL_LINES);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::FirecrawlClient;
+    use crossterm::event::KeyModifiers;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn app_with_layout() -> App {
+        let mut app = App::new(FirecrawlClient::new("http://127.0.0.1:9/v2", None).unwrap());
+        for i in 0..5 {
+            app.add_scrape_task(format!("https://example.com/{}", i));
+        }
+        app.layout = PaneLayout {
+            body: Rect::new(0, 3, 100, 40),
+            task_list: Rect::new(0, 3, 30, 28),
+            details: Rect::new(30, 3, 70, 10),
+            preview: Some(Rect::new(30, 13, 70, 18)),
+            logs: Some(Rect::new(0, 31, 100, 12)),
+        };
+        app.list_offset = 1;
+        app
+    }
+
+    #[test]
+    fn test_click_selects_and_wheel_scrolls_pane_under_pointer() {
+        let mut app = app_with_layout();
+
+        // Second row inside the border, with the list scrolled by one
+        app.scroll_offset = 7;
+        handle_mouse(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 5, 5));
+        assert_eq!(app.selected_task, 2);
+        assert_eq!(app.scroll_offset, 0);
+
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, 50, 20));
+        assert_eq!(app.scroll_offset, WHEEL_SCROLL_LINES);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, 50, 35));
+        assert_eq!(app.log_scroll, WHEEL_SCROLL_LINES);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, 5, 10));
+        assert_eq!(app.selected_task, 3);
+
+        // Clicks below the last task change nothing
+        handle_mouse(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 5, 20));
+        assert_eq!(app.selected_task, 3);
+    }
+
+    #[test]
+    fn test_dragging_borders_resizes_splits() {
+        let mut app = app_with_layout();
+
+        handle_mouse(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 29, 10));
+        assert_eq!(app.dragging, Some(Split::TaskList));
+        handle_mouse(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 45, 10));
+        assert_eq!(app.splits.task_list, 45);
+        handle_mouse(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 99, 10));
+        assert_eq!(app.splits.task_list, MAX_SPLIT_PERCENT);
+        handle_mouse(&mut app, mouse(MouseEventKind::Up(MouseButton::Left), 99, 10));
+        assert_eq!(app.dragging, None);
+
+        handle_mouse(&mut app, mouse(MouseEventKind::Down(MouseButton::Left), 50, 31));
+        handle_mouse(&mut app, mouse(MouseEventKind::Drag(MouseButton::Left), 50, 23));
+        assert_eq!(app.splits.logs, 50);
+    }
+}
@@ -8,19 +8,222 @@ use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
-use std::io;
-use crate::tui::{app::App, events::{Event, EventHandler}, ui};
+use std::io::{self, Write};
+use std::sync::Once;
+use crate::tui::{
+    app::{App, Mode, Operation, PREVIEW_PAGE_SIZE},
+    events::{Event, EventHandler},
+    export::SaveScope,
+    keymap::{Action, KeyContext},
+    logger,
+    mouse,
+    ui,
+};
+
+// Put the terminal back into its normal state
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    )
+}
+
+// Restores the terminal when dropped, so early returns and unwinding
+// panics never leave it in raw mode
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore_terminal();
+    }
+}
+
+// Restore the terminal before the panic message is printed, so it stays
+// readable. Only panics on the UI thread do this; a panicking worker must
+// not tear down the screen under a running UI.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let ui_thread = std::thread::current().id();
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if std::thread::current().id() == ui_thread {
+                let _ = restore_terminal();
+            }
+            previous(info);
+        }));
+    });
+}
+
+// Run the action bound to a key in normal mode; returns true when the app should quit
+fn handle_action(app: &mut App, action: Action) -> bool {
+    match action {
+        Action::Quit => return true,
+        Action::AddScrape => app.begin_input(Operation::Scrape),
+        Action::AddCrawl => app.begin_input(Operation::Crawl),
+        Action::EditOptions => app.open_options_form(),
+        Action::Process => app.start_selected_task(),
+        Action::RunAll => app.run_all_pending(),
+        Action::Cancel => app.cancel_selected_task(),
+        Action::Retry => app.retry_selected_task(),
+        Action::Delete => app.delete_selected_task(),
+        Action::Duplicate => app.duplicate_selected_task(),
+        Action::IncreaseLimit => app.adjust_crawl_limit(true),
+        Action::DecreaseLimit => app.adjust_crawl_limit(false),
+        Action::CycleView => app.cycle_preview_view(),
+        Action::SiteMap => app.open_site_map(),
+        Action::Dashboard => app.next_tab(),
+        Action::ToggleLogs => app.toggle_log_panel(),
+        Action::CycleLogFilter => app.cycle_log_filter(),
+        Action::Save => app.open_save_form(SaveScope::Selected),
+        Action::SaveAll => app.open_save_form(SaveScope::AllCompleted),
+        Action::SelectNext => app.select_next_task(),
+        Action::SelectPrevious => app.select_previous_task(),
+        Action::ScrollDown => app.scroll_preview_down(1),
+        Action::ScrollUp => app.scroll_preview_up(1),
+        Action::PageDown => app.scroll_preview_down(PREVIEW_PAGE_SIZE),
+        Action::PageUp => app.scroll_preview_up(PREVIEW_PAGE_SIZE),
+        Action::ScrollTop => app.scroll_offset = 0,
+        Action::Filter => app.begin_filter(),
+        Action::CycleStatusFilter => app.cycle_status_filter(),
+        Action::CycleOperationFilter => app.cycle_operation_filter(),
+        Action::ClearFilters => app.clear_filters(),
+        Action::CycleSort => app.cycle_sort(),
+        Action::ReverseSort => app.reverse_sort(),
+        Action::Dismiss => app.dismiss(),
+        Action::Help => app.toggle_help(),
+        // Needs the terminal; handled by the event loop
+        Action::Copy => {}
+        // Only bound in the site map
+        Action::SiteMapNext
+        | Action::SiteMapPrevious
+        | Action::SiteMapFold
+        | Action::SiteMapExpand
+        | Action::SiteMapCollapse
+        | Action::SiteMapScrape
+        | Action::SiteMapCrawl
+        | Action::SiteMapClose => {}
+    }
+    false
+}
+
+fn handle_input_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.submit_input(),
+        KeyCode::Tab => app.toggle_input_operation(),
+        KeyCode::Esc => app.cancel_input(),
+        KeyCode::Backspace => {
+            app.input.pop();
+        }
+        KeyCode::Char(c) => app.input.push(c),
+        _ => {}
+    }
+}
+
+fn handle_filter_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.close_filter(true),
+        KeyCode::Esc => app.close_filter(false),
+        KeyCode::Tab => app.filter.toggle_regex(),
+        KeyCode::Backspace => app.filter.backspace(),
+        KeyCode::Char(c) => app.filter.push_char(c),
+        _ => {}
+    }
+    app.sync_selection();
+}
+
+// Run an action bound to a key in the site map
+fn handle_site_map_action(app: &mut App, action: Action) {
+    match action {
+        // Close the help overlay before the site map under it
+        Action::SiteMapClose if app.show_help => app.dismiss(),
+        Action::SiteMapClose => app.close_site_map(),
+        Action::SiteMapScrape => app.add_site_map_task(Operation::Scrape),
+        Action::SiteMapCrawl => app.add_site_map_task(Operation::Crawl),
+        Action::CycleView => app.cycle_preview_view(),
+        Action::PageDown => app.scroll_preview_down(PREVIEW_PAGE_SIZE),
+        Action::PageUp => app.scroll_preview_up(PREVIEW_PAGE_SIZE),
+        Action::Help => app.toggle_help(),
+        _ => {
+            let Some(map) = app.site_map.as_mut() else {
+                return;
+            };
+            let selected = map.selected;
+            match action {
+                Action::SiteMapNext => map.select_next(),
+                Action::SiteMapPrevious => map.select_previous(),
+                Action::SiteMapFold => map.toggle(),
+                Action::SiteMapExpand => map.expand(),
+                Action::SiteMapCollapse => map.collapse(),
+                _ => {}
+            }
+            // Show the newly selected page from its top
+            if map.selected != selected {
+                app.scroll_offset = 0;
+            }
+        }
+    }
+}
+
+fn handle_form_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.save_options_form(),
+        KeyCode::Esc => app.close_options_form(),
+        _ => {
+            let Some(form) = app.options_form.as_mut() else {
+                return;
+            };
+            match code {
+                KeyCode::Tab | KeyCode::Down => form.focus_next(),
+                KeyCode::BackTab | KeyCode::Up => form.focus_previous(),
+                KeyCode::Left => form.cycle(false),
+                KeyCode::Right => form.cycle(true),
+                KeyCode::Backspace => form.backspace(),
+                KeyCode::Char(c) => form.input_char(c),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn handle_save_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.confirm_save(),
+        KeyCode::Esc => app.close_save_form(),
+        _ => {
+            let Some(form) = app.save_form.as_mut() else {
+                return;
+            };
+            match code {
+                KeyCode::Tab | KeyCode::Right => form.cycle_format(true),
+                KeyCode::BackTab | KeyCode::Left => form.cycle_format(false),
+                KeyCode::Backspace => form.backspace(),
+                KeyCode::Char(c) => form.input_char(c),
+                _ => {}
+            }
+        }
+    }
+}
 
 pub async fn run_tui(mut app: App) -> Result<()> {
     // Setup terminal
+    install_panic_hook();
     enable_raw_mode()?;
+    let _guard = TerminalGuard;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create event handler with 250ms tick rate
-    let events = EventHandler::new(250);
+    // Capture log records for the log panel; printing them would corrupt the screen
+    logger::install(app.logs.clone(), logger::CAPTURE_LEVEL);
+
+    // Tick at the configured refresh rate
+    let tick_rate = u64::try_from(app.config.ui.tui.refresh_rate.as_millis()).unwrap_or(u64::MAX);
+    let events = EventHandler::new(tick_rate.max(1));
 
     loop {
         // Render UI
@@ -29,80 +232,71 @@ pub async fn run_tui(mut app: App) -> Result<()> {
         // Handle events
         match events.next()? {
             Event::Key(key_event) => {
-                if let KeyCode::Char('q') = key_event.code {
-                    break;
-                }
-
-                match key_event.code {
-                    KeyCode::Char('a') => {
-                        app.mode = crate::tui::app::Mode::Input;
-                    }
-                    KeyCode::Char('c') => {
-                        if app.mode == crate::tui::app::Mode::Input {
-                            if !app.input.trim().is_empty() {
-                                app.add_crawl_task(app.input.trim().to_string());
-                                app.input.clear();
-                            }
-                        }
-                        app.mode = crate::tui::app::Mode::Normal;
-                    }
-                    KeyCode::Char('p') => {
-                        let client_clone = app.get_client().clone();
-                        let selected_task = app.selected_task;
-                        let mut app_clone = App::new(client_clone);
-                        app_clone.selected_task = selected_task;
-                        app_clone.tasks = app.tasks.clone();
-                        
-                        tokio::spawn(async move {
-                            if let Err(e) = app_clone.process_next_task().await {
-                                eprintln!("Error processing task: {}", e);
-                            }
-                        });
-                    }
-                    KeyCode::Up => {
-                        app.select_previous_task();
+                // Keys are commands only in normal mode; while typing a URL,
+                // a filter or options every character is text
+                let quit = match app.mode {
+                    Mode::Input => {
+                        handle_input_key(&mut app, key_event.code);
+                        false
                     }
-                    KeyCode::Down => {
-                        app.select_next_task();
+                    Mode::EditOptions => {
+                        handle_form_key(&mut app, key_event.code);
+                        false
                     }
-                    KeyCode::Enter => {
-                        if app.mode == crate::tui::app::Mode::Input && !app.input.trim().is_empty() {
-                            app.add_scrape_task(app.input.trim().to_string());
-                            app.input.clear();
-                            app.mode = crate::tui::app::Mode::Normal;
-                        }
+                    Mode::Save => {
+                        handle_save_key(&mut app, key_event.code);
+                        false
                     }
-                    KeyCode::Esc => {
-                        app.mode = crate::tui::app::Mode::Normal;
-                        app.input.clear();
+                    Mode::Filter => {
+                        handle_filter_key(&mut app, key_event.code);
+                        false
                     }
-                    KeyCode::Char(c) => {
-                        if app.mode == crate::tui::app::Mode::Input {
-                            app.input.push(c);
+                    Mode::SiteMap => {
+                        let action = app.keymap.action_in(KeyContext::SiteMap, &key_event);
+                        if let Some(action) = action {
+                            handle_site_map_action(&mut app, action);
                         }
+                        false
                     }
-                    KeyCode::Backspace => {
-                        if app.mode == crate::tui::app::Mode::Input {
-                            app.input.pop();
+                    Mode::Normal | Mode::Processing => match app.keymap.action(&key_event) {
+                        Some(Action::Copy) => {
+                            // The terminal, not this process, owns the clipboard
+                            if let Some(sequence) = app.copy_selected_markdown() {
+                                let backend = terminal.backend_mut();
+                                backend.write_all(sequence.as_bytes())?;
+                                backend.flush()?;
+                            }
+                            false
                         }
-                    }
-                    _ => {}
+                        Some(action) => handle_action(&mut app, action),
+                        None => false,
+                    },
+                };
+                if quit {
+                    break;
                 }
             }
+            Event::Mouse(mouse_event) => {
+                // Prompts and forms keep the focus; the panes stay put
+                if matches!(app.mode, Mode::Normal | Mode::Processing) {
+                    mouse::handle_mouse(&mut app, mouse_event);
+                }
+            }
+            Event::Resize(_, _) => {
+                // Nothing to update; the loop redraws at the new size right away
+            }
             Event::Tick => {
-                // Update any ongoing tasks or animations
+                // Apply status, progress and results sent by background workers
+                app.apply_updates();
             }
         }
     }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    // Restore terminal (the guard covers error and panic paths)
+    restore_terminal()?;
+
+    // Keep the task list for the next launch of this session
+    app.save_session()?;
 
     Ok(())
 }
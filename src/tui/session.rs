@@ -0,0 +1,113 @@
+use crate::config::ConfigLoader;
+use crate::storage::{StorageError, StorageResult};
+use crate::tui::app::Task;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Bumped when the session file layout changes incompatibly
+pub const SESSION_VERSION: u32 = 1;
+
+// Task list of the TUI as saved on quit and restored on launch
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub version: u32,
+    pub saved_at: DateTime<Utc>,
+    pub next_task_id: usize,
+    pub tasks: Vec<Task>,
+}
+
+// Session file for `name` under the config directory
+pub fn session_path(name: &str) -> StorageResult<PathBuf> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(StorageError::InvalidPath(format!(
+            "session name '{}' may only contain letters, digits, '-' and '_'",
+            name
+        )));
+    }
+    let config_dir = ConfigLoader::config_dir().ok_or_else(|| {
+        StorageError::DirectoryNotFound("home directory for the session file".to_string())
+    })?;
+    Ok(config_dir.join("sessions").join(format!("{}.json", name)))
+}
+
+// Read a session file; a missing file is a new, empty session
+pub fn load_session(path: &Path) -> StorageResult<Option<Session>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let session: Session = serde_json::from_str(&content)?;
+    if session.version != SESSION_VERSION {
+        return Err(StorageError::UnsupportedContentType(format!(
+            "session file version {} (expected {})",
+            session.version, SESSION_VERSION
+        )));
+    }
+    Ok(Some(session))
+}
+
+pub fn save_session(path: &Path, session: &Session) -> StorageResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(session)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::FirecrawlClient;
+    use crate::tui::app::{App, Operation, TaskStatus};
+    use tempfile::tempdir;
+
+    fn new_app() -> App {
+        App::new(FirecrawlClient::new("http://127.0.0.1:9/v2", None).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_session_round_trip_resumes_running_tasks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sessions").join("work.json");
+
+        let mut app = new_app();
+        app.open_session(path.clone()).unwrap();
+        app.add_scrape_task("https://example.com".to_string());
+        app.add_crawl_task("https://example.org".to_string());
+        app.add_scrape_task("https://example.net".to_string());
+        app.tasks[0].status = TaskStatus::Processing;
+        app.tasks[1].status = TaskStatus::Processing;
+        app.tasks[1].job_id = Some("job-1".to_string());
+        app.tasks[1].options.limit = 42;
+        app.save_session().unwrap();
+
+        let mut restored = new_app();
+        restored.open_session(path).unwrap();
+
+        assert_eq!(restored.tasks.len(), 3);
+        assert!(matches!(restored.tasks[0].status, TaskStatus::Interrupted));
+        // The crawl reattached to its job and is running again
+        let crawl = &restored.tasks[1];
+        assert_eq!(crawl.operation, Operation::Crawl);
+        assert!(matches!(crawl.status, TaskStatus::Processing));
+        assert_eq!(crawl.job_id.as_deref(), Some("job-1"));
+        assert_eq!(crawl.options.limit, 42);
+        assert!(matches!(restored.tasks[2].status, TaskStatus::Pending));
+
+        restored.add_scrape_task("https://example.io".to_string());
+        assert_eq!(restored.tasks[3].id, 3);
+    }
+
+    #[test]
+    fn test_session_path_rejects_unsafe_names() {
+        assert!(session_path("../etc").is_err());
+        assert!(session_path("").is_err());
+    }
+}
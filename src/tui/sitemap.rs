@@ -0,0 +1,320 @@
+use crate::api::CrawlResponse;
+use reqwest::Url;
+use std::collections::{BTreeMap, HashSet};
+
+// Levels below the hosts that start out expanded
+const INITIAL_EXPANDED_DEPTH: usize = 2;
+
+// Node of the site map: a host, or a path segment below it
+#[derive(Debug, Clone)]
+pub struct SiteNode {
+    pub label: String,
+    // URL and path of the subtree root, e.g. `https://example.com/docs` and `/docs`.
+    // The path of a host is empty.
+    pub url: String,
+    pub path: String,
+    // Index of the crawled page at exactly this URL, if one was crawled
+    pub page: Option<usize>,
+    // Pages in this subtree, the node itself included
+    pub count: usize,
+    pub children: Vec<SiteNode>,
+}
+
+// Row of the tree as shown; `expanded` is None for leaves
+#[derive(Debug, Clone, Copy)]
+pub struct SiteRow<'a> {
+    pub depth: usize,
+    pub node: &'a SiteNode,
+    pub expanded: Option<bool>,
+}
+
+#[derive(Debug, Default)]
+struct NodeBuilder {
+    page: Option<usize>,
+    children: BTreeMap<String, NodeBuilder>,
+}
+
+impl NodeBuilder {
+    fn finish(self, label: String, origin: &str, path: String) -> SiteNode {
+        let children: Vec<SiteNode> = self
+            .children
+            .into_iter()
+            .map(|(segment, child)| {
+                let child_path = format!("{}/{}", path, segment);
+                child.finish(segment, origin, child_path)
+            })
+            .collect();
+        let count = usize::from(self.page.is_some())
+            + children.iter().map(|child| child.count).sum::<usize>();
+        SiteNode {
+            label,
+            url: format!("{}{}", origin, path),
+            path,
+            page: self.page,
+            count,
+            children,
+        }
+    }
+}
+
+// Origin and path segments of a URL; a query stays on the last segment
+fn split_url(url: &str) -> (String, Vec<String>) {
+    let Ok(parsed) = Url::parse(url) else {
+        return (url.to_string(), Vec::new());
+    };
+    let mut segments: Vec<String> = parsed
+        .path_segments()
+        .map(|segments| {
+            segments
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(query) = parsed.query() {
+        match segments.last_mut() {
+            Some(last) => last.push_str(&format!("?{}", query)),
+            None => segments.push(format!("?{}", query)),
+        }
+    }
+    (parsed.origin().ascii_serialization(), segments)
+}
+
+// Group crawled pages by host and path segment
+pub fn build_tree(pages: &[CrawlResponse]) -> Vec<SiteNode> {
+    let mut hosts: BTreeMap<String, NodeBuilder> = BTreeMap::new();
+    for (index, page) in pages.iter().enumerate() {
+        let (origin, segments) = split_url(&page.url);
+        let mut node = hosts.entry(origin).or_default();
+        for segment in segments {
+            node = node.children.entry(segment).or_default();
+        }
+        node.page.get_or_insert(index);
+    }
+    hosts
+        .into_iter()
+        .map(|(origin, host)| host.finish(origin.clone(), &origin, String::new()))
+        .collect()
+}
+
+// Collapsible tree of the pages of a crawl task, with a selected row
+#[derive(Debug, Clone)]
+pub struct SiteMap {
+    pub task_id: usize,
+    pub roots: Vec<SiteNode>,
+    // URLs of the collapsed nodes
+    collapsed: HashSet<String>,
+    pub selected: usize,
+    // Index of the first row on screen, kept between frames
+    pub offset: usize,
+}
+
+impl SiteMap {
+    pub fn new(task_id: usize, pages: &[CrawlResponse]) -> Self {
+        let roots = build_tree(pages);
+        let mut collapsed = HashSet::new();
+        fn collapse_deep(node: &SiteNode, depth: usize, collapsed: &mut HashSet<String>) {
+            if depth >= INITIAL_EXPANDED_DEPTH && !node.children.is_empty() {
+                collapsed.insert(node.url.clone());
+            }
+            for child in &node.children {
+                collapse_deep(child, depth + 1, collapsed);
+            }
+        }
+        for root in &roots {
+            collapse_deep(root, 0, &mut collapsed);
+        }
+        Self {
+            task_id,
+            roots,
+            collapsed,
+            selected: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.roots.iter().map(|root| root.count).sum()
+    }
+
+    // Rows of the expanded part of the tree, depth first
+    pub fn rows(&self) -> Vec<SiteRow<'_>> {
+        fn push<'a>(
+            node: &'a SiteNode,
+            depth: usize,
+            collapsed: &HashSet<String>,
+            rows: &mut Vec<SiteRow<'a>>,
+        ) {
+            let expanded = (!node.children.is_empty()).then(|| !collapsed.contains(&node.url));
+            rows.push(SiteRow {
+                depth,
+                node,
+                expanded,
+            });
+            if expanded == Some(true) {
+                for child in &node.children {
+                    push(child, depth + 1, collapsed, rows);
+                }
+            }
+        }
+        let mut rows = Vec::new();
+        for root in &self.roots {
+            push(root, 0, &self.collapsed, &mut rows);
+        }
+        rows
+    }
+
+    pub fn selected_node(&self) -> Option<&SiteNode> {
+        self.rows().get(self.selected).map(|row| row.node)
+    }
+
+    pub fn select_next(&mut self) {
+        let rows = self.rows().len();
+        if self.selected + 1 < rows {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    // Expand or collapse the selected node
+    pub fn toggle(&mut self) {
+        let Some((url, expanded)) = self
+            .rows()
+            .get(self.selected)
+            .map(|row| (row.node.url.clone(), row.expanded))
+        else {
+            return;
+        };
+        match expanded {
+            Some(true) => self.collapsed.insert(url),
+            Some(false) => self.collapsed.remove(&url),
+            None => false,
+        };
+    }
+
+    pub fn expand(&mut self) {
+        let url = match self.rows().get(self.selected) {
+            Some(row) if row.expanded == Some(false) => row.node.url.clone(),
+            _ => return,
+        };
+        self.collapsed.remove(&url);
+    }
+
+    // Collapse the selected node, or select its parent if it is a leaf or
+    // already collapsed
+    pub fn collapse(&mut self) {
+        let rows = self.rows();
+        let Some(row) = rows.get(self.selected) else {
+            return;
+        };
+        if row.expanded == Some(true) {
+            let url = row.node.url.clone();
+            drop(rows);
+            self.collapsed.insert(url);
+            return;
+        }
+        let parent = rows[..self.selected]
+            .iter()
+            .rposition(|candidate| candidate.depth < row.depth);
+        if let Some(parent) = parent {
+            self.selected = parent;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::models::crawl_model::CrawlMetadata;
+
+    fn page(url: &str) -> CrawlResponse {
+        CrawlResponse {
+            id: "1".to_string(),
+            url: url.to_string(),
+            status: "completed".to_string(),
+            completed_at: None,
+            markdown: None,
+            html: None,
+            metadata: CrawlMetadata {
+                keywords: None,
+                robots: None,
+                og_image: None,
+                author: None,
+                published_date: None,
+                modified_date: None,
+                site_name: None,
+                title: None,
+                status_code: None,
+            },
+        }
+    }
+
+    fn pages() -> Vec<CrawlResponse> {
+        [
+            "https://example.com/",
+            "https://example.com/docs/guide/install",
+            "https://example.com/docs/guide/usage",
+            "https://example.com/docs",
+            "https://example.com/blog/post?page=2",
+            "https://other.org/about",
+        ]
+        .into_iter()
+        .map(page)
+        .collect()
+    }
+
+    #[test]
+    fn test_tree_groups_by_host_and_segment_with_counts() {
+        let roots = build_tree(&pages());
+
+        assert_eq!(roots.len(), 2);
+        let example = &roots[0];
+        assert_eq!(example.label, "https://example.com");
+        assert_eq!(example.page, Some(0));
+        assert_eq!(example.count, 5);
+        let labels: Vec<&str> = example.children.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["blog", "docs"]);
+
+        let docs = &example.children[1];
+        assert_eq!(docs.url, "https://example.com/docs");
+        assert_eq!(docs.path, "/docs");
+        assert_eq!(docs.page, Some(3));
+        assert_eq!(docs.count, 3);
+        assert_eq!(docs.children[0].count, 2);
+        assert_eq!(example.children[0].children[0].label, "post?page=2");
+        assert_eq!(roots[1].count, 1);
+    }
+
+    #[test]
+    fn test_collapse_expand_and_navigation() {
+        let mut map = SiteMap::new(7, &pages());
+        assert_eq!(map.page_count(), 6);
+        // docs/guide starts collapsed, two levels below the host
+        let labels = |map: &SiteMap| -> Vec<String> {
+            map.rows().iter().map(|row| row.node.label.clone()).collect()
+        };
+        assert_eq!(
+            labels(&map),
+            vec!["https://example.com", "blog", "post?page=2", "docs", "guide", "https://other.org", "about"]
+        );
+
+        map.selected = 4;
+        map.expand();
+        assert_eq!(map.rows().len(), 9);
+        map.select_next();
+        assert_eq!(map.selected_node().unwrap().label, "install");
+
+        // A leaf selects its parent, which then collapses
+        map.collapse();
+        assert_eq!(map.selected_node().unwrap().label, "guide");
+        map.collapse();
+        assert_eq!(map.rows().len(), 7);
+
+        map.selected = 0;
+        map.toggle();
+        assert_eq!(labels(&map), vec!["https://example.com", "https://other.org", "about"]);
+    }
+}
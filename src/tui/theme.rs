@@ -0,0 +1,394 @@
+use crate::config::UiConfig;
+use crate::tui::app::{Operation, TaskStatus};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols;
+use std::str::FromStr;
+
+// Colors of the TUI by role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub title: Color,
+    pub accent: Color,
+    pub heading: Color,
+    pub subheading: Color,
+    pub muted: Color,
+    pub highlight: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    pub pending: Color,
+    pub running: Color,
+    pub success: Color,
+    pub error: Color,
+    pub warning: Color,
+    pub cancelled: Color,
+    pub interrupted: Color,
+    pub link: Color,
+    pub quote: Color,
+    pub code_fg: Color,
+    pub code_bg: Color,
+    pub gauge_bg: Color,
+    pub status_fg: Color,
+    pub status_bg: Color,
+    pub alert_fg: Color,
+    pub alert_bg: Color,
+}
+
+impl Palette {
+    pub fn dark() -> Self {
+        Self {
+            title: Color::Cyan,
+            accent: Color::Cyan,
+            heading: Color::Magenta,
+            subheading: Color::Green,
+            muted: Color::DarkGray,
+            highlight: Color::Yellow,
+            selection_fg: Color::Reset,
+            selection_bg: Color::DarkGray,
+            pending: Color::Yellow,
+            running: Color::Blue,
+            success: Color::Green,
+            error: Color::Red,
+            warning: Color::Yellow,
+            cancelled: Color::DarkGray,
+            interrupted: Color::Magenta,
+            link: Color::Blue,
+            quote: Color::Gray,
+            code_fg: Color::Yellow,
+            code_bg: Color::Black,
+            gauge_bg: Color::DarkGray,
+            status_fg: Color::Black,
+            status_bg: Color::Gray,
+            alert_fg: Color::White,
+            alert_bg: Color::Red,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            title: Color::Blue,
+            accent: Color::Blue,
+            heading: Color::Magenta,
+            subheading: Color::Green,
+            muted: Color::DarkGray,
+            highlight: Color::Magenta,
+            selection_fg: Color::Black,
+            selection_bg: Color::Gray,
+            pending: Color::Rgb(160, 110, 0),
+            running: Color::Blue,
+            success: Color::Green,
+            error: Color::Red,
+            warning: Color::Rgb(160, 110, 0),
+            cancelled: Color::DarkGray,
+            interrupted: Color::Magenta,
+            link: Color::Blue,
+            quote: Color::DarkGray,
+            code_fg: Color::Red,
+            code_bg: Color::Rgb(235, 235, 235),
+            gauge_bg: Color::Gray,
+            status_fg: Color::White,
+            status_bg: Color::DarkGray,
+            alert_fg: Color::White,
+            alert_bg: Color::Red,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            title: Color::White,
+            accent: Color::LightCyan,
+            heading: Color::LightYellow,
+            subheading: Color::LightCyan,
+            muted: Color::White,
+            highlight: Color::LightYellow,
+            selection_fg: Color::Black,
+            selection_bg: Color::White,
+            pending: Color::LightYellow,
+            running: Color::LightCyan,
+            success: Color::LightGreen,
+            error: Color::LightRed,
+            warning: Color::LightYellow,
+            cancelled: Color::White,
+            interrupted: Color::LightMagenta,
+            link: Color::LightCyan,
+            quote: Color::White,
+            code_fg: Color::LightYellow,
+            code_bg: Color::Black,
+            gauge_bg: Color::Black,
+            status_fg: Color::Black,
+            status_bg: Color::White,
+            alert_fg: Color::White,
+            alert_bg: Color::Red,
+        }
+    }
+
+    // Builtin palette by name; "default" is the dark palette
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" | "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    // Set the color of a role named as in the config file
+    fn set(&mut self, role: &str, value: &str) -> Result<(), String> {
+        let color = Color::from_str(value)
+            .map_err(|_| format!("invalid color '{}' for '{}'", value, role))?;
+        let slot = match role {
+            "title" => &mut self.title,
+            "accent" => &mut self.accent,
+            "heading" => &mut self.heading,
+            "subheading" => &mut self.subheading,
+            "muted" => &mut self.muted,
+            "highlight" => &mut self.highlight,
+            "selection_fg" => &mut self.selection_fg,
+            "selection_bg" => &mut self.selection_bg,
+            "pending" => &mut self.pending,
+            "running" => &mut self.running,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "warning" => &mut self.warning,
+            "cancelled" => &mut self.cancelled,
+            "interrupted" => &mut self.interrupted,
+            "link" => &mut self.link,
+            "quote" => &mut self.quote,
+            "code_fg" => &mut self.code_fg,
+            "code_bg" => &mut self.code_bg,
+            "gauge_bg" => &mut self.gauge_bg,
+            "status_fg" => &mut self.status_fg,
+            "status_bg" => &mut self.status_bg,
+            "alert_fg" => &mut self.alert_fg,
+            "alert_bg" => &mut self.alert_bg,
+            _ => return Err(format!("unknown palette role '{}'", role)),
+        };
+        *slot = color;
+        Ok(())
+    }
+}
+
+// Palette plus the color and Unicode switches. Without colors only text
+// modifiers are used; without Unicode, markers are plain ASCII.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub palette: Palette,
+    pub colors: bool,
+    pub unicode: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            palette: Palette::dark(),
+            colors: true,
+            unicode: true,
+        }
+    }
+}
+
+impl Theme {
+    // Build the theme from the UI config. Colors are off when
+    // `enable_colors` is false or NO_COLOR is set to a non-empty value.
+    pub fn from_config(ui: &UiConfig) -> Result<Self, String> {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        Self::from_config_with(ui, no_color)
+    }
+
+    fn from_config_with(ui: &UiConfig, no_color: bool) -> Result<Self, String> {
+        let scheme = ui.theme.color_scheme.as_str();
+        let palette = match ui.theme.palettes.get(scheme) {
+            Some(custom) => {
+                let base = custom.base.as_deref().unwrap_or("dark");
+                let mut palette = Palette::builtin(base)
+                    .ok_or_else(|| format!("unknown base theme '{}' for palette '{}'", base, scheme))?;
+                for (role, value) in &custom.colors {
+                    palette.set(role, value)?;
+                }
+                palette
+            }
+            None => Palette::builtin(scheme).ok_or_else(|| {
+                format!(
+                    "unknown color scheme '{}' (expected dark, light, high-contrast or a configured palette)",
+                    scheme
+                )
+            })?,
+        };
+
+        Ok(Self {
+            palette,
+            colors: ui.enable_colors && !no_color,
+            unicode: ui.theme.use_unicode,
+        })
+    }
+
+    pub fn fg(&self, color: Color) -> Style {
+        if self.colors {
+            Style::default().fg(color)
+        } else {
+            Style::default()
+        }
+    }
+
+    pub fn fg_bg(&self, fg: Color, bg: Color) -> Style {
+        if self.colors {
+            Style::default().fg(fg).bg(bg)
+        } else {
+            Style::default()
+        }
+    }
+
+    // Selected list row; reversed video when colors are off
+    pub fn selected(&self) -> Style {
+        if self.colors {
+            self.fg_bg(self.palette.selection_fg, self.palette.selection_bg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        }
+    }
+
+    // Status bar, or an alert in it; alerts stay visible without colors
+    pub fn status_bar(&self, alert: bool) -> Style {
+        match (alert, self.colors) {
+            (true, true) => self.fg_bg(self.palette.alert_fg, self.palette.alert_bg),
+            (true, false) => Style::default().add_modifier(Modifier::REVERSED),
+            (false, _) => self.fg_bg(self.palette.status_fg, self.palette.status_bg),
+        }
+    }
+
+    pub fn status_color(&self, status: &TaskStatus) -> Color {
+        match status {
+            TaskStatus::Pending => self.palette.pending,
+            TaskStatus::Processing => self.palette.running,
+            TaskStatus::Completed => self.palette.success,
+            TaskStatus::Failed(_) => self.palette.error,
+            TaskStatus::Cancelled => self.palette.cancelled,
+            TaskStatus::Interrupted => self.palette.interrupted,
+        }
+    }
+
+    pub fn status_symbol(&self, status: &TaskStatus) -> &'static str {
+        match (status, self.unicode) {
+            (TaskStatus::Pending, true) => "⏸",
+            (TaskStatus::Processing, true) => "⏳",
+            (TaskStatus::Completed, true) => "✓",
+            (TaskStatus::Failed(_), true) => "✗",
+            (TaskStatus::Cancelled, true) => "⊘",
+            (TaskStatus::Interrupted, true) => "↻",
+            (TaskStatus::Pending, false) => "[ ]",
+            (TaskStatus::Processing, false) => "[~]",
+            (TaskStatus::Completed, false) => "[+]",
+            (TaskStatus::Failed(_), false) => "[x]",
+            (TaskStatus::Cancelled, false) => "[-]",
+            (TaskStatus::Interrupted, false) => "[!]",
+        }
+    }
+
+    pub fn operation_symbol(&self, operation: Operation) -> &'static str {
+        match (operation, self.unicode) {
+            (Operation::Scrape, true) => "🔥",
+            (Operation::Crawl, true) => "🕷️",
+            (Operation::Scrape, false) => "S",
+            (Operation::Crawl, false) => "C",
+        }
+    }
+
+    // Marker of a tree node: expanded, collapsed, or a leaf when None
+    pub fn tree_marker(&self, expanded: Option<bool>) -> &'static str {
+        match (expanded, self.unicode) {
+            (Some(true), true) => "▾",
+            (Some(false), true) => "▸",
+            (None, true) => "·",
+            (Some(true), false) => "-",
+            (Some(false), false) => "+",
+            (None, false) => " ",
+        }
+    }
+
+    // Bars of the dashboard sparklines
+    pub fn bar_set(&self) -> symbols::bar::Set {
+        if self.unicode {
+            symbols::bar::NINE_LEVELS
+        } else {
+            symbols::bar::Set {
+                full: "#",
+                seven_eighths: "#",
+                three_quarters: "=",
+                five_eighths: "=",
+                half: "-",
+                three_eighths: "-",
+                one_quarter: ".",
+                one_eighth: ".",
+                empty: " ",
+            }
+        }
+    }
+
+    pub fn bullet(&self) -> &'static str {
+        if self.unicode { "•" } else { "*" }
+    }
+
+    pub fn rule(&self) -> &'static str {
+        if self.unicode { "─" } else { "-" }
+    }
+
+    pub fn quote_bar(&self) -> &'static str {
+        if self.unicode { "│" } else { "|" }
+    }
+
+    pub fn error_marker(&self) -> &'static str {
+        if self.unicode { "✗" } else { "x" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PaletteConfig;
+
+    #[test]
+    fn test_custom_palette_overrides_base() {
+        let mut ui = UiConfig::default();
+        ui.theme.color_scheme = "mine".to_string();
+        ui.theme.palettes.insert(
+            "mine".to_string(),
+            PaletteConfig {
+                base: Some("light".to_string()),
+                colors: [
+                    ("title".to_string(), "magenta".to_string()),
+                    ("error".to_string(), "#ff5555".to_string()),
+                ]
+                .into(),
+            },
+        );
+
+        let theme = Theme::from_config_with(&ui, false).unwrap();
+        assert_eq!(theme.palette.title, Color::Magenta);
+        assert_eq!(theme.palette.error, Color::Rgb(0xff, 0x55, 0x55));
+        assert_eq!(theme.palette.link, Palette::light().link);
+
+        ui.theme.palettes.get_mut("mine").unwrap().colors.insert("border".to_string(), "red".to_string());
+        assert_eq!(
+            Theme::from_config_with(&ui, false).unwrap_err(),
+            "unknown palette role 'border'"
+        );
+    }
+
+    #[test]
+    fn test_no_color_and_ascii_markers() {
+        let mut ui = UiConfig::default();
+        ui.theme.color_scheme = "high-contrast".to_string();
+        ui.theme.use_unicode = false;
+
+        let theme = Theme::from_config_with(&ui, true).unwrap();
+        assert_eq!(theme.fg(theme.palette.error), Style::default());
+        assert!(theme.selected().add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(theme.status_symbol(&TaskStatus::Completed), "[+]");
+        assert_eq!(theme.operation_symbol(Operation::Crawl), "C");
+
+        ui.enable_colors = false;
+        assert!(!Theme::from_config_with(&ui, false).unwrap().colors);
+        ui.theme.color_scheme = "solarized".to_string();
+        assert!(Theme::from_config_with(&ui, false).is_err());
+    }
+}
@@ -1,13 +1,26 @@
-use crate::tui::app::{App, Mode, Operation, TaskStatus};
+use crate::api::CrawlResponse;
+use crate::tui::app::{
+    App, CrawlStats, Mode, Operation, PreviewView, Task, TaskResult, TaskStatus,
+};
+use crate::tui::dashboard::{Dashboard, Tab, SAMPLE_INTERVAL};
+use crate::tui::export::SaveScope;
+use crate::tui::filter::SortKey;
+use crate::tui::form::OptionsForm;
+use crate::tui::keymap::{Action, KeyContext, Keymap};
+use crate::tui::markdown::render_markdown;
+use crate::tui::mouse::PaneLayout;
+use crate::tui::theme::Theme;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::Line,
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, ListState, Paragraph, Wrap,
+        Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, RenderDirection,
+        Sparkline, Tabs, Wrap,
     },
     Frame,
 };
+use std::time::Duration;
 
 pub fn ui(f: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
@@ -19,138 +32,950 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         ])
         .split(f.area());
 
-    render_title_bar(f, chunks[0]);
-    render_main_content(f, app, chunks[1]);
+    render_title_bar(f, &app.theme, app.tab, chunks[0]);
+    app.layout = PaneLayout {
+        body: chunks[1],
+        ..PaneLayout::default()
+    };
+    if app.show_logs {
+        let logs = app.splits.logs;
+        let body = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(100 - logs), Constraint::Percentage(logs)])
+            .split(chunks[1]);
+        render_main_content(f, app, body[0]);
+        render_log_panel(f, app, body[1]);
+        app.layout.logs = Some(body[1]);
+    } else {
+        render_main_content(f, app, chunks[1]);
+    }
     render_status_bar(f, app, chunks[2]);
+
+    if let Some(form) = &app.options_form {
+        render_options_form(f, &app.theme, form);
+    }
+    if app.show_help {
+        // The site map has keys of its own
+        let context = match app.mode {
+            Mode::SiteMap => KeyContext::SiteMap,
+            _ => KeyContext::Normal,
+        };
+        render_help(f, &app.theme, &app.keymap, context);
+    }
+    if let Some(toast) = &app.toast {
+        render_toast(f, &app.theme, &toast.message, toast.is_error, chunks[2]);
+    }
 }
 
-fn render_title_bar(f: &mut Frame, area: Rect) {
-    let title = Paragraph::new("Firecrawl CLI - Terminal UI")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
-        .block(Block::default().borders(Borders::ALL))
-        .wrap(Wrap { trim: true });
-    f.render_widget(title, area);
+fn render_title_bar(f: &mut Frame, theme: &Theme, tab: Tab, area: Rect) {
+    let selected = Tab::ALL.iter().position(|t| *t == tab);
+    let tabs = Tabs::new(Tab::ALL.map(Tab::label))
+        .select(selected)
+        .divider(theme.quote_bar())
+        .highlight_style(theme.selected())
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Firecrawl CLI - Terminal UI")
+                .title_style(theme.fg(theme.palette.title).add_modifier(Modifier::BOLD)),
+        );
+    f.render_widget(tabs, area);
 }
 
 fn render_main_content(f: &mut Frame, app: &mut App, area: Rect) {
+    if app.tab == Tab::Dashboard {
+        render_dashboard(f, &app.dashboard, &app.theme, app.running_task_count(), area);
+        return;
+    }
+
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .constraints([
+            Constraint::Percentage(app.splits.task_list),
+            Constraint::Percentage(100 - app.splits.task_list),
+        ])
         .split(area);
 
     render_task_list(f, app, main_chunks[0]);
-    render_task_details(f, app, main_chunks[1]);
+    app.layout.task_list = main_chunks[0];
+
+    if app.site_map.is_some() {
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(main_chunks[1]);
+        render_site_map(f, app, right_chunks[0]);
+        render_site_page(f, app, right_chunks[1]);
+        app.layout.details = right_chunks[0];
+        app.layout.preview = Some(right_chunks[1]);
+        return;
+    }
+
+    // Split the right side between details and the result preview
+    let has_result = app
+        .selected()
+        .is_some_and(|task| task.result.is_some());
+    if has_result {
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(main_chunks[1]);
+        render_task_details(f, app, right_chunks[0]);
+        render_preview(f, app, right_chunks[1]);
+        app.layout.details = right_chunks[0];
+        app.layout.preview = Some(right_chunks[1]);
+    } else {
+        render_task_details(f, app, main_chunks[1]);
+        app.layout.details = main_chunks[1];
+    }
 }
 
 fn render_task_list(f: &mut Frame, app: &mut App, area: Rect) {
-    let tasks: Vec<ListItem> = app
-        .tasks
+    let visible = app.visible_tasks();
+    let tasks: Vec<ListItem> = visible
         .iter()
-        .enumerate()
-        .map(|(i, task)| {
-            let (color, symbol) = match task.status {
-                TaskStatus::Pending => (Color::Yellow, "⏸"),
-                TaskStatus::Processing => (Color::Blue, "⏳"),
-                TaskStatus::Completed => (Color::Green, "✓"),
-                TaskStatus::Failed(_) => (Color::Red, "✗"),
-            };
-
-            let operation_symbol = match task.operation {
-                Operation::Scrape => "🔥",
-                Operation::Crawl => "🕷️",
-            };
-
+        .map(|&i| {
+            let task = &app.tasks[i];
+            let theme = &app.theme;
             let content = format!(
                 "{} {} [{}] {}",
-                symbol, operation_symbol, task.id, task.url
+                theme.status_symbol(&task.status),
+                theme.operation_symbol(task.operation),
+                task.id,
+                task.url
             );
 
             let style = if i == app.selected_task {
-                Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+                theme.selected()
             } else {
-                Style::default().fg(color)
+                theme.fg(theme.status_color(&task.status))
             };
 
             ListItem::new(content).style(style)
         })
         .collect();
 
+    // Shown/total count, active filters and a non-default sort
+    let mut title = if app.filter.is_active() {
+        format!("Tasks {}/{} {}", visible.len(), app.tasks.len(), app.filter.describe())
+    } else {
+        format!("Tasks {}", app.tasks.len())
+    };
+    if app.sort != SortKey::Id || app.sort_descending {
+        let order = if app.sort_descending { "desc" } else { "asc" };
+        title.push_str(&format!(" | sort: {} {}", app.sort.label(), order));
+    }
     let tasks_list = List::new(tasks)
-        .block(Block::default().borders(Borders::ALL).title("Tasks"));
-    
-    let mut list_state = ListState::default();
-    list_state.select(Some(app.selected_task));
-    
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+    // Keep the previous offset so the list only scrolls once the selection
+    // would leave the viewport
+    let mut list_state = ListState::default()
+        .with_offset(app.list_offset)
+        .with_selected(visible.iter().position(|&i| i == app.selected_task));
+
     f.render_stateful_widget(tasks_list, area, &mut list_state);
+    app.list_offset = list_state.offset();
 }
 
 fn render_task_details(f: &mut Frame, app: &mut App, area: Rect) {
-    let details = if let Some(task) = app.tasks.get(app.selected_task) {
-        let status_text = match &task.status {
-            TaskStatus::Pending => "Status: Pending",
-            TaskStatus::Processing => "Status: Processing",
-            TaskStatus::Completed => "Status: Completed",
-            TaskStatus::Failed(msg) => &format!("Status: Failed - {}", msg),
-        };
+    let block = Block::default().borders(Borders::ALL).title("Task Details");
 
-        let operation_text = match task.operation {
-            Operation::Scrape => "Operation: Scrape",
-            Operation::Crawl => "Operation: Crawl",
-        };
+    let Some(task) = app.selected() else {
+        let empty = Paragraph::new("No task selected").block(block);
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let status_text = match &task.status {
+        TaskStatus::Pending => "Status: Pending".to_string(),
+        TaskStatus::Processing => format!("Status: Processing - {}", task.progress),
+        TaskStatus::Completed => "Status: Completed".to_string(),
+        TaskStatus::Failed(msg) => format!("Status: Failed - {}", msg),
+        TaskStatus::Cancelled => "Status: Cancelled".to_string(),
+        TaskStatus::Interrupted => format!("Status: Interrupted - {}", task.progress),
+    };
+
+    let operation_text = match task.operation {
+        Operation::Scrape => "Operation: Scrape".to_string(),
+        Operation::Crawl => format!("Operation: Crawl (limit: {} pages)", task.options.limit),
+    };
+
+    let mut content = vec![
+        Line::from(format!("Task ID: {}", task.id)),
+        Line::from(operation_text),
+        Line::from(format!("URL: {}", task.url)),
+        Line::from(status_text),
+        Line::from(options_summary(task)),
+    ];
+
+    if let Some(result) = &task.result {
+        content.push(Line::from(""));
+        content.push(Line::from("Result:"));
+        match result {
+            TaskResult::Scrape(scrape_result) => {
+                content.push(Line::from(format!("  URL: {}", scrape_result.url.as_deref().unwrap_or("N/A"))));
+                content.push(Line::from(format!("  HTML length: {} chars",
+                    scrape_result.html.as_ref().map_or(0, |h| h.len()))));
+            }
+            TaskResult::Crawl(crawl_results) => {
+                content.push(Line::from(format!("  Pages crawled: {}", crawl_results.len())));
+            }
+        }
+    }
 
-        let mut content = vec![
-            Line::from(format!("Task ID: {}", task.id)),
-            Line::from(operation_text.to_string()),
-            Line::from(format!("URL: {}", task.url)),
-            Line::from(status_text.to_string()),
-            Line::from(""),
-        ];
-
-        if let Some(result) = &task.result {
-            content.push(Line::from("Result:"));
-            match result {
-                crate::tui::app::TaskResult::Scrape(scrape_result) => {
-                    content.push(Line::from(format!("  URL: {}", scrape_result.url.as_deref().unwrap_or("N/A"))));
-                    content.push(Line::from(format!("  HTML length: {} chars", 
-                        scrape_result.html.as_ref().map_or(0, |h| h.len()))));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(stats) = &task.crawl_stats else {
+        let details = Paragraph::new(content).wrap(Wrap { trim: true });
+        f.render_widget(details, inner);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(content.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(content), chunks[0]);
+    render_crawl_progress(f, &app.theme, stats, chunks[1], chunks[2], chunks[3]);
+}
+
+// Most recent log records at or above the panel's level filter
+fn render_log_panel(f: &mut Frame, app: &mut App, area: Rect) {
+    let entries = app.logs.entries(app.log_filter);
+    let visible = area.height.saturating_sub(2) as usize;
+
+    // Scrolled back from the newest record by the mouse wheel
+    app.log_scroll = app.log_scroll.min(entries.len().saturating_sub(visible));
+    let end = entries.len() - app.log_scroll;
+    let scrolled = if app.log_scroll > 0 {
+        format!(", {} newer hidden", app.log_scroll)
+    } else {
+        String::new()
+    };
+    let title = format!(
+        "Logs (≥ {}, {} shown{}) {}",
+        app.log_filter,
+        entries.len(),
+        scrolled,
+        key_labels(
+            &app.keymap,
+            &[(Action::ToggleLogs, "hide"), (Action::CycleLogFilter, "level")],
+        )
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    let lines: Vec<ListItem> = entries[..end]
+        .iter()
+        .skip(end.saturating_sub(visible))
+        .map(|entry| {
+            let theme = &app.theme;
+            let color = match entry.level {
+                log::Level::Error => theme.palette.error,
+                log::Level::Warn => theme.palette.warning,
+                log::Level::Info => theme.palette.success,
+                log::Level::Debug | log::Level::Trace => theme.palette.muted,
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", entry.time.format("%H:%M:%S")),
+                    theme.fg(theme.palette.muted),
+                ),
+                Span::styled(format!("{:<5} ", entry.level), theme.fg(color)),
+                Span::raw(entry.message.clone()),
+            ]))
+        })
+        .collect();
+    f.render_widget(List::new(lines).block(block), area);
+}
+
+// One-line summary of the options the task runs with
+fn options_summary(task: &Task) -> String {
+    let options = &task.options;
+    let formats: Vec<String> = options.formats.iter().map(|f| f.to_string()).collect();
+    let mut parts = vec![format!("Options: {}", formats.join(","))];
+    if options.only_main_content {
+        parts.push("main content".to_string());
+    }
+    if task.operation == Operation::Crawl {
+        if !options.include_paths.is_empty() {
+            parts.push(format!("paths +{}", options.include_paths.join(",")));
+        }
+        if !options.exclude_paths.is_empty() {
+            parts.push(format!("paths -{}", options.exclude_paths.join(",")));
+        }
+    }
+    if let Some(wait_for) = options.wait_for {
+        parts.push(format!("wait {}ms", wait_for));
+    }
+    if options.mobile {
+        parts.push("mobile".to_string());
+    }
+    if let Some(proxy) = &options.proxy {
+        parts.push(format!("proxy {:?}", proxy).to_lowercase());
+    }
+    if let Some(location) = &options.location {
+        parts.push(format!("location {}", location.country));
+    }
+    parts.join(" | ")
+}
+
+// Gauge, rate/ETA line and the stream of completed pages of a crawl
+fn render_crawl_progress(
+    f: &mut Frame,
+    theme: &Theme,
+    stats: &CrawlStats,
+    gauge_area: Rect,
+    rate_area: Rect,
+    pages_area: Rect,
+) {
+    let gauge = Gauge::default()
+        .gauge_style(theme.fg_bg(theme.palette.success, theme.palette.gauge_bg))
+        .ratio(stats.ratio())
+        .label(format!("{}/{} pages", stats.completed, stats.total));
+    f.render_widget(gauge, gauge_area);
+
+    let eta = match stats.eta() {
+        Some(eta) if stats.completed < stats.total => format_duration(eta),
+        Some(_) => "done".to_string(),
+        None => "--:--".to_string(),
+    };
+    let rate = Paragraph::new(format!(
+        "{:.1} pages/min | ETA {}",
+        stats.pages_per_minute(),
+        eta
+    ));
+    f.render_widget(rate, rate_area);
+
+    // Keep the most recent pages in view as new ones stream in
+    let block = Block::default().borders(Borders::TOP).title("Completed pages");
+    let visible = block.inner(pages_area).height as usize;
+    let skip = stats.pages.len().saturating_sub(visible);
+    let pages: Vec<ListItem> = stats
+        .pages
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(i, url)| ListItem::new(format!("{:>4}. {}", i + 1, url)))
+        .collect();
+    f.render_widget(List::new(pages).block(block), pages_area);
+}
+
+fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(result) = app
+        .selected()
+        .and_then(|task| task.result.as_ref())
+    else {
+        return;
+    };
+    let lines = preview_lines(result, app.preview_view, &app.theme);
+
+    // Keep at least the last line on screen
+    app.scroll_offset = app.scroll_offset.min(lines.len().saturating_sub(1));
+
+    let title = format!(
+        "Preview: {} [v] switch view [PgUp/PgDn] scroll ({}/{})",
+        app.preview_view.title(),
+        (app.scroll_offset + 1).min(lines.len()),
+        lines.len()
+    );
+    let preview = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false })
+        .scroll((app.scroll_offset.min(u16::MAX as usize) as u16, 0));
+    f.render_widget(preview, area);
+}
+
+// Tree of the crawled pages by host and path, with page counts per subtree
+fn render_site_map(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(map) = app.site_map.as_mut() else {
+        return;
+    };
+    let theme = &app.theme;
+    let rows = map.rows();
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|row| {
+            let node = row.node;
+            let mut spans = vec![
+                Span::raw("  ".repeat(row.depth)),
+                Span::styled(
+                    format!("{} ", theme.tree_marker(row.expanded)),
+                    theme.fg(theme.palette.accent),
+                ),
+            ];
+            let label_style = if node.page.is_some() {
+                Style::default()
+            } else {
+                theme.fg(theme.palette.muted)
+            };
+            spans.push(Span::styled(node.label.clone(), label_style));
+            if !node.children.is_empty() {
+                spans.push(Span::styled(
+                    format!(" ({})", node.count),
+                    theme.fg(theme.palette.muted),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = format!(
+        "Site map: task {}, {} pages {}",
+        map.task_id,
+        map.page_count(),
+        key_labels(
+            &app.keymap,
+            &[
+                (Action::SiteMapScrape, "scrape"),
+                (Action::SiteMapCrawl, "crawl subtree"),
+                (Action::SiteMapFold, "fold"),
+                (Action::SiteMapClose, "close"),
+            ],
+        )
+    );
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(theme.selected());
+    let mut state = ListState::default()
+        .with_offset(map.offset)
+        .with_selected(Some(map.selected));
+    f.render_stateful_widget(list, area, &mut state);
+    map.offset = state.offset();
+}
+
+// Preview of the page at the selected site map node
+fn render_site_page(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(map) = &app.site_map else {
+        return;
+    };
+    let Some(node) = map.selected_node() else {
+        return;
+    };
+    let page = app
+        .tasks
+        .iter()
+        .find(|task| task.id == map.task_id)
+        .and_then(|task| match (&task.result, node.page) {
+            (Some(TaskResult::Crawl(pages)), Some(index)) => pages.get(index),
+            _ => None,
+        });
+    let lines = match page {
+        Some(page) => crawl_page_lines(page, app.preview_view, &app.theme),
+        None => vec![Line::from(format!(
+            "No page was crawled at {}; {} page(s) below it",
+            node.url, node.count
+        ))],
+    };
+    app.scroll_offset = app.scroll_offset.min(lines.len().saturating_sub(1));
+
+    let title = format!("Page: {} ({})", node.url, app.preview_view.title());
+    let preview = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false })
+        .scroll((app.scroll_offset.min(u16::MAX as usize) as u16, 0));
+    f.render_widget(preview, area);
+}
+
+// Lines shown in the preview pane for a result and view
+fn preview_lines(result: &TaskResult, view: PreviewView, theme: &Theme) -> Vec<Line<'static>> {
+    match result {
+        TaskResult::Scrape(data) => match view {
+            PreviewView::Markdown => data
+                .markdown
+                .as_deref()
+                .map(|markdown| render_markdown(markdown, theme))
+                .unwrap_or_else(|| vec![Line::from("No markdown content")]),
+            PreviewView::Html => plain_lines(
+                data.html.as_deref().or(data.raw_html.as_deref()),
+                "No HTML content",
+            ),
+            PreviewView::Links => match &data.links {
+                Some(links) if !links.is_empty() => links
+                    .iter()
+                    .enumerate()
+                    .map(|(i, link)| Line::from(format!("{:>4}. {}", i + 1, link)))
+                    .collect(),
+                _ => vec![Line::from("No links")],
+            },
+            PreviewView::Metadata => {
+                let mut fields: Vec<_> = data.metadata.extra.iter().collect();
+                fields.sort_by(|a, b| a.0.cmp(b.0));
+                if fields.is_empty() {
+                    vec![Line::from("No metadata")]
+                } else {
+                    fields
+                        .into_iter()
+                        .map(|(key, value)| metadata_line(key, &value.to_string()))
+                        .collect()
                 }
-                crate::tui::app::TaskResult::Crawl(crawl_results) => {
-                    content.push(Line::from(format!("  Pages crawled: {}", crawl_results.len())));
-                    for (i, result) in crawl_results.iter().take(5).enumerate() {
-                        content.push(Line::from(format!("  {}. {}", i + 1, result.url.as_deref().unwrap_or("N/A"))));
-                    }
-                    if crawl_results.len() > 5 {
-                        content.push(Line::from(format!("  ... and {} more", crawl_results.len() - 5)));
-                    }
+            }
+        },
+        TaskResult::Crawl(pages) => {
+            let mut lines: Vec<Line<'static>> = pages
+                .iter()
+                .flat_map(|page| crawl_page_lines(page, view, theme))
+                .collect();
+            if lines.is_empty() {
+                lines.push(Line::from("No pages"));
+            }
+            lines
+        }
+    }
+}
+
+// Lines of one crawled page in the given view
+fn crawl_page_lines(page: &CrawlResponse, view: PreviewView, theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    match view {
+        PreviewView::Markdown => {
+            lines.push(page_header(&page.url, theme));
+            lines.extend(
+                page.markdown
+                    .as_deref()
+                    .map(|markdown| render_markdown(markdown, theme))
+                    .unwrap_or_else(|| vec![Line::from("No markdown content")]),
+            );
+            lines.push(Line::from(""));
+        }
+        PreviewView::Html => {
+            lines.push(page_header(&page.url, theme));
+            lines.extend(plain_lines(page.html.as_deref(), "No HTML content"));
+            lines.push(Line::from(""));
+        }
+        PreviewView::Links => lines.push(Line::from(page.url.clone())),
+        PreviewView::Metadata => {
+            lines.push(page_header(&page.url, theme));
+            let metadata = &page.metadata;
+            let fields = [
+                ("title", metadata.title.clone()),
+                ("statusCode", metadata.status_code.map(|c| c.to_string())),
+                ("author", metadata.author.clone()),
+                ("siteName", metadata.site_name.clone()),
+                ("robots", metadata.robots.clone()),
+                ("ogImage", metadata.og_image.clone()),
+            ];
+            for (key, value) in fields {
+                if let Some(value) = value {
+                    lines.push(metadata_line(key, &value));
                 }
             }
+            lines.push(Line::from(""));
         }
+    }
+    lines
+}
+
+fn plain_lines(content: Option<&str>, empty: &str) -> Vec<Line<'static>> {
+    match content {
+        Some(content) => content.lines().map(|line| Line::from(line.to_string())).collect(),
+        None => vec![Line::from(empty.to_string())],
+    }
+}
+
+fn page_header(url: &str, theme: &Theme) -> Line<'static> {
+    let rule = theme.rule().repeat(2);
+    Line::styled(
+        format!("{} {} {}", rule, url, rule),
+        theme.fg(theme.palette.accent).add_modifier(Modifier::BOLD),
+    )
+}
+
+fn metadata_line(key: &str, value: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{}: ", key), Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(value.to_string()),
+    ])
+}
+
+// Statistics of the progress service: totals and rates, failures by error
+// code, and sparklines of throughput and latency
+fn render_dashboard(f: &mut Frame, dashboard: &Dashboard, theme: &Theme, running: usize, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(8),
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ])
+        .split(area);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
 
-        content
+    let stats = &dashboard.statistics;
+    let label = |text: &str| Span::styled(format!("{:<14}", text), theme.fg(theme.palette.muted));
+    let average = if stats.timed_tasks == 0 {
+        "-".to_string()
     } else {
-        vec![Line::from("No task selected")]
+        format_latency(stats.average_execution_time)
     };
+    let summary = vec![
+        Line::from(vec![
+            label("Tasks"),
+            Span::raw(format!(
+                "{} ({} scrape, {} crawl)",
+                stats.total_tasks, stats.scrape_tasks, stats.crawl_tasks
+            )),
+        ]),
+        Line::from(vec![label("Running"), Span::raw(running.to_string())]),
+        Line::from(vec![
+            label("Completed"),
+            Span::styled(stats.completed_tasks.to_string(), theme.fg(theme.palette.success)),
+        ]),
+        Line::from(vec![
+            label("Failed"),
+            Span::styled(stats.failed_tasks.to_string(), theme.fg(theme.palette.error)),
+        ]),
+        Line::from(vec![label("Success rate"), Span::raw(format!("{:.1}%", stats.success_rate()))]),
+        Line::from(vec![label("Average time"), Span::raw(average)]),
+    ];
+    f.render_widget(
+        Paragraph::new(summary).block(Block::default().borders(Borders::ALL).title("Statistics")),
+        top[0],
+    );
 
-    let details_paragraph = Paragraph::new(details)
-        .block(Block::default().borders(Borders::ALL).title("Task Details"))
-        .wrap(Wrap { trim: true });
+    // One bar per error code, scaled to the most frequent one
+    let errors_block = Block::default().borders(Borders::ALL).title("Errors by code");
+    let breakdown = dashboard.error_breakdown();
+    let code_width = breakdown.iter().map(|(code, _)| code.len()).max().unwrap_or(0);
+    let bar_width = (errors_block.inner(top[1]).width as usize).saturating_sub(code_width + 7);
+    let most = breakdown.first().map_or(1, |(_, count)| *count);
+    let errors: Vec<Line> = if breakdown.is_empty() {
+        vec![Line::styled("No failed tasks", theme.fg(theme.palette.muted))]
+    } else {
+        breakdown
+            .iter()
+            .map(|(code, count)| {
+                let bar = theme.bar_set().full.repeat((count * bar_width).div_ceil(most));
+                Line::from(vec![
+                    Span::raw(format!("{:<width$} {:>4} ", code, count, width = code_width)),
+                    Span::styled(bar, theme.fg(theme.palette.error)),
+                ])
+            })
+            .collect()
+    };
+    f.render_widget(Paragraph::new(errors).block(errors_block), top[1]);
 
-    f.render_widget(details_paragraph, area);
+    // Newest samples on the right; the sparklines take as many as fit
+    let throughput: Vec<u64> = dashboard.throughput.iter().rev().copied().collect();
+    let peak = throughput.iter().max().copied().unwrap_or(0);
+    let throughput_block = Block::default().borders(Borders::ALL).title(format!(
+        "Throughput: tasks finished per {}s (peak {})",
+        SAMPLE_INTERVAL.as_secs(),
+        peak
+    ));
+    f.render_widget(
+        Sparkline::default()
+            .block(throughput_block)
+            .data(throughput)
+            .direction(RenderDirection::RightToLeft)
+            .bar_set(theme.bar_set())
+            .style(theme.fg(theme.palette.success)),
+        rows[1],
+    );
+
+    let latencies: Vec<u64> = dashboard.latencies_ms().into_iter().rev().collect();
+    let latency_title = match stats.recent_latencies.iter().max() {
+        Some(slowest) => format!(
+            "Latency of the last {} tasks (slowest {})",
+            latencies.len(),
+            format_latency(*slowest)
+        ),
+        None => "Latency: no finished tasks yet".to_string(),
+    };
+    f.render_widget(
+        Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(latency_title))
+            .data(latencies)
+            .direction(RenderDirection::RightToLeft)
+            .bar_set(theme.bar_set())
+            .style(theme.fg(theme.palette.running)),
+        rows[2],
+    );
+}
+
+// Milliseconds below a second, seconds with a decimal above
+fn format_latency(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{} ms", duration.as_millis())
+    } else {
+        format!("{:.1} s", duration.as_secs_f64())
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
 }
 
 fn render_status_bar(f: &mut Frame, app: &mut App, area: Rect) {
     let status_text: String = match app.mode {
-        Mode::Normal => {
-            "Commands: [q]uit [a]dd scrape [c]rawl [p]rocess [↑↓]navigate".to_string()
-        }
+        Mode::Normal => key_hints(&app.keymap),
         Mode::Input => {
-            format!("Enter URL: {} [Enter] to submit [Esc] to cancel", app.input)
+            let operation = match app.input_operation {
+                Operation::Scrape => "scrape",
+                Operation::Crawl => "crawl",
+            };
+            format!(
+                "New {} URL: {} [Tab] scrape/crawl [Enter] to submit [Esc] to cancel",
+                operation, app.input
+            )
+        }
+        Mode::Save => match &app.save_form {
+            Some(form) => {
+                let scope = match form.scope {
+                    SaveScope::Selected => "selected result",
+                    SaveScope::AllCompleted => "all completed results",
+                };
+                format!(
+                    "Save {} as <{}> to: {}_ [Tab/←→] format [Enter] save [Esc] cancel",
+                    scope, form.format, form.directory
+                )
+            }
+            None => String::new(),
+        },
+        Mode::Filter => {
+            let kind = if app.filter.regex { "regex" } else { "substring" };
+            let mut text = format!(
+                "Filter URLs ({}): {}_ [Tab] substring/regex [Enter] keep [Esc] clear",
+                kind, app.filter.query
+            );
+            if let Some(error) = &app.filter.error {
+                text.push_str(&format!(" | invalid regex: {}", error.lines().last().unwrap_or(error)));
+            }
+            text
+        }
+        Mode::SiteMap => key_labels(
+            &app.keymap,
+            &[
+                (Action::SiteMapNext, "next"),
+                (Action::SiteMapPrevious, "previous"),
+                (Action::SiteMapFold, "fold"),
+                (Action::SiteMapCollapse, "collapse"),
+                (Action::SiteMapExpand, "expand"),
+                (Action::SiteMapScrape, "scrape"),
+                (Action::SiteMapCrawl, "crawl subtree"),
+                (Action::CycleView, "view"),
+                (Action::SiteMapClose, "close"),
+                (Action::Help, "help"),
+            ],
+        ),
+        Mode::EditOptions => {
+            "[Tab/↑↓] field [Space/←→] change [Enter] save [Esc] cancel".to_string()
         }
         Mode::Processing => "Processing task...".to_string(),
     };
 
+    let running = app.running_task_count();
+    let status_text = if running > 0 {
+        format!("{} | {} running", status_text, running)
+    } else {
+        status_text
+    };
+
+    // Surface the latest error instead of the key hints until the next one
+    let (status_text, style) = match (&app.mode, &app.status_message) {
+        (Mode::Normal, Some(message)) => (
+            format!("{} | {}", message, status_text),
+            app.theme.status_bar(true),
+        ),
+        _ => (status_text, app.theme.status_bar(false)),
+    };
+
     let status_paragraph = Paragraph::new(status_text)
-        .style(Style::default().fg(Color::Black).bg(Color::Gray))
+        .style(style)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(status_paragraph, area);
 }
+
+// Most used commands with their current keys, ending with the help key
+fn key_hints(keymap: &Keymap) -> String {
+    let hints = key_labels(
+        keymap,
+        &[
+            (Action::AddScrape, "scrape"),
+            (Action::AddCrawl, "crawl"),
+            (Action::Process, "process"),
+            (Action::RunAll, "run all"),
+            (Action::Cancel, "cancel"),
+            (Action::Save, "save"),
+            (Action::Dashboard, "dashboard"),
+            (Action::Quit, "quit"),
+            (Action::Help, "help"),
+        ],
+    );
+    format!("Commands: {}", hints)
+}
+
+// `[keys] label` for each bound action, skipping unbound ones
+fn key_labels(keymap: &Keymap, actions: &[(Action, &str)]) -> String {
+    actions
+        .iter()
+        .filter_map(|(action, label)| {
+            let keys = keymap.keys_label(*action);
+            (!keys.is_empty()).then(|| format!("[{}] {}", keys, label))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Every action bound in the current view with its keys, centered over the UI
+fn render_help(f: &mut Frame, theme: &Theme, keymap: &Keymap, context: KeyContext) {
+    let entries = keymap.help_entries(context);
+    let key_width = entries.iter().map(|(keys, _)| keys.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = entries
+        .into_iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}  ", keys, width = key_width),
+                    theme.fg(theme.palette.highlight).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(description),
+            ])
+        })
+        .collect();
+
+    let dismiss = match context {
+        KeyContext::Normal => Action::Dismiss,
+        KeyContext::SiteMap => Action::SiteMapClose,
+    };
+    let close = [keymap.keys_label(Action::Help), keymap.keys_label(dismiss)]
+        .into_iter()
+        .filter(|keys| !keys.is_empty())
+        .collect::<Vec<_>>()
+        .join(" or ");
+    let area = centered_rect(f.area(), 60, lines.len() as u16 + 2);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.fg(theme.palette.accent))
+                .title(format!("Keys ({} to close)", close)),
+        ),
+        area,
+    );
+}
+
+// Modal editor for the options of a pending task, centered over the UI
+fn render_options_form(f: &mut Frame, theme: &Theme, form: &OptionsForm) {
+    let label_width = form
+        .entries
+        .iter()
+        .map(|entry| entry.field.label().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line> = form
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let focused = i == form.focused;
+            let marker = if focused { "> " } else { "  " };
+            let value = if entry.is_choice() {
+                format!("< {} >", entry.value)
+            } else if focused {
+                format!("{}_", entry.value)
+            } else {
+                entry.value.clone()
+            };
+            let value_style = if focused {
+                theme.fg(theme.palette.highlight).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let mut spans = vec![
+                Span::raw(format!("{}{:<width$}  ", marker, entry.field.label(), width = label_width)),
+                Span::styled(value, value_style),
+            ];
+            if let Some(error) = &entry.error {
+                spans.push(Span::styled(
+                    format!("  {} {}", theme.error_marker(), error),
+                    theme.fg(theme.palette.error),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    if let Some(entry) = form.entries.get(form.focused) {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            entry.field.hint(),
+            theme.fg(theme.palette.muted),
+        ));
+    }
+
+    let operation = match form.operation {
+        Operation::Scrape => "Scrape",
+        Operation::Crawl => "Crawl",
+    };
+    let title = format!("{} options for task {}", operation, form.task_id);
+    let area = centered_rect(f.area(), 80, lines.len() as u16 + 2);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+// Rectangle of the given width percentage and height centered in `area`
+fn centered_rect(area: Rect, percent_x: u16, height: u16) -> Rect {
+    let width = area.width * percent_x / 100;
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+// Notification box above the status bar, sized to its message
+fn render_toast(f: &mut Frame, theme: &Theme, message: &str, is_error: bool, status_area: Rect) {
+    let area = f.area();
+    let lines: Vec<&str> = message.lines().collect();
+    let longest = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let width = (longest as u16 + 4).min(area.width);
+    let height = (lines.len() as u16 + 2).min(status_area.y.saturating_sub(area.y));
+    let toast_area = Rect {
+        x: area.x + area.width - width,
+        y: status_area.y - height,
+        width,
+        height,
+    };
+
+    let (title, color) = if is_error {
+        ("Error", theme.palette.error)
+    } else {
+        ("Done", theme.palette.success)
+    };
+    f.render_widget(Clear, toast_area);
+    f.render_widget(
+        Paragraph::new(message.to_string()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.fg(color))
+                .title(title),
+        ),
+        toast_area,
+    );
+}
@@ -2,13 +2,13 @@ use anyhow::Result;
 use std::path::PathBuf;
 use tokio::fs;
 
-// Save markdown content to a file with metadata header
+// Save markdown content to a file with metadata header; returns the file path
 pub async fn save_markdown(
     dir: &PathBuf,
     url: &str,
     content: &str,
     title: Option<&str>,
-) -> Result<()> {
+) -> Result<PathBuf> {
     // Create output directory if it doesn't exist
     if !dir.exists() {
         fs::create_dir_all(dir).await?;
@@ -25,11 +25,11 @@ pub async fn save_markdown(
     // Write the content to file
     fs::write(&path, file_content).await?;
     println!("💾 Saved markdown: {:?}", path);
-    Ok(())
+    Ok(path)
 }
 
-// Save HTML content to a file with metadata header
-pub async fn save_html(dir: &PathBuf, url: &str, content: &str, title: Option<&str>) -> Result<()> {
+// Save HTML content to a file with metadata header; returns the file path
+pub async fn save_html(dir: &PathBuf, url: &str, content: &str, title: Option<&str>) -> Result<PathBuf> {
     // Create output directory if it doesn't exist
     if !dir.exists() {
         fs::create_dir_all(dir).await?;
@@ -46,16 +46,16 @@ pub async fn save_html(dir: &PathBuf, url: &str, content: &str, title: Option<&s
     // Write the content to file
     fs::write(&path, file_content).await?;
     println!("💾 Saved HTML: {:?}", path);
-    Ok(())
+    Ok(path)
 }
 
-// Save JSON metadata to a file
+// Save JSON metadata to a file; returns the file path
 pub async fn save_json(
     dir: &PathBuf,
     url: &str,
     data: &serde_json::Value,
     title: Option<&str>,
-) -> Result<()> {
+) -> Result<PathBuf> {
     // Create output directory if it doesn't exist
     if !dir.exists() {
         fs::create_dir_all(dir).await?;
@@ -70,5 +70,5 @@ pub async fn save_json(
     let file_content = serde_json::to_string_pretty(data)?;
     fs::write(&path, file_content).await?;
     println!("💾 Saved JSON: {:?}", path);
-    Ok(())
+    Ok(path)
 }